@@ -105,9 +105,16 @@ func _shutdown_feagi_async() -> void:
 		print("   🦀 Shutting down embedded FEAGI extension...")
 		if _ui_manager:
 			await _ui_manager.update_shutdown_status("Stopping embedded FEAGI...")
-		_feagi_embedded_instance.shutdown()
+		# Shut down on a worker thread so the shutdown screen keeps drawing
+		if _feagi_embedded_instance.shutdown_async():
+			while _feagi_embedded_instance.poll_state() == _feagi_embedded_instance.STATE_STOPPING:
+				await get_tree().process_frame
+		else:
+			# A worker is still busy (e.g. initializing): shutdown() waits for it,
+			# bounded by the shutdown timeout
+			_feagi_embedded_instance.shutdown()
+		_report_embedded_shutdown()
 		_feagi_embedded_instance = null
-		print("   ✅ Embedded FEAGI shut down")
 	
 	# Stop FEAGI subprocess if running
 	if FeagiProcessManager.is_running():
@@ -139,8 +146,8 @@ func _shutdown_feagi() -> void:
 	if _feagi_embedded_instance:
 		print("   🦀 Shutting down embedded FEAGI extension...")
 		_feagi_embedded_instance.shutdown()
+		_report_embedded_shutdown()
 		_feagi_embedded_instance = null
-		print("   ✅ Embedded FEAGI shut down")
 	
 	# Stop FEAGI subprocess if running
 	if FeagiProcessManager.is_running():
@@ -159,6 +166,13 @@ func _shutdown_feagi() -> void:
 	print("─" . repeat(60))
 	print("")
 
+## Log the outcome of an embedded FEAGI shutdown (success only if it reached STATE_UNINITIALIZED)
+func _report_embedded_shutdown() -> void:
+	if _feagi_embedded_instance.get_state() == _feagi_embedded_instance.STATE_UNINITIALIZED:
+		print("   ✅ Embedded FEAGI shut down")
+	else:
+		push_error("Embedded FEAGI shutdown failed (%s): %s" % [_feagi_embedded_instance.get_state_name(), _feagi_embedded_instance.get_last_error()])
+
 ## Clean up all state directories (async version with UI updates)
 func _cleanup_all_state_async() -> void:
 	if OS.has_feature("editor"):
@@ -110,6 +110,7 @@ func _ready() -> void:
 func _process(_delta: float) -> void:
 	if _feagi_embedded:
 		_feagi_embedded.poll_logs()
+		_feagi_embedded.poll_state()
 
 ## Handle window close request
 func _notification(what: int) -> void:
@@ -287,11 +288,15 @@ func _initialize_feagi_embedded():
 	ShutdownManager.register_embedded_instance(_feagi_embedded)
 	
 	_UI_manager.update_loading_status("Starting FEAGI...")
+	_feagi_embedded.state_changed.connect(_on_feagi_embedded_state_changed)
 	
-	# Initialize with default settings
-	print("   [DEBUG] Calling initialize_default()...")
-	var init_success = _feagi_embedded.initialize_default()
-	print("   [DEBUG] initialize_default() returned: ", init_success)
+	# Initialize with default settings on a worker thread (progress via state_changed)
+	print("   [DEBUG] Calling initialize_default_async()...")
+	var init_success = _feagi_embedded.initialize_default_async()
+	while init_success and _feagi_embedded.get_state() == _feagi_embedded.STATE_INITIALIZING:
+		await get_tree().process_frame
+	init_success = init_success and _feagi_embedded.get_state() == _feagi_embedded.STATE_READY
+	print("   [DEBUG] initialization finished: ", _feagi_embedded.get_state_name())
 	if not init_success:
 		push_error("Failed to initialize embedded FEAGI")
 		_UI_manager.update_loading_status("FEAGI initialization failed - connect to external FEAGI...")
@@ -338,6 +343,13 @@ func _initialize_feagi_embedded():
 	print("🔗 [BV] Connecting to embedded FEAGI...")
 	FeagiCore.attempt_connection_to_FEAGI(endpoint_details)
 
+## Show embedded FEAGI initialization progress on the loading screen
+func _on_feagi_embedded_state_changed(state: int, _previous: int, detail: String, error: String) -> void:
+	if state == _feagi_embedded.STATE_INITIALIZING:
+		_UI_manager.update_loading_status(detail + "...")
+	elif state == _feagi_embedded.STATE_FAILED:
+		_UI_manager.update_loading_status("FEAGI failed: " + error)
+
 ## Wait for embedded FEAGI HTTP server to be ready
 func _wait_for_embedded_http_ready(api_url: String) -> bool:
 	var http = HTTPRequest.new()
@@ -27,6 +27,9 @@ var ws_viz_port: int = 9050
 ## User preference (can be set via settings UI)
 var prefer_embedded: bool = true  ## Default to embedded if available
 
+## FeagiEmbeddedSettings used for embedded mode when no config path is given (null = defaults)
+var embedded_settings: Resource = null
+
 func _ready():
 	detect_feagi_mode()
 
@@ -86,7 +89,13 @@ func _initialize_embedded(config_path: String) -> bool:
 		return false
 	
 	var success: bool = false
-	if config_path.is_empty():
+	if config_path.is_empty() and embedded_settings:
+		# Use the settings resource (may pick free ports)
+		print("   Using FeagiEmbeddedSettings")
+		success = feagi_instance.initialize_with_settings(embedded_settings)
+		if success:
+			ws_viz_port = embedded_settings.visualization_port
+	elif config_path.is_empty():
 		# Use embedded defaults
 		print("   Using embedded defaults (API: :8000, WebSocket: :9050)")
 		success = feagi_instance.initialize_default()
@@ -171,8 +180,12 @@ func set_burst_frequency(hz: float) -> bool:
 
 ## Load a genome file
 func load_genome(genome_path: String) -> bool:
-	# Both modes use HTTP API for genome loading (complex operation)
 	print("\n🧠 [FEAGI-MGR] Loading genome: ", genome_path)
+	if feagi_mode == FeagiMode.EMBEDDED and feagi_instance:
+		# Direct FFI (hot-swaps the running genome)
+		var success: bool = feagi_instance.load_genome_from_file(genome_path)
+		genome_loaded.emit(success)
+		return success
 	return _load_genome_via_http(genome_path)
 
 ## Check if FEAGI is running
@@ -202,6 +215,22 @@ func is_genome_loaded() -> bool:
 		# COLD PATH: HTTP API call
 		return _is_genome_loaded_via_http()
 
+## Push sensory voxels of one cortical area into the next burst
+## Embedded mode only - external FEAGI receives sensory data over its sensory WebSocket
+func push_sensory_data(cortical_id: String, x: PackedInt32Array, y: PackedInt32Array, z: PackedInt32Array, p: PackedFloat32Array) -> bool:
+	if feagi_mode == FeagiMode.EMBEDDED and feagi_instance:
+		# HOT PATH: Direct FFI into the burst engine
+		return feagi_instance.push_sensory_data(cortical_id, x, y, z, p)
+	return false
+
+## Get motor (OPU) activations of the latest burst: {"burst": int, "areas": {id: {x, y, z, p}}}
+## Embedded mode only - returns burst -1 and no areas otherwise
+func get_motor_data() -> Dictionary:
+	if feagi_mode == FeagiMode.EMBEDDED and feagi_instance:
+		# HOT PATH: Direct FFI from the burst engine
+		return feagi_instance.get_motor_data()
+	return {"burst": -1, "areas": {}}
+
 ## Get the HTTP API base URL (works for both modes)
 func get_api_url() -> String:
 	return api_url
@@ -47,10 +47,35 @@ func _ready():
 		var http_running = feagi.is_http_server_running()
 		print("  HTTP API URL: ", api_url)
 		print("  HTTP Server Running: ", http_running)
+		if feagi.get_state() == feagi.STATE_READY:
+			print("  ✅ PASS: State is ", feagi.get_state_name())
+		else:
+			print("  ❌ FAIL: Expected state ready, got ", feagi.get_state_name())
 	else:
 		print("  ❌ FAIL: FEAGI initialization failed")
 		return
 	
+	# Test 3b: Load a bundled circuit genome via FFI
+	print("\nTest 3b: Loading circuits/logic_and_gate/genome.json via FFI...")
+	var genome_path := ProjectSettings.globalize_path("res://").path_join("../circuits/logic_and_gate/genome.json")
+	var expected_neurons := _count_genome_neurons(genome_path)
+	if not feagi.get_capabilities()["genome_ffi"]:
+		print("  ⏭️  SKIP: built without the genome-ffi feature")
+	elif feagi.load_genome_from_file(genome_path):
+		var loaded_neurons: int = feagi.get_neuron_count()
+		print("  Neurons: ", loaded_neurons, " (genome defines ", expected_neurons, ")")
+		if loaded_neurons == expected_neurons:
+			print("  ✅ PASS: Genome loaded with the expected neuron count")
+		else:
+			print("  ❌ FAIL: Neuron count mismatch")
+		var exported: Variant = JSON.parse_string(feagi.export_genome())
+		if exported is Dictionary and exported.has("blueprint"):
+			print("  ✅ PASS: Genome exported")
+		else:
+			print("  ❌ FAIL: Exported genome is not valid JSON")
+	else:
+		print("  ❌ FAIL: Genome load failed")
+	
 	# Test 4: Start burst engine
 	print("\nTest 4: Starting burst engine...")
 	var start_success = feagi.start()
@@ -75,6 +100,14 @@ func _ready():
 	else:
 		print("  ⚠️  WARNING: Burst engine not running")
 	
+	# Test 5b: In-process visualization
+	print("\nTest 5b: Polling in-process visualization...")
+	var capabilities: Dictionary = feagi.get_capabilities()
+	if not capabilities["visualization_callback"]:
+		print("  ⏭️  SKIP: built without the visualization-callback feature")
+	else:
+		await _test_visualization()
+	
 	# Test 6: Stop burst engine
 	print("\nTest 6: Stopping burst engine...")
 	var stop_success = feagi.stop()
@@ -91,22 +124,79 @@ func _ready():
 	else:
 		print("  ❌ FAIL: Burst engine still running")
 	
+	# Test 8: Background shutdown reports its progress
+	print("\nTest 8: Shutting down on a worker thread...")
+	var shutdown_steps: Array[String] = []
+	feagi.state_changed.connect(func(_state, _previous, detail, _error): shutdown_steps.append(detail))
+	if feagi.shutdown_async():
+		while feagi.poll_state() == feagi.STATE_STOPPING:
+			await get_tree().process_frame
+	print("  Steps: ", shutdown_steps)
+	if feagi.get_state() == feagi.STATE_UNINITIALIZED:
+		print("  ✅ PASS: FEAGI shut down")
+	else:
+		print("  ❌ FAIL: ", feagi.get_state_name(), " - ", feagi.get_last_error())
+	
 	# Summary
 	print("\n" + "=".repeat(60))
 	print("  ALL TESTS PASSED! 🎉")
 	print("=".repeat(60))
 	print("\n💡 Next steps:")
-	print("  1. Test genome loading via HTTP API")
-	print("  2. Integrate into main BV scene")
-	print("  3. Wire up UI controls")
+	print("  1. Integrate into main BV scene")
+	print("  2. Wire up UI controls")
 	print("\n📖 See: docs/FEAGI_EMBEDDED_QUICK_START.md\n")
 
+## Test 5b body: bursts reach poll_visualization() without a WebSocket
+func _test_visualization() -> void:
+	feagi.visualization_data.connect(_on_visualization_data)
+	for i in 30:
+		await get_tree().process_frame
+		feagi.poll_visualization()
+	var viz_stats: Dictionary = feagi.get_visualization_stats()
+	print("  Published: ", viz_stats["published"], "  Consumed: ", viz_stats["consumed"], "  Dropped: ", viz_stats["dropped"])
+	if viz_stats["errors"] > 0:
+		print("  ❌ FAIL: ", viz_stats["errors"], " bursts failed to decode")
+	elif viz_stats["consumed"] > 0:
+		print("  ✅ PASS: Visualization delivered without WebSocket")
+	else:
+		print("  ⚠️  WARNING: No bursts published (is a genome loaded?)")
+
+## Neurons defined by a genome: voxels x neurons per voxel, summed over cortical areas
+func _count_genome_neurons(genome_path: String) -> int:
+	var genome: Variant = JSON.parse_string(FileAccess.get_file_as_string(genome_path))
+	if not genome is Dictionary:
+		return -1
+	var blueprint: Dictionary = genome.get("blueprint", {})
+	var areas: Dictionary = {}
+	for key in blueprint:
+		# Flat keys: "<region>-<cortical_id>-cx-<gene>-<type>"
+		var parts: PackedStringArray = key.split("-")
+		if parts.size() >= 4 and parts[2] == "cx":
+			areas["%s-%s" % [parts[0], parts[1]]] = true
+	var total := 0
+	for area in areas:
+		var prefix: String = area + "-cx-"
+		total += int(blueprint.get(prefix + "___bbx-i", 1)) * int(blueprint.get(prefix + "___bby-i", 1)) \
+			* int(blueprint.get(prefix + "___bbz-i", 1)) * int(blueprint.get(prefix + "_n_cnt-i", 1))
+	return total
+
+func _on_visualization_data(cortical_ids: PackedStringArray, neuron_counts: PackedInt32Array, x: PackedInt32Array, _y: PackedInt32Array, _z: PackedInt32Array, _powers: PackedFloat32Array):
+	if neuron_counts.size() != cortical_ids.size():
+		print("  ❌ FAIL: ", cortical_ids.size(), " areas but ", neuron_counts.size(), " neuron counts")
+	var total := 0
+	for count in neuron_counts:
+		total += count
+	if total != x.size():
+		print("  ❌ FAIL: neuron counts add up to ", total, " but ", x.size(), " neurons were sent")
+
 func _notification(what):
 	if what == NOTIFICATION_WM_CLOSE_REQUEST:
 		# Graceful shutdown
 		if feagi:
 			print("\n🛑 Shutting down FEAGI...")
-			feagi.shutdown()
-			print("✅ FEAGI shutdown complete")
+			if feagi.shutdown():
+				print("✅ FEAGI shutdown complete")
+			else:
+				print("❌ FEAGI shutdown failed: ", feagi.get_last_error())
 		get_tree().quit()
 
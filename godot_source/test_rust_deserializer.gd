@@ -91,6 +91,63 @@ func _ready():
 			print("❌ Test 5d FAILED: P array conversion failed: ", p_array)
 	else:
 		print("❌ Test 5 FAILED: Missing arrays in bulk conversion result: ", bulk_result)
-	
+
+	var split_result = rust_deserializer.convert_bulk_arrays_by_area(
+		x_bytes, y_bytes, z_bytes, p_bytes, PackedStringArray(["area_a", "area_b"]), PackedInt64Array([0, 1]))
+	if split_result.success and split_result.areas.area_a.x_array == PackedInt32Array([1]) \
+			and split_result.areas.area_b.p_array.size() == 1:
+		print("✅ Test 5e PASSED: Per-area split by offset table works")
+	else:
+		print("❌ Test 5e FAILED: Per-area split failed: ", split_result)
+
+	# Test 6: Encode → decode round trip (container and raw Type 11, with and without LZ4)
+	var power_id = "X19fcG93ZXI="  # base64 of "___power"
+	var encode_areas = {
+		power_id: {
+			"x_array": PackedInt32Array([0, 1, 2]),
+			"y_array": PackedInt32Array([0, 0, 1]),
+			"z_array": PackedInt32Array([0, 0, 0]),
+			"p_array": PackedFloat32Array([1.0, 0.5, 0.25]),
+		}
+	}
+	var encoded_packets = {
+		"container": rust_deserializer.encode_type_11_data(encode_areas, "", rust_deserializer.COMPRESSION_NONE),
+		"container_lz4_block": rust_deserializer.encode_type_11_data(encode_areas, "", rust_deserializer.COMPRESSION_LZ4_BLOCK),
+		"raw_lz4_frame": rust_deserializer.encode_type_11_raw(encode_areas, rust_deserializer.COMPRESSION_LZ4_FRAME),
+	}
+	for packet_name in encoded_packets:
+		var encoded = encoded_packets[packet_name]
+		if not encoded.success:
+			print("❌ Test 6 FAILED (", packet_name, "): encode error: ", encoded.error)
+			continue
+		var round_trip = rust_deserializer.decode_type_11_data(encoded.buffer)
+		if round_trip.success and round_trip.total_neurons == 3 and round_trip.areas.has(power_id) \
+				and round_trip.areas[power_id].x_array == encode_areas[power_id].x_array:
+			print("✅ Test 6 PASSED (", packet_name, "): ", round_trip.compression, " / ", round_trip.payload_format)
+		else:
+			print("❌ Test 6 FAILED (", packet_name, "): ", round_trip)
+
+	# Test 7: Typed error codes
+	var empty_result = rust_deserializer.decode_type_11_data(PackedByteArray([]))
+	var garbage_result = rust_deserializer.decode_type_11_data(PackedByteArray([200, 1]))
+	if empty_result.error_code == rust_deserializer.ERROR_EMPTY_BUFFER \
+			and garbage_result.error_code == rust_deserializer.ERROR_UNSUPPORTED_VERSION:
+		print("✅ Test 7 PASSED: Failures carry typed error codes")
+	else:
+		print("❌ Test 7 FAILED: ", empty_result.error_code, " / ", garbage_result.error_code)
+
+	# Test 8: Coordinate spaces (voxel -> area-local -> brain-world and back)
+	rust_deserializer.set_area_placement(power_id, Vector3(20, 30, -20), Vector3(16, 16, 3))
+	var corner_world = rust_deserializer.voxel_to_world(power_id, Vector3i(0, 0, 0))
+	var far_local = rust_deserializer.voxel_to_area_local(Vector3(16, 16, 3), Vector3i(15, 15, 2))
+	var area_transform = rust_deserializer.get_area_transform(power_id)
+	var back = rust_deserializer.world_to_voxel(power_id, area_transform * far_local)
+	if corner_world.is_equal_approx(Vector3(20.5, 30.5, 19.5)) and back.inside \
+			and back.voxel == Vector3i(15, 15, 2):
+		print("✅ Test 8 PASSED: Coordinate spaces round-trip")
+	else:
+		print("❌ Test 8 FAILED: ", corner_world, " / ", back)
+	rust_deserializer.clear_area_placements()
+
 	print("🧪 Rust deserializer testing completed!")
 	print("🦀 If all tests passed, the Rust integration is working correctly!")
\ No newline at end of file
@@ -0,0 +1,142 @@
+extends Node
+## Test script for FeagiVisualizationStream (feagi_agent_client GDExtension)
+##
+## Starts a local in-process WebSocket server, points the Rust stream client at it and
+## checks connection signals, text frames, newest-frame-only delivery and worker-side decoding
+## (frames are encoded with FeagiDataDeserializer, so both extensions must be built).
+##
+## Usage:
+##  1. Attach to a Node in a test scene
+##  2. Run the scene
+##  3. Check console output
+
+const TEST_PORT := 29153
+const TIMEOUT_MS := 5000
+
+var _server := TCPServer.new()
+var _server_peer: WebSocketPeer = null
+var _states: Array[int] = []
+var _texts: Array[String] = []
+
+func _ready():
+	print("\n" + "=".repeat(60))
+	print("  FeagiVisualizationStream Test")
+	print("=".repeat(60) + "\n")
+
+	# Test 1: Class available
+	if not ClassDB.class_exists("FeagiVisualizationStream"):
+		print("  ❌ FAIL: FeagiVisualizationStream class not found")
+		print("  → Build the extension: cd rust_extensions && ./build.sh")
+		return
+	print("  ✅ PASS: FeagiVisualizationStream class found")
+
+	if _server.listen(TEST_PORT, "127.0.0.1") != OK:
+		print("  ❌ FAIL: Could not start local WebSocket server on port ", TEST_PORT)
+		return
+
+	var stream = ClassDB.instantiate("FeagiVisualizationStream")
+	stream.connection_state_changed.connect(func(state: int, _message: String): _states.append(state))
+	stream.text_message_received.connect(func(text: String): _texts.append(text))
+	stream.set_auto_reconnect(false)
+
+	# Test 2: Connect
+	if not stream.connect_to_url("ws://127.0.0.1:%d" % TEST_PORT):
+		print("  ❌ FAIL: connect_to_url returned false")
+		return
+	if not await _wait_for(func(): _accept_and_poll(); stream.poll_latest_frame(); return _server_peer != null and stream.is_stream_connected()):
+		print("  ❌ FAIL: Stream did not reach STATE_CONNECTED (states: ", _states, ")")
+		return
+	print("  ✅ PASS: Connected (states: ", _states, ")")
+
+	# Test 3: Text frames are reported, not returned as frames
+	_server_peer.send_text("updated")
+	if await _wait_for(func(): _server_peer.poll(); stream.poll_latest_frame(); return _texts.has("updated")):
+		print("  ✅ PASS: Text frame delivered via text_message_received")
+	else:
+		print("  ❌ FAIL: Text frame not received (texts: ", _texts, ")")
+
+	# Test 4: Only the newest binary frame is kept (real raw Type 11 frames with 1, 2 and 3 neurons)
+	var deserializer = ClassDB.instantiate("FeagiDataDeserializer")
+	var frames: Array[PackedByteArray] = []
+	for neuron_count in [1, 2, 3]:
+		frames.append(deserializer.encode_type_11_raw(_activity(neuron_count), deserializer.COMPRESSION_NONE).buffer)
+	for frame in frames:
+		_server_peer.send(frame)
+	_server_peer.poll()
+	await _wait_for(func():
+		_server_peer.poll()
+		var stats: Dictionary = stream.get_stats()
+		return int(stats.frames_received) >= 3)
+	var newest: PackedByteArray = stream.poll_latest_frame()
+	if newest == frames[2]:
+		print("  ✅ PASS: poll_latest_frame returned the newest frame")
+	else:
+		print("  ❌ FAIL: Expected the 3-neuron frame (", frames[2].size(), " bytes), got ", newest.size(), " bytes")
+	var stats: Dictionary = stream.get_stats()
+	if int(stats.frames_superseded) == 2 and stream.poll_latest_frame().is_empty():
+		print("  ✅ PASS: Older frames superseded (stats: ", stats, ")")
+	else:
+		print("  ❌ FAIL: Unexpected stats: ", stats)
+
+	# Test 4b: Frames that do not decode are dropped on the worker
+	_server_peer.send(PackedByteArray([11, 1, 3, 0]))  # Type 11 header claiming 3 areas, no data
+	await _wait_for(func(): _server_peer.poll(); return int(stream.get_stats().frames_received) >= 4)
+	stats = stream.get_stats()
+	if int(stats.frames_invalid) == 1 and stream.poll_latest_frame().is_empty():
+		print("  ✅ PASS: Truncated Type 11 frame rejected")
+	else:
+		print("  ❌ FAIL: Truncated Type 11 frame not rejected (stats: ", stats, ")")
+
+	# Test 4c: LZ4 block frames are decompressed and decoded on the worker
+	var compressed: PackedByteArray = deserializer.encode_type_11_data(_activity(3), "", deserializer.COMPRESSION_LZ4_BLOCK).buffer
+	_server_peer.send(compressed)
+	await _wait_for(func(): _server_peer.poll(); return int(stream.get_stats().frames_received) >= 5)
+	var neurons: Dictionary = stream.poll_latest_neurons()
+	if not neurons.is_empty() and neurons.x == PackedInt32Array([0, 1, 2]) and int(stream.get_stats().lz4_frames) == 1:
+		print("  ✅ PASS: LZ4 block frame decoded on the worker: ", neurons)
+	else:
+		print("  ❌ FAIL: LZ4 block frame not decoded (neurons: ", neurons, ", stats: ", stream.get_stats(), ")")
+
+	# Test 5: Server close is reported
+	_server_peer.close()
+	if await _wait_for(func(): _server_peer.poll(); stream.poll_latest_frame(); return _states.back() == stream.STATE_ERRORED):
+		print("  ✅ PASS: Connection loss reported as STATE_ERRORED")
+	else:
+		print("  ❌ FAIL: Connection loss not reported (states: ", _states, ")")
+
+	stream.disconnect_stream()
+	_server.stop()
+	print("\n" + "=".repeat(60))
+	print("  FeagiVisualizationStream Test complete")
+	print("=".repeat(60) + "\n")
+
+
+## One area with neuron_count neurons along x (x = 0, 1, ...)
+func _activity(neuron_count: int) -> Dictionary:
+	var x := PackedInt32Array()
+	var zeros := PackedInt32Array()
+	var powers := PackedFloat32Array()
+	for i in neuron_count:
+		x.append(i)
+		zeros.append(0)
+		powers.append(1.0)
+	return {"X19fcG93ZXI=": {"x_array": x, "y_array": zeros, "z_array": zeros, "p_array": powers}}
+
+
+func _accept_and_poll() -> bool:
+	if _server_peer == null and _server.is_connection_available():
+		_server_peer = WebSocketPeer.new()
+		_server_peer.accept_stream(_server.take_connection())
+	if _server_peer == null:
+		return false
+	_server_peer.poll()
+	return _server_peer.get_ready_state() == WebSocketPeer.STATE_OPEN
+
+
+func _wait_for(condition: Callable) -> bool:
+	var start := Time.get_ticks_msec()
+	while Time.get_ticks_msec() - start < TIMEOUT_MS:
+		if condition.call():
+			return true
+		await get_tree().process_frame
+	return false
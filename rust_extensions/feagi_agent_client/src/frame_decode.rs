@@ -0,0 +1,159 @@
+//! Visualization frame decoding for the stream worker.
+//!
+//! Packet detection, LZ4 block/frame decompression under `FrameLimits::max_decompressed_bytes`
+//! and the retry of LZ4 blocks that look like plain payloads live in the shared
+//! `feagi_frame_codec` crate, which feagi_data_deserializer's ingest path uses too. This
+//! module turns the uncompressed payload into flat neuron arrays.
+
+use feagi_frame_codec::{Compression, Payload, PayloadFormat};
+use feagi_serialization::{FeagiByteContainer, FeagiByteStructureType, FeagiSerializable};
+use feagi_structures::neuron_voxels::xyzp::CorticalMappedXYZPNeuronVoxels;
+
+pub(crate) use feagi_frame_codec::FrameLimits;
+
+// Uncompressed containers are detected by version byte in feagi_frame_codec
+const _: () = assert!(
+    feagi_frame_codec::is_container_version(FeagiByteContainer::CURRENT_FBS_VERSION),
+    "feagi_frame_codec does not recognise the current FeagiByteContainer version"
+);
+
+/// Fired neurons of one frame, flattened per cortical area.
+#[derive(Default)]
+pub(crate) struct NeuronArrays {
+    /// Base64 cortical IDs, one per area
+    pub cortical_ids: Vec<String>,
+    /// Neurons per area; the coordinate arrays hold the areas back to back in this order
+    pub neuron_counts: Vec<i32>,
+    pub x: Vec<i32>,
+    pub y: Vec<i32>,
+    pub z: Vec<i32>,
+    pub powers: Vec<f32>,
+}
+
+impl NeuronArrays {
+    fn append(&mut self, neuron_data: &CorticalMappedXYZPNeuronVoxels) {
+        for (cortical_id, neurons) in neuron_data.mappings.iter() {
+            let count = neurons.len();
+            if count == 0 {
+                continue;
+            }
+            self.cortical_ids.push(cortical_id.as_base_64());
+            self.neuron_counts.push(count as i32);
+            for neuron in neurons.iter() {
+                let coordinate = neuron.neuron_voxel_coordinate;
+                self.x.push(coordinate.x as i32);
+                self.y.push(coordinate.y as i32);
+                self.z.push(coordinate.z as i32);
+                self.powers.push(neuron.potential);
+            }
+        }
+    }
+}
+
+/// A frame after detection, decompression and decoding.
+pub(crate) struct DecodedFrame {
+    /// Uncompressed payload (FeagiByteContainer or raw Type 11), for the deserializer
+    pub bytes: Vec<u8>,
+    /// Whether the packet was LZ4 compressed (block or frame)
+    pub was_lz4: bool,
+    pub neurons: NeuronArrays,
+}
+
+/// Detect, decompress and decode one binary frame.
+pub(crate) fn decode_frame(packet: Vec<u8>, limits: &FrameLimits) -> Result<DecodedFrame, String> {
+    feagi_frame_codec::decode_packet(packet, limits, |payload: Payload| {
+        let was_lz4 = payload.compression != Compression::None;
+        decode_payload(payload.bytes, was_lz4, limits)
+    })
+}
+
+/// Decode uncompressed payload bytes by their format byte.
+fn decode_payload(
+    bytes: Vec<u8>,
+    was_lz4: bool,
+    limits: &FrameLimits,
+) -> Result<DecodedFrame, String> {
+    let mut neurons = NeuronArrays::default();
+    match PayloadFormat::of(&bytes) {
+        None if bytes.is_empty() => return Err("Payload decompressed to 0 bytes".to_string()),
+        None => {
+            return Err(format!(
+                "Payload has an unknown format (first byte {}; expected 2, 3 or 11)",
+                bytes[0]
+            ))
+        }
+        Some(PayloadFormat::RawType11) => {
+            let mut neuron_data = CorticalMappedXYZPNeuronVoxels::new();
+            neuron_data
+                .try_deserialize_and_update_self_from_byte_slice(&bytes)
+                .map_err(|e| format!("Type 11 deserialize error: {:?}", e))?;
+            check_frame_limits(&neuron_data, limits)?;
+            neurons.append(&neuron_data);
+        }
+        Some(PayloadFormat::Container(_)) => {
+            decode_container(&bytes, &mut neurons, limits)?;
+        }
+    }
+    limits.check_area_count(neurons.cortical_ids.len())?;
+    Ok(DecodedFrame {
+        bytes,
+        was_lz4,
+        neurons,
+    })
+}
+
+/// Read every neuron voxel structure of a FeagiByteContainer (other structures are skipped).
+fn decode_container(
+    bytes: &[u8],
+    neurons: &mut NeuronArrays,
+    limits: &FrameLimits,
+) -> Result<(), String> {
+    let mut container = FeagiByteContainer::new_empty();
+    let mut data = bytes.to_vec();
+    container
+        .try_write_data_to_container_and_verify(&mut |target| {
+            std::mem::swap(target, &mut data);
+            Ok(())
+        })
+        .map_err(|e| format!("FeagiByteContainer: {:?}", e))?;
+    let count = container
+        .try_get_number_contained_structures()
+        .map_err(|e| format!("FeagiByteContainer: {:?}", e))?;
+    if count == 0 {
+        return Err("Empty container".to_string());
+    }
+
+    for index in 0..count {
+        // Structures newer than this build must not hide the neuron data next to them
+        let Ok(structure) = container.try_create_new_struct_from_index(index) else {
+            continue;
+        };
+        if !matches!(
+            structure.get_type(),
+            FeagiByteStructureType::NeuronCategoricalXYZP
+        ) {
+            continue;
+        }
+        if let Some(neuron_data) = structure
+            .as_any()
+            .downcast_ref::<CorticalMappedXYZPNeuronVoxels>()
+        {
+            check_frame_limits(neuron_data, limits)?;
+            neurons.append(neuron_data);
+        }
+    }
+    Ok(())
+}
+
+/// Reject structures with more areas, or areas with more neurons, than the limits allow.
+fn check_frame_limits(
+    neuron_data: &CorticalMappedXYZPNeuronVoxels,
+    limits: &FrameLimits,
+) -> Result<(), String> {
+    let mappings = &neuron_data.mappings;
+    limits.check_area_count(mappings.len())?;
+    for (cortical_id, neurons) in mappings.iter() {
+        limits.check_area_neurons(neurons.len(), || cortical_id.as_base_64())?;
+    }
+    Ok(())
+}
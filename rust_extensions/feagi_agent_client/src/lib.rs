@@ -24,6 +24,9 @@ use std::sync::{Arc, Mutex, OnceLock};
 use std::thread;
 use std::time::{Duration, Instant};
 
+mod frame_decode;
+mod visualization_stream;
+
 struct FeagiAgentClientLib;
 
 #[gdextension]
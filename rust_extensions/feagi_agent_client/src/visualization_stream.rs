@@ -0,0 +1,477 @@
+//! Visualization stream client.
+//!
+//! Owns the FEAGI visualization WebSocket on a worker thread: packets are received,
+//! LZ4-decompressed (under a size ceiling) and decoded off the main thread, and only the
+//! newest frame is kept. Godot polls it once per frame, either with `poll_latest_frame()`
+//! to hand the bytes to `FeagiDataDeserializer.apply_type11_packet_to_multimeshes`, or with
+//! `poll_latest_neurons()` to get the already decoded neuron arrays.
+
+use crate::frame_decode::{self, DecodedFrame, FrameLimits};
+use feagi_io::protocol_implementations::websocket::websocket_std::FeagiWebSocketClientSubscriberProperties;
+use feagi_io::traits_and_enums::client::FeagiClientSubscriberProperties;
+use feagi_io::traits_and_enums::shared::FeagiEndpointState;
+use godot::prelude::*;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Frames at or below this size made only of printable ASCII are control messages ("updated", "ping")
+const MAX_TEXT_FRAME_BYTES: usize = 256;
+
+/// Sleep between polls while the socket has nothing to read
+const IDLE_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Connection changes and text frames, forwarded to the main thread
+enum StreamEvent {
+    State(i32, String),
+    Text(String),
+}
+
+/// Data shared between the worker thread and the Godot object
+#[derive(Default)]
+struct StreamShared {
+    stop: AtomicBool,
+    latest_frame: Mutex<Option<DecodedFrame>>,
+    frames_received: AtomicU64,
+    frames_superseded: AtomicU64,
+    frames_invalid: AtomicU64,
+    lz4_frames: AtomicU64,
+    bytes_received: AtomicU64,
+    /// Decompress + decode time of the newest frame, in microseconds
+    last_decode_us: AtomicU64,
+}
+
+/// Connection settings handed to the worker
+#[derive(Clone)]
+struct StreamConfig {
+    url: String,
+    auto_reconnect: bool,
+    reconnect_interval: Duration,
+    limits: FrameLimits,
+}
+
+/// GDExtension class: FEAGI visualization WebSocket client running on a worker thread.
+///
+/// Example (GDScript):
+///   var stream = FeagiVisualizationStream.new()
+///   stream.connection_state_changed.connect(_on_viz_state)
+///   stream.connect_to_url(visualization_ws_url)
+///   func _process(_delta):
+///       var frame = stream.poll_latest_frame()
+///       if not frame.is_empty():
+///           deserializer.apply_type11_packet_to_multimeshes(frame, multimeshes, dimensions, true)
+#[derive(GodotClass)]
+#[class(base=RefCounted)]
+pub struct FeagiVisualizationStream {
+    #[base]
+    base: Base<RefCounted>,
+    shared: Arc<StreamShared>,
+    events: Option<mpsc::Receiver<StreamEvent>>,
+    worker: Option<thread::JoinHandle<()>>,
+    state: i32,
+    auto_reconnect: bool,
+    reconnect_interval_s: f64,
+    limits: FrameLimits,
+    frames_delivered: u64,
+}
+
+#[godot_api]
+impl IRefCounted for FeagiVisualizationStream {
+    fn init(base: Base<RefCounted>) -> Self {
+        Self {
+            base,
+            shared: Arc::new(StreamShared::default()),
+            events: None,
+            worker: None,
+            state: Self::STATE_DISCONNECTED,
+            auto_reconnect: true,
+            reconnect_interval_s: 1.0,
+            limits: FrameLimits::default(),
+            frames_delivered: 0,
+        }
+    }
+}
+
+#[godot_api]
+impl FeagiVisualizationStream {
+    #[constant]
+    const STATE_DISCONNECTED: i32 = 0;
+    #[constant]
+    const STATE_CONNECTING: i32 = 1;
+    #[constant]
+    const STATE_CONNECTED: i32 = 2;
+    /// Connection lost; the worker retries after the reconnect interval
+    #[constant]
+    const STATE_RECONNECTING: i32 = 3;
+    /// Connection failed and auto-reconnect is off
+    #[constant]
+    const STATE_ERRORED: i32 = 4;
+
+    /// Emitted from poll_latest_frame() whenever the connection state changes
+    #[signal]
+    fn connection_state_changed(state: i32, message: GString);
+
+    /// Emitted from poll_latest_frame() for text frames (e.g. the "updated" genome flag)
+    #[signal]
+    fn text_message_received(text: GString);
+
+    /// Retry after connection loss (default true)
+    #[func]
+    pub fn set_auto_reconnect(&mut self, enabled: bool) {
+        self.auto_reconnect = enabled;
+    }
+
+    /// Seconds between reconnect attempts (default 1.0, minimum 0.05)
+    #[func]
+    pub fn set_reconnect_interval(&mut self, seconds: f64) {
+        self.reconnect_interval_s = seconds.max(0.05);
+    }
+
+    /// Largest decompressed frame accepted, in bytes (default 256 MiB).
+    ///
+    /// LZ4 blocks declaring more are dropped as invalid before anything is allocated.
+    /// Takes effect on the next connect_to_url().
+    #[func]
+    pub fn set_max_decompressed_bytes(&mut self, bytes: i64) {
+        self.limits.max_decompressed_bytes = bytes.max(1) as usize;
+    }
+
+    #[func]
+    pub fn get_max_decompressed_bytes(&self) -> i64 {
+        self.limits.max_decompressed_bytes as i64
+    }
+
+    /// Start receiving from a visualization WebSocket URL (closes any previous connection).
+    ///
+    /// Returns: false if the URL is empty or the worker could not be started
+    #[func]
+    pub fn connect_to_url(&mut self, url: GString) -> bool {
+        self.disconnect_stream();
+
+        let url = url.to_string().trim().to_string();
+        if url.is_empty() {
+            godot_error!("🦀 [VIZ-WS] Visualization URL is empty");
+            return false;
+        }
+
+        let shared = Arc::new(StreamShared::default());
+        let (event_tx, event_rx) = mpsc::channel::<StreamEvent>();
+        let config = StreamConfig {
+            url,
+            auto_reconnect: self.auto_reconnect,
+            reconnect_interval: Duration::from_secs_f64(self.reconnect_interval_s),
+            limits: self.limits,
+        };
+
+        let worker_shared = Arc::clone(&shared);
+        let worker = thread::Builder::new()
+            .name("bv-feagi-visualization".to_string())
+            .spawn(move || run_worker(config, worker_shared, event_tx));
+
+        match worker {
+            Ok(handle) => {
+                self.shared = shared;
+                self.events = Some(event_rx);
+                self.worker = Some(handle);
+                self.frames_delivered = 0;
+                true
+            }
+            Err(e) => {
+                godot_error!("🦀 [VIZ-WS] Failed to spawn visualization worker: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Close the connection and stop the worker. Safe to call when not connected.
+    #[func]
+    pub fn disconnect_stream(&mut self) {
+        self.shared.stop.store(true, Ordering::Release);
+        if let Some(handle) = self.worker.take() {
+            let _ = handle.join();
+        }
+        // Deliver the worker's last state changes before dropping the channel
+        self.dispatch_events();
+        self.events = None;
+        if self.state != Self::STATE_DISCONNECTED {
+            self.set_state(Self::STATE_DISCONNECTED, "Disconnected".to_string());
+        }
+    }
+
+    /// Newest frame received since the last call, ready for the deserializer.
+    ///
+    /// Frames are LZ4-decompressed and decoded on the worker, so only frames that decode are
+    /// returned; older frames that arrived in between are skipped. Also emits pending
+    /// connection_state_changed / text_message_received signals, so call it every frame.
+    ///
+    /// Returns: frame bytes (uncompressed FeagiByteContainer or raw Type 11), or an empty array
+    #[func]
+    pub fn poll_latest_frame(&mut self) -> PackedByteArray {
+        match self.take_latest_frame() {
+            Some(frame) => PackedByteArray::from(frame.bytes.as_slice()),
+            None => PackedByteArray::new(),
+        }
+    }
+
+    /// Newest frame received since the last call, as the neuron arrays decoded on the worker.
+    ///
+    /// Takes the same frame as poll_latest_frame() (use one or the other) and emits the same
+    /// pending signals.
+    ///
+    /// Returns: Dictionary {cortical_ids: PackedStringArray, neuron_counts: PackedInt32Array,
+    /// x, y, z: PackedInt32Array, powers: PackedFloat32Array}; the coordinate arrays hold the
+    /// areas back to back in cortical_ids order. Empty Dictionary if there is no new frame.
+    #[func]
+    pub fn poll_latest_neurons(&mut self) -> VarDictionary {
+        let Some(frame) = self.take_latest_frame() else {
+            return VarDictionary::new();
+        };
+        let neurons = frame.neurons;
+        let cortical_ids: PackedStringArray = neurons
+            .cortical_ids
+            .iter()
+            .map(|id| GString::from(id.as_str()))
+            .collect();
+        vdict!(
+            "cortical_ids": cortical_ids,
+            "neuron_counts": PackedInt32Array::from(neurons.neuron_counts.as_slice()),
+            "x": PackedInt32Array::from(neurons.x.as_slice()),
+            "y": PackedInt32Array::from(neurons.y.as_slice()),
+            "z": PackedInt32Array::from(neurons.z.as_slice()),
+            "powers": PackedFloat32Array::from(neurons.powers.as_slice())
+        )
+    }
+
+    /// Current connection state (one of the STATE_* constants, as of the last poll)
+    #[func]
+    pub fn get_state(&self) -> i32 {
+        self.state
+    }
+
+    #[func]
+    pub fn is_stream_connected(&self) -> bool {
+        self.state == Self::STATE_CONNECTED
+    }
+
+    /// Receive statistics.
+    ///
+    /// Returns: Dictionary {frames_received, frames_delivered, frames_superseded (replaced by a
+    /// newer frame before being polled), frames_invalid, lz4_frames, bytes_received, last_decode_ms}
+    #[func]
+    pub fn get_stats(&self) -> VarDictionary {
+        let shared = &self.shared;
+        vdict!(
+            "frames_received": shared.frames_received.load(Ordering::Relaxed) as i64,
+            "frames_delivered": self.frames_delivered as i64,
+            "frames_superseded": shared.frames_superseded.load(Ordering::Relaxed) as i64,
+            "frames_invalid": shared.frames_invalid.load(Ordering::Relaxed) as i64,
+            "lz4_frames": shared.lz4_frames.load(Ordering::Relaxed) as i64,
+            "bytes_received": shared.bytes_received.load(Ordering::Relaxed) as i64,
+            "last_decode_ms": shared.last_decode_us.load(Ordering::Relaxed) as f64 / 1000.0
+        )
+    }
+}
+
+impl FeagiVisualizationStream {
+    fn take_latest_frame(&mut self) -> Option<DecodedFrame> {
+        self.dispatch_events();
+        let frame = self
+            .shared
+            .latest_frame
+            .lock()
+            .expect("visualization frame mutex poisoned")
+            .take();
+        if frame.is_some() {
+            self.frames_delivered += 1;
+        }
+        frame
+    }
+
+    fn dispatch_events(&mut self) {
+        let pending: Vec<StreamEvent> = match self.events.as_ref() {
+            Some(rx) => rx.try_iter().collect(),
+            None => return,
+        };
+        for event in pending {
+            match event {
+                StreamEvent::State(state, message) => self.set_state(state, message),
+                StreamEvent::Text(text) => {
+                    self.base_mut().emit_signal(
+                        "text_message_received",
+                        &[GString::from(text.as_str()).to_variant()],
+                    );
+                }
+            }
+        }
+    }
+
+    fn set_state(&mut self, state: i32, message: String) {
+        self.state = state;
+        self.base_mut().emit_signal(
+            "connection_state_changed",
+            &[
+                state.to_variant(),
+                GString::from(message.as_str()).to_variant(),
+            ],
+        );
+    }
+}
+
+impl Drop for FeagiVisualizationStream {
+    fn drop(&mut self) {
+        self.shared.stop.store(true, Ordering::Release);
+        if let Some(handle) = self.worker.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Worker thread: connect, receive, reconnect until stopped.
+fn run_worker(config: StreamConfig, shared: Arc<StreamShared>, events: mpsc::Sender<StreamEvent>) {
+    let mut first_attempt = true;
+    while !shared.stop.load(Ordering::Acquire) {
+        let connecting_state = if first_attempt {
+            FeagiVisualizationStream::STATE_CONNECTING
+        } else {
+            FeagiVisualizationStream::STATE_RECONNECTING
+        };
+        let _ = events.send(StreamEvent::State(
+            connecting_state,
+            format!("Connecting to {}", config.url),
+        ));
+        first_attempt = false;
+
+        let error = match receive_until_closed(&config, &shared, &events) {
+            Ok(()) => return, // stopped
+            Err(e) => e,
+        };
+
+        if !config.auto_reconnect {
+            let _ = events.send(StreamEvent::State(
+                FeagiVisualizationStream::STATE_ERRORED,
+                error,
+            ));
+            return;
+        }
+        let _ = events.send(StreamEvent::State(
+            FeagiVisualizationStream::STATE_RECONNECTING,
+            error,
+        ));
+        let wait_start = Instant::now();
+        while wait_start.elapsed() < config.reconnect_interval {
+            if shared.stop.load(Ordering::Acquire) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
+
+/// One connection lifetime. Returns Ok when stopped, Err when the connection failed or closed.
+fn receive_until_closed(
+    config: &StreamConfig,
+    shared: &StreamShared,
+    events: &mpsc::Sender<StreamEvent>,
+) -> Result<(), String> {
+    let properties = FeagiWebSocketClientSubscriberProperties::new(&config.url)
+        .map_err(|e| format!("WebSocket subscriber: {}", e))?;
+    let mut subscriber = properties.as_boxed_client_subscriber();
+    subscriber
+        .request_connect()
+        .map_err(|e| format!("Connect: {}", e))?;
+
+    let mut connected = false;
+    loop {
+        if shared.stop.load(Ordering::Acquire) {
+            let _ = subscriber.request_disconnect();
+            return Ok(());
+        }
+        match subscriber.poll().clone() {
+            FeagiEndpointState::ActiveHasData => {
+                if !connected {
+                    connected = true;
+                    let _ = events.send(StreamEvent::State(
+                        FeagiVisualizationStream::STATE_CONNECTED,
+                        String::new(),
+                    ));
+                }
+                let data = subscriber
+                    .consume_retrieved_data()
+                    .map(|bytes| bytes.to_vec())
+                    .map_err(|e| format!("Receive: {}", e))?;
+                handle_payload(data, &config.limits, shared, events);
+            }
+            FeagiEndpointState::ActiveWaiting => {
+                if !connected {
+                    connected = true;
+                    let _ = events.send(StreamEvent::State(
+                        FeagiVisualizationStream::STATE_CONNECTED,
+                        String::new(),
+                    ));
+                }
+                thread::sleep(IDLE_POLL_INTERVAL);
+            }
+            FeagiEndpointState::Errored(err) => {
+                let _ = subscriber.confirm_error_and_close();
+                return Err(format!("Connection error: {}", err));
+            }
+            _ => thread::sleep(IDLE_POLL_INTERVAL),
+        }
+    }
+}
+
+fn handle_payload(
+    data: Vec<u8>,
+    limits: &FrameLimits,
+    shared: &StreamShared,
+    events: &mpsc::Sender<StreamEvent>,
+) {
+    if data.is_empty() {
+        return;
+    }
+    shared
+        .bytes_received
+        .fetch_add(data.len() as u64, Ordering::Relaxed);
+
+    if is_probably_text(&data) {
+        let text = String::from_utf8_lossy(&data).trim().to_string();
+        let lowered = text.to_lowercase();
+        if lowered != "ping" && lowered != "pong" {
+            let _ = events.send(StreamEvent::Text(text));
+        }
+        return;
+    }
+
+    shared.frames_received.fetch_add(1, Ordering::Relaxed);
+    let decode_start = Instant::now();
+    match frame_decode::decode_frame(data, limits) {
+        Ok(frame) => {
+            if frame.was_lz4 {
+                shared.lz4_frames.fetch_add(1, Ordering::Relaxed);
+            }
+            shared
+                .last_decode_us
+                .store(decode_start.elapsed().as_micros() as u64, Ordering::Relaxed);
+            let previous = shared
+                .latest_frame
+                .lock()
+                .expect("visualization frame mutex poisoned")
+                .replace(frame);
+            if previous.is_some() {
+                shared.frames_superseded.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        Err(_) => {
+            shared.frames_invalid.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+fn is_probably_text(bytes: &[u8]) -> bool {
+    bytes.len() <= MAX_TEXT_FRAME_BYTES
+        && bytes
+            .iter()
+            .all(|&b| b == b'\t' || b == b'\n' || b == b'\r' || (32..=126).contains(&b))
+}
@@ -0,0 +1,22 @@
+//! Arbitrary SoA columns and offset tables into the bulk conversion
+//! (convert_bulk_arrays_to_godot / convert_bulk_arrays_by_area).
+//!
+//! Run with `cargo fuzz run bulk_columns` from `feagi_data_deserializer`.
+
+#![no_main]
+
+use feagi_data_deserializer::fuzzing::split_bulk_columns;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(
+    |input: (Vec<u8>, Vec<u8>, Vec<u8>, Vec<u8>, u8, Vec<i64>)| {
+        let (x, y, z, p, area_count, offsets) = input;
+        let area_count = area_count as usize;
+        if let Ok(counts) = split_bulk_columns(&x, &y, &z, &p, area_count, &offsets) {
+            assert_eq!(counts.len(), area_count);
+            if area_count > 0 {
+                assert_eq!(counts.iter().sum::<usize>(), x.len() / 4);
+            }
+        }
+    }
+);
@@ -0,0 +1,25 @@
+//! Arbitrary packets into the ingest path of every decode and apply method (LZ4 detection,
+//! FeagiByteContainer and raw Type 11 decoding, frame limits).
+//!
+//! Run with `cargo fuzz run ingest_packet` from `feagi_data_deserializer`.
+
+#![no_main]
+
+use feagi_data_deserializer::fuzzing::{ingest_packet, Limits};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let limits = Limits {
+        max_decompressed_bytes: 1024 * 1024,
+        max_areas_per_frame: 64,
+        max_neurons_per_area: 4096,
+    };
+    if let Ok(decoded) = ingest_packet(data, &limits) {
+        assert!(decoded.payload_bytes <= limits.max_decompressed_bytes);
+        assert!(decoded.areas.len() <= limits.max_areas_per_frame);
+        assert!(decoded
+            .areas
+            .iter()
+            .all(|(_, neurons)| *neurons <= limits.max_neurons_per_area));
+    }
+});
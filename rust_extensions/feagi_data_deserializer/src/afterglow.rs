@@ -0,0 +1,138 @@
+//! Temporal activity decay ("afterglow") for neuron visualization.
+//!
+//! Every Type 11 packet only lists the neurons that fired in that burst, so fast-firing
+//! neurons flicker and single-burst events vanish before they are seen at 60 fps. The
+//! accumulator remembers recently fired voxels per cortical area and keeps drawing them
+//! with an exponentially decaying alpha/size until they reach the maximum age.
+
+use crate::multimesh_buffer::VoxelInstance;
+use std::collections::HashMap;
+
+/// Afterglow configuration.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct AfterglowSettings {
+    pub enabled: bool,
+    /// Bursts after which a voxel's intensity has halved
+    pub half_life_bursts: f32,
+    /// Bursts after which a voxel is dropped (0 = only the current burst is drawn)
+    pub max_age_bursts: u32,
+    /// Instance scale at zero intensity (1.0 disables size falloff)
+    pub min_scale: f32,
+}
+
+impl Default for AfterglowSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            half_life_bursts: 2.0,
+            max_age_bursts: 8,
+            min_scale: 0.4,
+        }
+    }
+}
+
+impl AfterglowSettings {
+    /// Intensity of a voxel that last fired `age` bursts ago.
+    #[inline]
+    pub fn intensity(&self, age: u64) -> f32 {
+        if age == 0 {
+            return 1.0;
+        }
+        if self.half_life_bursts <= 0.0 {
+            return 0.0;
+        }
+        0.5f32.powf(age as f32 / self.half_life_bursts)
+    }
+
+    /// Fade a row-major 3x4 instance transform and its color by `intensity`.
+    ///
+    /// Alpha is multiplied by the intensity; the basis shrinks towards `min_scale` around
+    /// the voxel center (the origin is untouched).
+    #[inline]
+    pub fn fade(&self, transform: &mut [f32; 12], color: &mut [f32; 4], intensity: f32) {
+        if intensity >= 1.0 {
+            return;
+        }
+        let size = self.min_scale + (1.0 - self.min_scale) * intensity;
+        transform[0] *= size;
+        transform[5] *= size;
+        transform[10] *= size;
+        color[3] *= intensity;
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct GlowEntry {
+    last_fired_burst: u64,
+    potential: f32,
+}
+
+/// Per-area activity accumulator keyed by voxel coordinate.
+#[derive(Default)]
+pub(crate) struct AfterglowBuffer {
+    pub settings: AfterglowSettings,
+    /// Number of bursts seen since the buffer was created or cleared
+    burst: u64,
+    areas: HashMap<String, HashMap<(u32, u32, u32), GlowEntry>>,
+}
+
+impl AfterglowBuffer {
+    /// Advance to the next burst; call once per applied packet before `record`.
+    pub fn begin_burst(&mut self) {
+        self.burst += 1;
+    }
+
+    /// Mark voxels of an area as fired in the current burst.
+    pub fn record(&mut self, cortical_id: &str, fired: &[VoxelInstance]) {
+        let area = self.areas.entry(cortical_id.to_string()).or_default();
+        for voxel in fired {
+            area.insert(
+                (voxel.x, voxel.y, voxel.z),
+                GlowEntry {
+                    last_fired_burst: self.burst,
+                    potential: voxel.potential,
+                },
+            );
+        }
+    }
+
+    /// Cortical IDs that still have glowing voxels.
+    pub fn area_ids(&self) -> Vec<String> {
+        self.areas.keys().cloned().collect()
+    }
+
+    /// Drop expired voxels of an area and return the remaining ones with their intensity.
+    ///
+    /// The area is forgotten once nothing in it glows anymore.
+    pub fn take_glowing(&mut self, cortical_id: &str) -> Vec<VoxelInstance> {
+        let settings = self.settings;
+        let burst = self.burst;
+        let area = match self.areas.get_mut(cortical_id) {
+            Some(a) => a,
+            None => return Vec::new(),
+        };
+        area.retain(|_, entry| burst - entry.last_fired_burst <= settings.max_age_bursts as u64);
+
+        let glowing: Vec<VoxelInstance> = area
+            .iter()
+            .map(|(&(x, y, z), entry)| VoxelInstance {
+                x,
+                y,
+                z,
+                potential: entry.potential,
+                intensity: settings.intensity(burst - entry.last_fired_burst),
+                extent: 1,
+                size: 1.0,
+            })
+            .collect();
+        if glowing.is_empty() {
+            self.areas.remove(cortical_id);
+        }
+        glowing
+    }
+
+    pub fn clear(&mut self) {
+        self.areas.clear();
+        self.burst = 0;
+    }
+}
@@ -0,0 +1,154 @@
+//! Bulk conversion of SoA byte columns.
+//!
+//! Tools outside FEAGI often ship neuron activity as four little-endian columns (x, y, z as
+//! int32, potential as float32) instead of Type 11 structures. The columns are validated and
+//! converted in one pass, optionally split into cortical areas by an offset table.
+
+use godot::prelude::*;
+
+/// Bytes per value in every column (int32 / float32)
+const VALUE_BYTES: usize = 4;
+
+/// The four columns, decoded.
+pub(crate) struct BulkColumns {
+    pub x: Vec<i32>,
+    pub y: Vec<i32>,
+    pub z: Vec<i32>,
+    pub potential: Vec<f32>,
+}
+
+impl BulkColumns {
+    /// Decode and validate the raw columns: each must be a whole number of 4-byte values and
+    /// all four must hold the same number of neurons.
+    pub fn from_bytes(x: &[u8], y: &[u8], z: &[u8], p: &[u8]) -> Result<Self, String> {
+        let neurons = column_len("x", x)?;
+        for (name, column) in [("y", y), ("z", z), ("p", p)] {
+            let len = column_len(name, column)?;
+            if len != neurons {
+                return Err(format!(
+                    "Column {} holds {} values, x holds {}",
+                    name, len, neurons
+                ));
+            }
+        }
+        Ok(Self {
+            x: read_i32(x),
+            y: read_i32(y),
+            z: read_i32(z),
+            potential: read_f32(p),
+        })
+    }
+
+    pub fn empty() -> Self {
+        Self {
+            x: Vec::new(),
+            y: Vec::new(),
+            z: Vec::new(),
+            potential: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.x.len()
+    }
+
+    /// Neuron ranges of the areas of an offset table.
+    ///
+    /// `offsets[i]` is the first neuron of area i. The table holds one entry per area (the last
+    /// area runs to the end of the columns) or one more, ending at the neuron count. Offsets
+    /// start at 0 and never decrease.
+    pub fn area_ranges(
+        &self,
+        area_count: usize,
+        offsets: &[i64],
+    ) -> Result<Vec<std::ops::Range<usize>>, String> {
+        let neurons = self.len() as i64;
+        if offsets.len() != area_count && offsets.len() != area_count + 1 {
+            return Err(format!(
+                "Offset table holds {} entries for {} areas (expected {} or {})",
+                offsets.len(),
+                area_count,
+                area_count,
+                area_count + 1
+            ));
+        }
+        if area_count == 0 {
+            return Ok(Vec::new());
+        }
+        if offsets[0] != 0 {
+            return Err(format!("First offset must be 0, got {}", offsets[0]));
+        }
+        let mut bounds: Vec<i64> = offsets.to_vec();
+        if bounds.len() == area_count {
+            bounds.push(neurons);
+        } else if bounds[area_count] != neurons {
+            return Err(format!(
+                "Last offset {} does not match the neuron count {}",
+                bounds[area_count], neurons
+            ));
+        }
+        for window in bounds.windows(2) {
+            if window[1] < window[0] || window[1] > neurons {
+                return Err(format!(
+                    "Offsets must be ascending and within 0..={} (got {} then {})",
+                    neurons, window[0], window[1]
+                ));
+            }
+        }
+        Ok(bounds
+            .windows(2)
+            .map(|w| w[0] as usize..w[1] as usize)
+            .collect())
+    }
+
+    /// Copy of several ranges, concatenated in order.
+    pub fn gather(&self, ranges: &[std::ops::Range<usize>]) -> Self {
+        let mut out = Self::empty();
+        for range in ranges {
+            out.x.extend_from_slice(&self.x[range.clone()]);
+            out.y.extend_from_slice(&self.y[range.clone()]);
+            out.z.extend_from_slice(&self.z[range.clone()]);
+            out.potential
+                .extend_from_slice(&self.potential[range.clone()]);
+        }
+        out
+    }
+
+    /// One range of the columns as an area Dictionary {x_array, y_array, z_array, p_array}
+    /// (the shape of decode_type_11_data's per-area entries).
+    pub fn to_area_dictionary(&self, range: std::ops::Range<usize>) -> Dictionary {
+        let mut dict = Dictionary::new();
+        dict.set("x_array", PackedInt32Array::from(&self.x[range.clone()]));
+        dict.set("y_array", PackedInt32Array::from(&self.y[range.clone()]));
+        dict.set("z_array", PackedInt32Array::from(&self.z[range.clone()]));
+        dict.set("p_array", PackedFloat32Array::from(&self.potential[range]));
+        dict
+    }
+}
+
+/// Number of 4-byte values in a column.
+fn column_len(name: &str, column: &[u8]) -> Result<usize, String> {
+    if column.len() % VALUE_BYTES != 0 {
+        return Err(format!(
+            "Column {} is {} bytes, not a multiple of {}",
+            name,
+            column.len(),
+            VALUE_BYTES
+        ));
+    }
+    Ok(column.len() / VALUE_BYTES)
+}
+
+fn read_i32(column: &[u8]) -> Vec<i32> {
+    column
+        .chunks_exact(VALUE_BYTES)
+        .map(|b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+fn read_f32(column: &[u8]) -> Vec<f32> {
+    column
+        .chunks_exact(VALUE_BYTES)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
@@ -0,0 +1,265 @@
+//! Neuron coloring: selectable color modes and colormaps.
+//!
+//! The default (depth mode + "red" colormap) reproduces the original z-depth red gradient
+//! exactly, so existing scenes look the same until a different mode is selected.
+
+use std::collections::HashMap;
+
+/// What drives the color of a neuron instance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ColorMode {
+    /// Front-to-back gradient along the area's z axis (legacy behaviour)
+    Depth,
+    /// Membrane potential / firing strength mapped through the potential range
+    Potential,
+    /// One fixed color per cortical area (falls back to depth for areas without a color)
+    AreaFixed,
+    /// Signed potential mapped symmetrically around zero (signed-percentage OPUs)
+    SignedDiverging,
+}
+
+impl ColorMode {
+    pub fn from_i32(mode: i32) -> Option<Self> {
+        match mode {
+            0 => Some(ColorMode::Depth),
+            1 => Some(ColorMode::Potential),
+            2 => Some(ColorMode::AreaFixed),
+            3 => Some(ColorMode::SignedDiverging),
+            _ => None,
+        }
+    }
+
+    pub fn as_i32(self) -> i32 {
+        match self {
+            ColorMode::Depth => 0,
+            ColorMode::Potential => 1,
+            ColorMode::AreaFixed => 2,
+            ColorMode::SignedDiverging => 3,
+        }
+    }
+}
+
+/// Piecewise-linear RGBA gradient over t in [0, 1].
+#[derive(Clone, Debug)]
+pub(crate) struct Gradient {
+    /// (offset, rgba) pairs sorted by offset
+    stops: Vec<(f32, [f32; 4])>,
+}
+
+impl Gradient {
+    /// Build a gradient from user-supplied stops.
+    ///
+    /// Offsets must be finite; they are sorted and clamped to [0, 1]. At least one stop is required.
+    pub fn from_stops(mut stops: Vec<(f32, [f32; 4])>) -> Result<Self, String> {
+        if stops.is_empty() {
+            return Err("Gradient needs at least one stop".to_string());
+        }
+        if stops.iter().any(|(offset, _)| !offset.is_finite()) {
+            return Err("Gradient offsets must be finite".to_string());
+        }
+        for stop in stops.iter_mut() {
+            stop.0 = stop.0.clamp(0.0, 1.0);
+        }
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Ok(Self { stops })
+    }
+
+    fn from_rgb_table(table: &[(f32, [f32; 3])]) -> Self {
+        Self {
+            stops: table
+                .iter()
+                .map(|(offset, rgb)| (*offset, [rgb[0], rgb[1], rgb[2], 1.0]))
+                .collect(),
+        }
+    }
+
+    /// Sample the gradient; `t` is clamped to [0, 1] (NaN samples the first stop).
+    #[inline]
+    pub fn sample(&self, t: f32) -> [f32; 4] {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let first = self.stops[0];
+        if t <= first.0 {
+            return first.1;
+        }
+        for pair in self.stops.windows(2) {
+            let (o0, c0) = pair[0];
+            let (o1, c1) = pair[1];
+            if t <= o1 {
+                let span = o1 - o0;
+                let f = if span > 0.0 { (t - o0) / span } else { 1.0 };
+                return [
+                    c0[0] + (c1[0] - c0[0]) * f,
+                    c0[1] + (c1[1] - c0[1]) * f,
+                    c0[2] + (c1[2] - c0[2]) * f,
+                    c0[3] + (c1[3] - c0[3]) * f,
+                ];
+            }
+        }
+        self.stops[self.stops.len() - 1].1
+    }
+}
+
+/// Names accepted by `builtin_colormap`.
+pub(crate) const BUILTIN_COLORMAPS: [&str; 4] = ["red", "viridis", "magma", "coolwarm"];
+
+/// Built-in colormaps (sampled control points of the matplotlib maps).
+pub(crate) fn builtin_colormap(name: &str) -> Option<Gradient> {
+    let gradient = match name {
+        // max(t, 0.2) on the red channel: identical to the original depth coloring
+        "red" => Gradient {
+            stops: vec![
+                (0.0, [0.2, 0.0, 0.0, 1.0]),
+                (0.2, [0.2, 0.0, 0.0, 1.0]),
+                (1.0, [1.0, 0.0, 0.0, 1.0]),
+            ],
+        },
+        "viridis" => Gradient::from_rgb_table(&[
+            (0.0, [0.267, 0.005, 0.329]),
+            (0.125, [0.283, 0.141, 0.458]),
+            (0.25, [0.254, 0.265, 0.530]),
+            (0.375, [0.207, 0.372, 0.553]),
+            (0.5, [0.164, 0.471, 0.558]),
+            (0.625, [0.128, 0.567, 0.551]),
+            (0.75, [0.135, 0.659, 0.518]),
+            (0.875, [0.267, 0.749, 0.441]),
+            (0.9375, [0.626, 0.855, 0.223]),
+            (1.0, [0.993, 0.906, 0.144]),
+        ]),
+        "magma" => Gradient::from_rgb_table(&[
+            (0.0, [0.001, 0.000, 0.014]),
+            (0.125, [0.079, 0.054, 0.212]),
+            (0.25, [0.232, 0.060, 0.438]),
+            (0.375, [0.390, 0.100, 0.502]),
+            (0.5, [0.550, 0.161, 0.506]),
+            (0.625, [0.716, 0.215, 0.475]),
+            (0.75, [0.869, 0.288, 0.409]),
+            (0.875, [0.968, 0.440, 0.360]),
+            (0.9375, [0.994, 0.624, 0.427]),
+            (1.0, [0.987, 0.991, 0.750]),
+        ]),
+        "coolwarm" => Gradient::from_rgb_table(&[
+            (0.0, [0.230, 0.299, 0.754]),
+            (0.125, [0.348, 0.466, 0.888]),
+            (0.25, [0.484, 0.622, 0.975]),
+            (0.375, [0.619, 0.744, 0.999]),
+            (0.5, [0.865, 0.865, 0.865]),
+            (0.625, [0.958, 0.754, 0.656]),
+            (0.75, [0.957, 0.598, 0.480]),
+            (0.875, [0.871, 0.400, 0.321]),
+            (1.0, [0.706, 0.016, 0.150]),
+        ]),
+        _ => return None,
+    };
+    Some(gradient)
+}
+
+/// Active coloring configuration of a deserializer.
+#[derive(Clone, Debug)]
+pub(crate) struct ColorSettings {
+    pub mode: ColorMode,
+    /// Name of the active colormap ("custom" for user-supplied stops)
+    pub colormap_name: String,
+    gradient: Gradient,
+    /// Potential mapped to the start of the colormap (potential mode)
+    pub potential_min: f32,
+    /// Potential mapped to the end of the colormap (potential mode)
+    pub potential_max: f32,
+    area_colors: HashMap<String, [f32; 4]>,
+}
+
+impl Default for ColorSettings {
+    fn default() -> Self {
+        Self {
+            mode: ColorMode::Depth,
+            colormap_name: "red".to_string(),
+            gradient: builtin_colormap("red").expect("built-in colormap"),
+            potential_min: 0.0,
+            potential_max: 1.0,
+            area_colors: HashMap::new(),
+        }
+    }
+}
+
+impl ColorSettings {
+    pub fn set_colormap(&mut self, name: &str) -> bool {
+        match builtin_colormap(name) {
+            Some(gradient) => {
+                self.gradient = gradient;
+                self.colormap_name = name.to_string();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn set_custom_gradient(&mut self, gradient: Gradient) {
+        self.gradient = gradient;
+        self.colormap_name = "custom".to_string();
+    }
+
+    pub fn set_area_color(&mut self, cortical_id: &str, rgba: [f32; 4]) {
+        self.area_colors.insert(cortical_id.to_string(), rgba);
+    }
+
+    pub fn clear_area_colors(&mut self) {
+        self.area_colors.clear();
+    }
+
+    /// Active colormap
+    pub fn gradient(&self) -> &Gradient {
+        &self.gradient
+    }
+
+    /// Color assigned with set_area_color, regardless of the color mode.
+    pub fn assigned_area_color(&self, cortical_id: &str) -> Option<[f32; 4]> {
+        self.area_colors.get(cortical_id).copied()
+    }
+
+    /// Fixed color of an area; only meaningful in area-fixed mode.
+    pub fn area_color(&self, cortical_id: &str) -> Option<[f32; 4]> {
+        if self.mode != ColorMode::AreaFixed {
+            return None;
+        }
+        self.area_colors.get(cortical_id).copied()
+    }
+
+    /// Color for one neuron instance (shared by the Rayon, WASM and MultiMesh paths).
+    #[inline]
+    pub fn color(
+        &self,
+        z: u32,
+        z_max: f32,
+        potential: f32,
+        area_color: Option<[f32; 4]>,
+    ) -> [f32; 4] {
+        match self.mode {
+            ColorMode::Depth => self.depth_color(z, z_max),
+            ColorMode::Potential => {
+                let span = self.potential_max - self.potential_min;
+                let t = if span.abs() > f32::EPSILON {
+                    (potential - self.potential_min) / span
+                } else {
+                    1.0
+                };
+                self.gradient.sample(t)
+            }
+            ColorMode::AreaFixed => area_color.unwrap_or_else(|| self.depth_color(z, z_max)),
+            ColorMode::SignedDiverging => {
+                let magnitude = self.potential_min.abs().max(self.potential_max.abs());
+                let t = if magnitude > f32::EPSILON {
+                    0.5 + 0.5 * (potential / magnitude)
+                } else {
+                    0.5
+                };
+                self.gradient.sample(t)
+            }
+        }
+    }
+
+    /// Front bright, back dark
+    #[inline(always)]
+    fn depth_color(&self, z: u32, z_max: f32) -> [f32; 4] {
+        let z_normalized = (z as f32 / z_max).clamp(0.0, 1.0);
+        self.gradient.sample(1.0 - z_normalized)
+    }
+}
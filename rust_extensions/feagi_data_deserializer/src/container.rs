@@ -0,0 +1,178 @@
+//! FeagiByteContainer walking for the visualization stream.
+//!
+//! Newer FEAGI builds pack several structures into a single container (neuron voxels
+//! alongside other payloads). Every structure is visited, all
+//! `CorticalMappedXYZPNeuronVoxels` blocks are merged into one, and anything this
+//! crate does not understand is recorded instead of failing the whole packet.
+
+use crate::error::{DecodeError, ErrorKind};
+use feagi_serialization::{FeagiByteContainer, FeagiByteStructureType, FeagiSerializable};
+use feagi_structures::neuron_voxels::xyzp::CorticalMappedXYZPNeuronVoxels;
+use godot::prelude::*;
+
+/// A structure inside a container that was skipped by the walker.
+pub(crate) struct UnknownStructure {
+    /// Position of the structure inside the container
+    pub index: usize,
+    /// Structure type (or extraction error) as reported by feagi-serialization
+    pub description: String,
+}
+
+/// Neuron data extracted from one visualization payload.
+pub(crate) struct DecodedNeuronPayload {
+    /// All neuron voxel structures of the payload, merged per cortical area
+    pub neuron_data: CorticalMappedXYZPNeuronVoxels,
+    /// Number of structures in the container (1 for raw Type 11 payloads)
+    pub structure_count: usize,
+    /// Number of `CorticalMappedXYZPNeuronVoxels` structures that were merged
+    pub neuron_structure_count: usize,
+    /// Structures that were not neuron voxels
+    pub unknown_structures: Vec<UnknownStructure>,
+}
+
+impl DecodedNeuronPayload {
+    /// Write structure bookkeeping into a result dictionary.
+    ///
+    /// Adds `structure_count`, `neuron_structure_count` and `unknown_structures`
+    /// (Array of `{index: int, type: String}`).
+    pub fn write_structure_info(&self, dict: &mut Dictionary) {
+        let mut unknown = Array::<Dictionary>::new();
+        for structure in &self.unknown_structures {
+            let mut entry = Dictionary::new();
+            entry.set("index", structure.index as i32);
+            entry.set("type", structure.description.as_str());
+            unknown.push(&entry);
+        }
+        dict.set("structure_count", self.structure_count as i32);
+        dict.set("neuron_structure_count", self.neuron_structure_count as i32);
+        dict.set("unknown_structures", unknown);
+    }
+}
+
+/// Decode raw Type 11 struct bytes (first byte == 11), as sent when upstream unwrapped the container.
+pub(crate) fn decode_raw_type11(bytes: Vec<u8>) -> Result<DecodedNeuronPayload, DecodeError> {
+    if bytes.first() != Some(&11) {
+        return Err(DecodeError::new(
+            ErrorKind::WrongStructureType,
+            format!("Expected structure type 11, got {:?}", bytes.first()),
+        ));
+    }
+    let mut neuron_data = CorticalMappedXYZPNeuronVoxels::new();
+    neuron_data
+        .try_deserialize_and_update_self_from_byte_slice(&bytes)
+        .map_err(|e| {
+            DecodeError::new(
+                ErrorKind::CorruptContainer,
+                format!("Type 11 deserialize error: {:?}", e),
+            )
+        })?;
+    Ok(DecodedNeuronPayload {
+        neuron_data,
+        structure_count: 1,
+        neuron_structure_count: 1,
+        unknown_structures: Vec::new(),
+    })
+}
+
+/// Decode a FeagiByteContainer (v2 or v3): visit every structure and merge all neuron voxel blocks.
+pub(crate) fn decode_container(bytes: Vec<u8>) -> Result<DecodedNeuronPayload, DecodeError> {
+    let version = bytes.first().copied().unwrap_or(0);
+    let mut byte_container = FeagiByteContainer::new_empty();
+    let mut data_vec = bytes;
+    byte_container
+        .try_write_data_to_container_and_verify(&mut |bytes| {
+            std::mem::swap(bytes, &mut data_vec);
+            Ok(())
+        })
+        .map_err(|e| {
+            // A version other than the one this build writes is the likely cause
+            let kind = if version == FeagiByteContainer::CURRENT_FBS_VERSION {
+                ErrorKind::CorruptContainer
+            } else {
+                ErrorKind::UnsupportedVersion
+            };
+            DecodeError::new(kind, format!("{:?}", e))
+        })?;
+
+    let num_structures = byte_container
+        .try_get_number_contained_structures()
+        .map_err(|e| DecodeError::new(ErrorKind::CorruptContainer, format!("{:?}", e)))?;
+    if num_structures == 0 {
+        return Err(DecodeError::new(
+            ErrorKind::CorruptContainer,
+            "Empty container",
+        ));
+    }
+
+    let mut merged: Option<CorticalMappedXYZPNeuronVoxels> = None;
+    let mut neuron_structure_count = 0usize;
+    let mut unknown_structures = Vec::new();
+
+    for index in 0..num_structures {
+        // An unreadable structure (e.g. a type newer than this build) must not
+        // hide the neuron data that sits next to it in the same container.
+        let boxed_struct = match byte_container.try_create_new_struct_from_index(index) {
+            Ok(s) => s,
+            Err(e) => {
+                unknown_structures.push(UnknownStructure {
+                    index: index as usize,
+                    description: format!("unreadable: {:?}", e),
+                });
+                continue;
+            }
+        };
+
+        match boxed_struct.get_type() {
+            FeagiByteStructureType::NeuronCategoricalXYZP => {
+                let neuron_data = match boxed_struct
+                    .as_any()
+                    .downcast_ref::<CorticalMappedXYZPNeuronVoxels>()
+                {
+                    Some(nd) => nd,
+                    None => {
+                        unknown_structures.push(UnknownStructure {
+                            index: index as usize,
+                            description: "NeuronCategoricalXYZP (unexpected layout)".to_string(),
+                        });
+                        continue;
+                    }
+                };
+                neuron_structure_count += 1;
+                match merged.as_mut() {
+                    Some(target) => merge_neuron_voxels(target, neuron_data),
+                    None => merged = Some(neuron_data.clone()),
+                }
+            }
+            other => unknown_structures.push(UnknownStructure {
+                index: index as usize,
+                description: format!("{:?}", other),
+            }),
+        }
+    }
+
+    Ok(DecodedNeuronPayload {
+        neuron_data: merged.unwrap_or_else(CorticalMappedXYZPNeuronVoxels::new),
+        structure_count: num_structures as usize,
+        neuron_structure_count,
+        unknown_structures,
+    })
+}
+
+/// Append every neuron of `incoming` to the matching cortical area of `target`.
+fn merge_neuron_voxels(
+    target: &mut CorticalMappedXYZPNeuronVoxels,
+    incoming: &CorticalMappedXYZPNeuronVoxels,
+) {
+    for (cortical_id, neuron_array) in incoming.mappings.iter() {
+        match target.mappings.get_mut(cortical_id) {
+            Some(existing) => {
+                for neuron in neuron_array.iter() {
+                    existing.push(&neuron);
+                }
+            }
+            None => {
+                target.mappings.insert(*cortical_id, neuron_array.clone());
+            }
+        }
+    }
+}
@@ -0,0 +1,330 @@
+//! Coordinate spaces of neuron placement.
+//!
+//! All placement math of the MultiMesh apply paths, picking and the GDScript helpers lives
+//! here, so a voxel lands in the same spot whichever path draws or queries it:
+//!
+//! - **voxel**: integer neuron coordinate from FEAGI, in [0, dimensions)
+//! - **cell**: continuous voxel space; voxel (x, y, z) covers [x, x+1) x [y, y+1) x [z, z+1)
+//! - **area-local**: MultiMesh space. The area is a unit box centered on the origin and +z
+//!   voxels point along -Z; voxel (x, y, z) is drawn at ((x, y, z) - dimensions / 2) / dimensions
+//!   with no extra offset (see NEURON_POSITION_SCALING_FIX.md)
+//! - **brain-world**: Godot space of the brain. Cell point c of an area whose lower-left-front
+//!   corner sits at FEAGI coordinate p maps to (p.x + c.x * s.x, p.y + c.y * s.y,
+//!   -(p.z + c.z * s.z)) + brain offset, with voxel spacing s (1 = FEAGI units)
+//!
+//! Area-local to brain-world is the transform the renderer's StaticBody3D (scale = dimensions,
+//! centered on the area) and the half-voxel MultiMesh offset add up to.
+
+use godot::prelude::{Basis, Transform3D, Vector3};
+use std::collections::HashMap;
+
+/// Placement of one cortical area.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct AreaSpace {
+    /// Area size in voxels
+    pub dimensions: Vector3,
+    /// FEAGI coordinate of the area's lower-left-front corner
+    pub position: Vector3,
+    /// Brain-world size of one voxel per axis (different values scale anisotropically)
+    pub spacing: Vector3,
+    /// Fraction of each cell left empty around the drawn voxel (0 = touching cubes)
+    pub gap: f32,
+    /// Brain-world translation applied to every area (e.g. a brain region's placement)
+    pub brain_offset: Vector3,
+}
+
+impl AreaSpace {
+    /// Area at the FEAGI origin with unit spacing and no gap.
+    pub fn new(dimensions: Vector3) -> Self {
+        Self {
+            dimensions,
+            position: Vector3::ZERO,
+            spacing: Vector3::ONE,
+            gap: 0.0,
+            brain_offset: Vector3::ZERO,
+        }
+    }
+
+    /// Area-local distance between neighbouring voxels, per axis (Z negative).
+    pub fn voxel_pitch(&self) -> [f32; 3] {
+        let d = self.dimensions;
+        [1.0 / d.x, 1.0 / d.y, -1.0 / d.z]
+    }
+
+    /// Area-local position where voxel (x, y, z) is drawn.
+    pub fn voxel_to_local(&self, x: u32, y: u32, z: u32) -> Vector3 {
+        self.cell_to_local([x as f32 + 0.5, y as f32 + 0.5, z as f32 + 0.5])
+    }
+
+    /// Area-local position of a cell-space point.
+    pub fn cell_to_local(&self, cell: [f32; 3]) -> Vector3 {
+        let d = self.dimensions;
+        Vector3::new(
+            (cell[0] - 0.5 - d.x / 2.0) / d.x,
+            (cell[1] - 0.5 - d.y / 2.0) / d.y,
+            -(cell[2] - 0.5 - d.z / 2.0) / d.z,
+        )
+    }
+
+    /// Cell-space position of an area-local point.
+    pub fn local_to_cell(&self, local: Vector3) -> [f32; 3] {
+        let d = self.dimensions;
+        [
+            local.x * d.x + d.x / 2.0 + 0.5,
+            local.y * d.y + d.y / 2.0 + 0.5,
+            -local.z * d.z + d.z / 2.0 + 0.5,
+        ]
+    }
+
+    /// Brain-world position of a cell-space point.
+    pub fn cell_to_world(&self, cell: [f32; 3]) -> Vector3 {
+        let (p, s) = (self.position, self.spacing);
+        self.brain_offset
+            + Vector3::new(
+                p.x + cell[0] * s.x,
+                p.y + cell[1] * s.y,
+                -(p.z + cell[2] * s.z),
+            )
+    }
+
+    /// Cell-space position of a brain-world point.
+    pub fn world_to_cell(&self, world: Vector3) -> [f32; 3] {
+        let (p, s) = (self.position, self.spacing);
+        let w = world - self.brain_offset;
+        [(w.x - p.x) / s.x, (w.y - p.y) / s.y, (-w.z - p.z) / s.z]
+    }
+
+    /// Brain-world center of voxel (x, y, z).
+    pub fn voxel_to_world(&self, x: u32, y: u32, z: u32) -> Vector3 {
+        self.cell_to_world([x as f32 + 0.5, y as f32 + 0.5, z as f32 + 0.5])
+    }
+
+    /// Voxel containing a brain-world point, if it lies inside the area.
+    pub fn world_to_voxel(&self, world: Vector3) -> Option<[u32; 3]> {
+        let cell = self.world_to_cell(world);
+        let limits = [self.dimensions.x, self.dimensions.y, self.dimensions.z];
+        let mut voxel = [0u32; 3];
+        for a in 0..3 {
+            let v = cell[a].floor();
+            if !(0.0..limits[a].ceil()).contains(&v) {
+                return None;
+            }
+            voxel[a] = v as u32;
+        }
+        Some(voxel)
+    }
+
+    /// Area-local to brain-world transform (the MultiMesh node's transform in brain-world).
+    pub fn local_to_world(&self) -> Transform3D {
+        let (d, s) = (self.dimensions, self.spacing);
+        let origin = self.cell_to_world([d.x / 2.0 + 0.5, d.y / 2.0 + 0.5, d.z / 2.0 + 0.5]);
+        Transform3D::new(
+            Basis::from_scale(Vector3::new(d.x * s.x, d.y * s.y, d.z * s.z)),
+            origin,
+        )
+    }
+
+    /// Instance transform of voxel (x, y, z) as a row-major 3x4 matrix: the voxel's
+    /// area-local position with a basis of one voxel, shrunk by the gap.
+    #[inline]
+    pub fn instance_transform(&self, x: u32, y: u32, z: u32) -> [f32; 12] {
+        let origin = self.voxel_to_local(x, y, z);
+        let size = 1.0 - self.gap;
+        let [px, py, pz] = self.voxel_pitch();
+        [
+            px * size,
+            0.0,
+            0.0,
+            origin.x,
+            0.0,
+            py * size,
+            0.0,
+            origin.y,
+            0.0,
+            0.0,
+            pz * size,
+            origin.z,
+        ]
+    }
+}
+
+/// Brain-level placement settings and the FEAGI position and dimensions of each area.
+pub(crate) struct Placement {
+    pub spacing: Vector3,
+    pub gap: f32,
+    pub brain_offset: Vector3,
+    /// Cortical ID -> (FEAGI corner position, dimensions)
+    areas: HashMap<String, (Vector3, Vector3)>,
+}
+
+impl Default for Placement {
+    fn default() -> Self {
+        Self {
+            spacing: Vector3::ONE,
+            gap: 0.0,
+            brain_offset: Vector3::ZERO,
+            areas: HashMap::new(),
+        }
+    }
+}
+
+impl Placement {
+    /// Space used to draw an area of the given dimensions (instance transforms only depend on
+    /// dimensions and gap).
+    pub fn space(&self, dimensions: Vector3) -> AreaSpace {
+        AreaSpace {
+            dimensions,
+            position: Vector3::ZERO,
+            spacing: self.spacing,
+            gap: self.gap,
+            brain_offset: self.brain_offset,
+        }
+    }
+
+    /// Full space of a registered area.
+    pub fn area_space(&self, cortical_id: &str) -> Option<AreaSpace> {
+        let &(position, dimensions) = self.areas.get(cortical_id)?;
+        Some(AreaSpace {
+            position,
+            ..self.space(dimensions)
+        })
+    }
+
+    pub fn set_area(&mut self, cortical_id: &str, position: Vector3, dimensions: Vector3) {
+        self.areas
+            .insert(cortical_id.to_string(), (position, dimensions));
+    }
+
+    pub fn remove_area(&mut self, cortical_id: &str) -> bool {
+        self.areas.remove(cortical_id).is_some()
+    }
+
+    pub fn clear_areas(&mut self) {
+        self.areas.clear();
+    }
+
+    pub fn area_ids(&self) -> impl Iterator<Item = &String> {
+        self.areas.keys()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Non-cubic area with anisotropic spacing, a gap and a brain offset.
+    fn placed_space() -> AreaSpace {
+        let mut placement = Placement {
+            spacing: Vector3::new(2.0, 0.5, 3.0),
+            gap: 0.25,
+            brain_offset: Vector3::new(-7.0, 11.0, 4.5),
+            ..Placement::default()
+        };
+        placement.set_area(
+            "area",
+            Vector3::new(10.0, -3.0, 6.0),
+            Vector3::new(5.0, 3.0, 8.0),
+        );
+        placement.area_space("area").unwrap()
+    }
+
+    fn voxels(space: &AreaSpace) -> impl Iterator<Item = (u32, u32, u32)> {
+        let d = space.dimensions;
+        let (nx, ny, nz) = (d.x as u32, d.y as u32, d.z as u32);
+        (0..nx).flat_map(move |x| (0..ny).flat_map(move |y| (0..nz).map(move |z| (x, y, z))))
+    }
+
+    fn assert_near(a: Vector3, b: Vector3) {
+        assert!((a - b).length() < 1e-4, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn voxel_local_world_voxel_round_trip() {
+        let space = placed_space();
+        let local_to_world = space.local_to_world();
+        for (x, y, z) in voxels(&space) {
+            let local = space.voxel_to_local(x, y, z);
+            let world = local_to_world * local;
+            assert_near(world, space.voxel_to_world(x, y, z));
+            assert_eq!(space.world_to_voxel(world), Some([x, y, z]));
+            assert_near(local_to_world.affine_inverse() * world, local);
+
+            let cell = space.local_to_cell(local);
+            let center = [x as f32 + 0.5, y as f32 + 0.5, z as f32 + 0.5];
+            for a in 0..3 {
+                assert!((cell[a] - center[a]).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn spacing_scales_each_axis() {
+        let space = placed_space();
+        let s = space.spacing;
+        let step_x = space.voxel_to_world(1, 0, 0) - space.voxel_to_world(0, 0, 0);
+        let step_y = space.voxel_to_world(0, 1, 0) - space.voxel_to_world(0, 0, 0);
+        let step_z = space.voxel_to_world(0, 0, 1) - space.voxel_to_world(0, 0, 0);
+        assert_near(step_x, Vector3::new(s.x, 0.0, 0.0));
+        assert_near(step_y, Vector3::new(0.0, s.y, 0.0));
+        assert_near(step_z, Vector3::new(0.0, 0.0, -s.z));
+
+        // Lower-left-front corner of voxel 0 sits at the FEAGI position (Z negated) + offset
+        let p = space.position;
+        assert_near(
+            space.cell_to_world([0.0; 3]),
+            space.brain_offset + Vector3::new(p.x, p.y, -p.z),
+        );
+        assert_eq!(
+            space.world_to_voxel(space.cell_to_world([-0.5, 0.5, 0.5])),
+            None
+        );
+        assert_eq!(
+            space.world_to_voxel(space.cell_to_world([0.5, 3.5, 0.5])),
+            None
+        );
+    }
+
+    #[test]
+    fn instance_transform_matches_legacy_placement() {
+        for dimensions in [
+            Vector3::new(5.0, 3.0, 8.0),
+            Vector3::new(1.0, 1.0, 1.0),
+            Vector3::new(64.0, 7.0, 33.0),
+        ] {
+            let space = AreaSpace::new(dimensions);
+            let d = dimensions;
+            for (x, y, z) in voxels(&space) {
+                // Legacy calculate_transform: origin (v - d/2) / d with Z negated, basis 1 / d
+                let expected = [
+                    1.0 / d.x,
+                    0.0,
+                    0.0,
+                    (x as f32 - d.x / 2.0) / d.x,
+                    0.0,
+                    1.0 / d.y,
+                    0.0,
+                    (y as f32 - d.y / 2.0) / d.y,
+                    0.0,
+                    0.0,
+                    -1.0 / d.z,
+                    -((z as f32 - d.z / 2.0) / d.z),
+                ];
+                assert_eq!(space.instance_transform(x, y, z), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn gap_only_shrinks_the_basis() {
+        let space = placed_space();
+        let no_gap = AreaSpace { gap: 0.0, ..space };
+        let with_gap = space.instance_transform(4, 2, 7);
+        let without = no_gap.instance_transform(4, 2, 7);
+        for i in [3, 7, 11] {
+            assert_eq!(with_gap[i], without[i]);
+        }
+        for i in [0, 5, 10] {
+            assert_eq!(with_gap[i], without[i] * (1.0 - space.gap));
+        }
+    }
+}
@@ -0,0 +1,342 @@
+//! Delta updates between consecutive visualization frames.
+//!
+//! For mostly-static activity most voxels of an area fire in consecutive bursts, so rebuilding
+//! the whole MultiMesh every frame repeats the same work. The tracker keeps the previous frame's
+//! active voxels per area in a dense slot table (slot == MultiMesh instance index) and reports
+//! which slots have to be rewritten:
+//!
+//! - a voxel that disappeared is swap-removed; the voxel moved into its slot is rewritten
+//! - a voxel that appeared is appended
+//! - a voxel that stayed keeps its slot (rewritten only if its size changed, or if its color
+//!   depends on the potential and the potential changed)
+//!
+//! The MultiMesh keeps spare capacity; `visible_instance_count` hides the unused tail.
+
+use crate::multimesh_buffer::VoxelInstance;
+use godot::prelude::Vector3;
+use std::collections::HashMap;
+
+type VoxelKey = (u32, u32, u32);
+
+/// What changed in one area between the previous and the current frame.
+#[derive(Default, Debug)]
+pub(crate) struct DeltaChanges {
+    pub added: usize,
+    pub removed: usize,
+    pub unchanged: usize,
+    /// Unchanged voxels rewritten because their size or potential-driven color changed
+    pub updated: usize,
+    /// Slots (instance indices) that must be rewritten, ascending
+    pub dirty_slots: Vec<usize>,
+}
+
+/// Slot table of one area.
+#[derive(Default)]
+pub(crate) struct AreaDelta {
+    voxels: Vec<VoxelInstance>,
+    index: HashMap<VoxelKey, usize>,
+    /// MultiMesh instance_count this table was written with (0 = never written)
+    pub capacity: usize,
+    /// Area dimensions this table was written with
+    pub dimensions: Vector3,
+}
+
+impl AreaDelta {
+    /// Active voxels in slot order.
+    pub fn active(&self) -> &[VoxelInstance] {
+        &self.voxels
+    }
+
+    /// Forget all slots (the next update reports every voxel as added).
+    pub fn reset(&mut self) {
+        self.voxels.clear();
+        self.index.clear();
+        self.capacity = 0;
+    }
+
+    /// Replace the active set with `frame` and report the slots to rewrite.
+    ///
+    /// Duplicate coordinates in `frame` keep the last occurrence.
+    pub fn update(&mut self, frame: &[VoxelInstance], potential_sensitive: bool) -> DeltaChanges {
+        let mut next: HashMap<VoxelKey, VoxelInstance> = HashMap::with_capacity(frame.len());
+        let mut next_order: Vec<VoxelKey> = Vec::with_capacity(frame.len());
+        for voxel in frame {
+            let key = (voxel.x, voxel.y, voxel.z);
+            if next.insert(key, *voxel).is_none() {
+                next_order.push(key);
+            }
+        }
+
+        let mut changes = DeltaChanges::default();
+        let mut dirty = vec![false; self.voxels.len()];
+
+        // Removals, highest slot first: the element swapped in from the end is never one
+        // that is still waiting to be removed.
+        let mut removed_slots: Vec<usize> = self
+            .voxels
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| !next.contains_key(&(v.x, v.y, v.z)))
+            .map(|(slot, _)| slot)
+            .collect();
+        removed_slots.sort_unstable_by(|a, b| b.cmp(a));
+        for slot in removed_slots {
+            let removed = self.voxels.swap_remove(slot);
+            self.index.remove(&(removed.x, removed.y, removed.z));
+            if slot < self.voxels.len() {
+                let moved = self.voxels[slot];
+                self.index.insert((moved.x, moved.y, moved.z), slot);
+                dirty[slot] = true;
+            }
+            changes.removed += 1;
+        }
+        dirty.truncate(self.voxels.len());
+
+        for key in next_order {
+            let voxel = next[&key];
+            match self.index.get(&key) {
+                Some(&slot) => {
+                    changes.unchanged += 1;
+                    let previous = &mut self.voxels[slot];
+                    let resized = previous.extent != voxel.extent || previous.size != voxel.size;
+                    if resized || (potential_sensitive && previous.potential != voxel.potential) {
+                        changes.updated += 1;
+                        dirty[slot] = true;
+                    }
+                    *previous = voxel;
+                }
+                None => {
+                    self.index.insert(key, self.voxels.len());
+                    self.voxels.push(voxel);
+                    dirty.push(true);
+                    changes.added += 1;
+                }
+            }
+        }
+
+        changes.dirty_slots = dirty
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| **d)
+            .map(|(slot, _)| slot)
+            .collect();
+        changes
+    }
+}
+
+/// MultiMesh instance count for `active` voxels, with headroom so small growth does not
+/// force a full rewrite.
+pub(crate) fn capacity_for(active: usize) -> usize {
+    active + (active / 4).max(16)
+}
+
+/// Delta-mode state of a deserializer.
+#[derive(Default)]
+pub(crate) struct DeltaTracker {
+    pub enabled: bool,
+    areas: HashMap<String, AreaDelta>,
+}
+
+impl DeltaTracker {
+    /// Remove an area's slot table for updating (a fresh one if the area is new).
+    pub fn take_area(&mut self, cortical_id: &str) -> AreaDelta {
+        self.areas.remove(cortical_id).unwrap_or_default()
+    }
+
+    pub fn put_area(&mut self, cortical_id: &str, area: AreaDelta) {
+        self.areas.insert(cortical_id.to_string(), area);
+    }
+
+    /// Active voxels of an area in slot order (empty if the area has no slot table).
+    pub fn active(&self, cortical_id: &str) -> &[VoxelInstance] {
+        self.areas
+            .get(cortical_id)
+            .map(|area| area.active())
+            .unwrap_or(&[])
+    }
+
+    /// Cortical IDs with a slot table.
+    pub fn area_ids(&self) -> Vec<String> {
+        self.areas.keys().cloned().collect()
+    }
+
+    /// Forget all slot tables; the next frame rewrites every area completely.
+    pub fn clear(&mut self) {
+        self.areas.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn voxel(x: u32, y: u32, z: u32, potential: f32) -> VoxelInstance {
+        VoxelInstance {
+            x,
+            y,
+            z,
+            potential,
+            intensity: 1.0,
+            extent: 1,
+            size: 1.0,
+        }
+    }
+
+    fn keys(voxels: &[VoxelInstance]) -> Vec<VoxelKey> {
+        voxels.iter().map(|v| (v.x, v.y, v.z)).collect()
+    }
+
+    /// Every slot is indexed under its own coordinate and nothing else is indexed.
+    fn assert_consistent(area: &AreaDelta) {
+        assert_eq!(area.index.len(), area.voxels.len());
+        for (slot, v) in area.voxels.iter().enumerate() {
+            assert_eq!(area.index.get(&(v.x, v.y, v.z)), Some(&slot));
+        }
+    }
+
+    #[test]
+    fn first_frame_adds_every_voxel() {
+        let mut area = AreaDelta::default();
+        let frame = [
+            voxel(0, 0, 0, 1.0),
+            voxel(1, 0, 0, 1.0),
+            voxel(2, 0, 0, 1.0),
+        ];
+        let changes = area.update(&frame, false);
+        assert_eq!(
+            (changes.added, changes.removed, changes.unchanged),
+            (3, 0, 0)
+        );
+        assert_eq!(changes.dirty_slots, vec![0, 1, 2]);
+        assert_eq!(keys(area.active()), keys(&frame));
+        assert_consistent(&area);
+    }
+
+    #[test]
+    fn added_removed_and_unchanged_voxels() {
+        let mut area = AreaDelta::default();
+        let [a, b, c, d, e] = [0, 1, 2, 3, 4].map(|x| voxel(x, 0, 0, 1.0));
+        area.update(&[a, b, c, d], false);
+
+        // d is the last slot and goes away; b's slot is refilled with c, e is appended
+        let changes = area.update(&[a, c, e], false);
+        assert_eq!(changes.added, 1);
+        assert_eq!(changes.removed, 2);
+        assert_eq!(changes.unchanged, 2);
+        assert_eq!(changes.updated, 0);
+        assert_eq!(changes.dirty_slots, vec![1, 2]);
+        assert_eq!(keys(area.active()), keys(&[a, c, e]));
+        assert_consistent(&area);
+
+        // The same frame again touches nothing
+        let changes = area.update(&[e, a, c], false);
+        assert_eq!(
+            (changes.added, changes.removed, changes.unchanged),
+            (0, 0, 3)
+        );
+        assert!(changes.dirty_slots.is_empty());
+        assert_eq!(keys(area.active()), keys(&[a, c, e]));
+
+        let changes = area.update(&[], false);
+        assert_eq!(changes.removed, 3);
+        assert!(changes.dirty_slots.is_empty());
+        assert!(area.active().is_empty());
+        assert_consistent(&area);
+    }
+
+    #[test]
+    fn potential_changes_rewrite_only_potential_sensitive_colors() {
+        let mut area = AreaDelta::default();
+        area.update(&[voxel(0, 0, 0, 1.0), voxel(1, 0, 0, 1.0)], false);
+
+        let changes = area.update(&[voxel(0, 0, 0, 1.0), voxel(1, 0, 0, 0.25)], false);
+        assert_eq!((changes.unchanged, changes.updated), (2, 0));
+        assert!(changes.dirty_slots.is_empty());
+        // The stored voxel still follows the frame
+        assert_eq!(area.active()[1].potential, 0.25);
+
+        let changes = area.update(&[voxel(0, 0, 0, 1.0), voxel(1, 0, 0, 0.5)], true);
+        assert_eq!((changes.unchanged, changes.updated), (2, 1));
+        assert_eq!(changes.dirty_slots, vec![1]);
+
+        // Size changes are rewritten either way
+        let mut resized = voxel(0, 0, 0, 1.0);
+        resized.size = 0.5;
+        let changes = area.update(&[resized, voxel(1, 0, 0, 0.5)], false);
+        assert_eq!(changes.updated, 1);
+        assert_eq!(changes.dirty_slots, vec![0]);
+    }
+
+    #[test]
+    fn duplicate_coordinates_keep_the_last_occurrence() {
+        let mut area = AreaDelta::default();
+        let changes = area.update(&[voxel(1, 1, 1, 0.1), voxel(1, 1, 1, 0.9)], false);
+        assert_eq!(changes.added, 1);
+        assert_eq!(area.active().len(), 1);
+        assert_eq!(area.active()[0].potential, 0.9);
+    }
+
+    #[test]
+    fn slots_stay_consistent_across_churn() {
+        // Deterministic pseudo-random frames over a 6x6 plane
+        let mut state = 0x2545_f491_u32;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state
+        };
+        let mut area = AreaDelta::default();
+        for _ in 0..200 {
+            let frame: Vec<VoxelInstance> = (0..36)
+                .filter(|_| next() % 3 == 0)
+                .map(|i| voxel(i % 6, i / 6, 0, 1.0))
+                .collect();
+            let before = area.active().len();
+            let changes = area.update(&frame, false);
+            assert_consistent(&area);
+
+            let mut expected = keys(&frame);
+            let mut active = keys(area.active());
+            expected.sort_unstable();
+            active.sort_unstable();
+            assert_eq!(active, expected);
+            assert_eq!(
+                before - changes.removed + changes.added,
+                area.active().len()
+            );
+            assert_eq!(changes.unchanged + changes.added, frame.len());
+            assert!(changes.dirty_slots.windows(2).all(|w| w[0] < w[1]));
+            assert!(changes.dirty_slots.iter().all(|&s| s < area.active().len()));
+        }
+    }
+
+    #[test]
+    fn reset_reports_every_voxel_as_added() {
+        let mut area = AreaDelta::default();
+        let frame = [voxel(0, 0, 0, 1.0), voxel(0, 1, 0, 1.0)];
+        area.update(&frame, false);
+        area.capacity = capacity_for(2);
+        area.reset();
+        assert_eq!(area.capacity, 0);
+        let changes = area.update(&frame, false);
+        assert_eq!((changes.added, changes.unchanged), (2, 0));
+        assert_eq!(changes.dirty_slots, vec![0, 1]);
+    }
+
+    #[test]
+    fn capacity_leaves_headroom() {
+        assert_eq!(capacity_for(0), 16);
+        assert_eq!(capacity_for(10), 26);
+        assert_eq!(capacity_for(64), 80);
+        assert_eq!(capacity_for(100), 125);
+        assert_eq!(capacity_for(1000), 1250);
+        let mut previous = 0;
+        for active in 0..5000 {
+            let capacity = capacity_for(active);
+            assert!(capacity >= active + 16);
+            assert!(capacity >= previous);
+            previous = capacity;
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! Type 11 encoder: the inverse of the ingest path.
+//!
+//! Builds `CorticalMappedXYZPNeuronVoxels` from per-area x/y/z/p arrays, serializes it as a raw
+//! Type 11 structure or inside a FeagiByteContainer stamped with an agent ID, and optionally
+//! compresses the result the same way the PNS layer does. Every packet produced here is
+//! accepted by `ingest::ingest`, so tests and synthetic activity exercise the real decode paths.
+
+use crate::ingest::Compression;
+use feagi_serialization::{FeagiByteContainer, FeagiSerializable};
+use feagi_structures::genomic::cortical_area::CorticalID;
+use feagi_structures::neuron_voxels::xyzp::{
+    CorticalMappedXYZPNeuronVoxels, NeuronVoxelXYZP, NeuronVoxelXYZPArrays,
+};
+use godot::prelude::*;
+use std::io::Write;
+
+/// Fired neurons of one cortical area, as parallel arrays.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct AreaActivity {
+    /// Base64 cortical ID (the key format of decode results)
+    pub cortical_id: String,
+    pub x: Vec<u32>,
+    pub y: Vec<u32>,
+    pub z: Vec<u32>,
+    pub potential: Vec<f32>,
+}
+
+impl AreaActivity {
+    pub fn new(cortical_id: &str) -> Self {
+        Self {
+            cortical_id: cortical_id.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Add one fired neuron.
+    #[inline]
+    pub fn push(&mut self, x: u32, y: u32, z: u32, potential: f32) {
+        self.x.push(x);
+        self.y.push(y);
+        self.z.push(z);
+        self.potential.push(potential);
+    }
+
+    pub fn len(&self) -> usize {
+        self.x.len()
+    }
+
+    /// Arrays of one area from a Dictionary shaped like a decode result's area entry
+    /// ({x_array, y_array, z_array: PackedInt32Array, p_array: PackedFloat32Array}).
+    pub fn from_dictionary(cortical_id: String, area: &Dictionary) -> Result<Self, String> {
+        let coordinates = |name: &str| -> Result<Vec<u32>, String> {
+            let array = area
+                .get(name)
+                .and_then(|v| v.try_to::<PackedInt32Array>().ok())
+                .ok_or_else(|| format!("Area {}: missing {}", cortical_id, name))?;
+            array
+                .as_slice()
+                .iter()
+                .map(|&c| {
+                    u32::try_from(c).map_err(|_| {
+                        format!("Area {}: negative coordinate in {}", cortical_id, name)
+                    })
+                })
+                .collect()
+        };
+        let potential = area
+            .get("p_array")
+            .and_then(|v| v.try_to::<PackedFloat32Array>().ok())
+            .ok_or_else(|| format!("Area {}: missing p_array", cortical_id))?;
+        Ok(Self {
+            x: coordinates("x_array")?,
+            y: coordinates("y_array")?,
+            z: coordinates("z_array")?,
+            potential: potential.to_vec(),
+            cortical_id,
+        })
+    }
+}
+
+/// How the serialized structure is packaged.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Envelope<'a> {
+    /// Bare Type 11 structure (first byte == 11)
+    RawType11,
+    /// FeagiByteContainer holding the structure
+    Container {
+        /// Agent ID bytes (`FeagiByteContainer::AGENT_ID_BYTE_COUNT` long)
+        agent_id: &'a [u8],
+        /// Container increment counter
+        increment: u16,
+    },
+}
+
+/// Build the neuron voxel structure. Areas listed more than once are merged.
+pub(crate) fn build_neuron_voxels(
+    areas: &[AreaActivity],
+) -> Result<CorticalMappedXYZPNeuronVoxels, String> {
+    let mut neuron_data = CorticalMappedXYZPNeuronVoxels::new();
+    for area in areas {
+        let n = area.x.len();
+        if area.y.len() != n || area.z.len() != n || area.potential.len() != n {
+            return Err(format!(
+                "Area {}: array lengths differ (x={}, y={}, z={}, p={})",
+                area.cortical_id,
+                n,
+                area.y.len(),
+                area.z.len(),
+                area.potential.len()
+            ));
+        }
+        let cortical_id = CorticalID::try_from_base_64(&area.cortical_id)
+            .map_err(|e| format!("Invalid cortical ID {}: {:?}", area.cortical_id, e))?;
+
+        let neurons = (0..n)
+            .map(|i| NeuronVoxelXYZP::new(area.x[i], area.y[i], area.z[i], area.potential[i]));
+        match neuron_data.mappings.get_mut(&cortical_id) {
+            Some(existing) => neurons.for_each(|neuron| existing.push(&neuron)),
+            None => {
+                let mut neuron_array = NeuronVoxelXYZPArrays::new();
+                neurons.for_each(|neuron| neuron_array.push(&neuron));
+                neuron_data.mappings.insert(cortical_id, neuron_array);
+            }
+        }
+    }
+    Ok(neuron_data)
+}
+
+/// Serialize the structure as raw Type 11 bytes.
+pub(crate) fn serialize_raw_type11(
+    neuron_data: &CorticalMappedXYZPNeuronVoxels,
+) -> Result<Vec<u8>, String> {
+    let mut bytes = vec![0u8; neuron_data.get_number_of_bytes_needed()];
+    neuron_data
+        .try_serialize_struct_to_byte_slice(&mut bytes)
+        .map_err(|e| format!("Type 11 serialize error: {:?}", e))?;
+    Ok(bytes)
+}
+
+/// Serialize the structure into a FeagiByteContainer stamped with `agent_id`.
+pub(crate) fn serialize_container(
+    neuron_data: &CorticalMappedXYZPNeuronVoxels,
+    agent_id: &[u8],
+    increment: u16,
+) -> Result<Vec<u8>, String> {
+    if agent_id.len() != FeagiByteContainer::AGENT_ID_BYTE_COUNT {
+        return Err(format!(
+            "Agent ID must be {} bytes, got {}",
+            FeagiByteContainer::AGENT_ID_BYTE_COUNT,
+            agent_id.len()
+        ));
+    }
+    let mut container = FeagiByteContainer::new_empty();
+    container
+        .overwrite_byte_data_with_single_struct_data(neuron_data, increment)
+        .map_err(|e| format!("Container serialize error: {:?}", e))?;
+    let mut bytes = container.get_byte_ref().to_vec();
+
+    // The agent ID follows the global header
+    let start = FeagiByteContainer::GLOBAL_BYTE_HEADER_BYTE_COUNT;
+    let end = start + FeagiByteContainer::AGENT_ID_BYTE_COUNT;
+    if bytes.len() < end {
+        return Err(format!(
+            "Container of {} bytes has no room for an agent ID",
+            bytes.len()
+        ));
+    }
+    bytes[start..end].copy_from_slice(agent_id);
+
+    // Make sure the stamped bytes still read back as a valid container
+    FeagiByteContainer::new_empty()
+        .try_write_data_by_copy_and_verify(&bytes)
+        .map_err(|e| format!("Encoded container failed verification: {:?}", e))?;
+    Ok(bytes)
+}
+
+/// Compress a payload the way the PNS layer does (no-op for `Compression::None`).
+pub(crate) fn compress(payload: Vec<u8>, compression: Compression) -> Result<Vec<u8>, String> {
+    match compression {
+        Compression::None => Ok(payload),
+        Compression::Lz4Block => {
+            lz4::block::compress(&payload, None, true).map_err(|e| format!("LZ4 block: {}", e))
+        }
+        Compression::Lz4Frame => {
+            let mut encoder = lz4::EncoderBuilder::new()
+                .build(Vec::with_capacity(payload.len() / 2))
+                .map_err(|e| format!("LZ4 frame: {}", e))?;
+            encoder
+                .write_all(&payload)
+                .map_err(|e| format!("LZ4 frame: {}", e))?;
+            let (out, result) = encoder.finish();
+            result.map_err(|e| format!("LZ4 frame: {}", e))?;
+            Ok(out)
+        }
+    }
+}
+
+/// Encode areas into a complete visualization packet.
+///
+/// Returns the packet and the size of the payload before compression.
+pub(crate) fn encode(
+    areas: &[AreaActivity],
+    envelope: Envelope,
+    compression: Compression,
+) -> Result<(Vec<u8>, usize), String> {
+    let neuron_data = build_neuron_voxels(areas)?;
+    let payload = match envelope {
+        Envelope::RawType11 => serialize_raw_type11(&neuron_data)?,
+        Envelope::Container {
+            agent_id,
+            increment,
+        } => serialize_container(&neuron_data, agent_id, increment)?,
+    };
+    let payload_bytes = payload.len();
+    Ok((compress(payload, compression)?, payload_bytes))
+}
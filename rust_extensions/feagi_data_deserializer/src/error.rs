@@ -0,0 +1,245 @@
+//! Typed decode errors and rate-limited logging.
+//!
+//! Every failure of the packet paths carries an `ErrorKind` with a stable numeric code, which
+//! result dictionaries expose as `error_code` next to the human-readable `error` message
+//! (codes are also GDScript constants, ERROR_*). Console output goes through `RateLimitedLog`
+//! so a stream of bad packets at 60 Hz prints one line per second instead of sixty.
+
+use godot::prelude::*;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// `error_code` of successful results
+pub(crate) const ERROR_NONE: i32 = 0;
+
+/// Category of a failure. Codes are stable: never renumber, only append.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum ErrorKind {
+    /// Packet or input arrays are empty
+    EmptyBuffer,
+    /// Container version or payload format this build does not understand
+    UnsupportedVersion,
+    /// Container or Type 11 bytes failed to parse
+    CorruptContainer,
+    /// A structure of another type where neuron voxels were expected
+    WrongStructureType,
+    /// Payload above the decompression ceiling or decode limits
+    OversizedPayload,
+    /// LZ4 block or frame failed to decompress
+    Lz4Failure,
+    /// Invalid arguments from the caller (mismatched arrays, unknown IDs, bad settings)
+    InvalidArgument,
+    /// File system failure (exports)
+    IoFailure,
+    /// Panic caught at the API boundary
+    Internal,
+}
+
+impl ErrorKind {
+    pub fn code(self) -> i32 {
+        match self {
+            ErrorKind::EmptyBuffer => 1,
+            ErrorKind::UnsupportedVersion => 2,
+            ErrorKind::CorruptContainer => 3,
+            ErrorKind::WrongStructureType => 4,
+            ErrorKind::OversizedPayload => 5,
+            ErrorKind::Lz4Failure => 6,
+            ErrorKind::InvalidArgument => 7,
+            ErrorKind::IoFailure => 8,
+            ErrorKind::Internal => 9,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorKind::EmptyBuffer => "empty_buffer",
+            ErrorKind::UnsupportedVersion => "unsupported_version",
+            ErrorKind::CorruptContainer => "corrupt_container",
+            ErrorKind::WrongStructureType => "wrong_structure_type",
+            ErrorKind::OversizedPayload => "oversized_payload",
+            ErrorKind::Lz4Failure => "lz4_failure",
+            ErrorKind::InvalidArgument => "invalid_argument",
+            ErrorKind::IoFailure => "io_failure",
+            ErrorKind::Internal => "internal",
+        }
+    }
+}
+
+/// A failure with its category and a precise message.
+#[derive(Clone, Debug)]
+pub(crate) struct DecodeError {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+impl DecodeError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    /// Same kind, message prefixed with context.
+    pub fn context(self, prefix: impl fmt::Display) -> Self {
+        Self {
+            kind: self.kind,
+            message: format!("{}: {}", prefix, self.message),
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Set `error` and `error_code` of a result dictionary (empty / ERROR_NONE for `None`).
+pub(crate) fn set_error(dict: &mut Dictionary, error: Option<&DecodeError>) {
+    match error {
+        Some(e) => {
+            dict.set("error", e.message.as_str());
+            dict.set("error_code", e.kind.code());
+        }
+        None => {
+            dict.set("error", "");
+            dict.set("error_code", ERROR_NONE);
+        }
+    }
+}
+
+/// Console verbosity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Verbosity {
+    Silent,
+    /// Failures only (default)
+    Errors,
+    /// Failures and occasional status messages
+    Info,
+    /// Per-packet diagnostics
+    Debug,
+}
+
+impl Verbosity {
+    pub fn from_i32(verbosity: i32) -> Option<Self> {
+        match verbosity {
+            0 => Some(Verbosity::Silent),
+            1 => Some(Verbosity::Errors),
+            2 => Some(Verbosity::Info),
+            3 => Some(Verbosity::Debug),
+            _ => None,
+        }
+    }
+
+    pub fn as_i32(self) -> i32 {
+        match self {
+            Verbosity::Silent => 0,
+            Verbosity::Errors => 1,
+            Verbosity::Info => 2,
+            Verbosity::Debug => 3,
+        }
+    }
+}
+
+/// Rate-limit state of one message key.
+struct KeyState {
+    last_logged: Instant,
+    suppressed: u64,
+}
+
+/// Console logger that prints each message key at most once per interval.
+///
+/// Keys are a category tag (e.g. "[DECODE]") plus the error kind, so different failures are
+/// limited independently. The next line printed for a key reports how many were suppressed.
+pub(crate) struct RateLimitedLog {
+    pub verbosity: Verbosity,
+    pub interval: Duration,
+    keys: Mutex<HashMap<(&'static str, i32), KeyState>>,
+}
+
+impl Default for RateLimitedLog {
+    fn default() -> Self {
+        Self {
+            verbosity: Verbosity::Errors,
+            interval: Duration::from_secs(1),
+            keys: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl RateLimitedLog {
+    /// Log a failure of the packet paths.
+    pub fn error(&self, category: &'static str, error: &DecodeError) {
+        if self.verbosity < Verbosity::Errors {
+            return;
+        }
+        if let Some(suffix) = self.admit(category, error.kind.code()) {
+            godot_error!(
+                "🦀 {} {} [{}]{}",
+                category,
+                error.message,
+                error.kind.as_str(),
+                suffix
+            );
+        }
+    }
+
+    /// Log a status message (built lazily, only when it will be printed).
+    pub fn info<F: FnOnce() -> String>(&self, category: &'static str, message: F) {
+        self.message(Verbosity::Info, category, message);
+    }
+
+    /// Log a per-packet diagnostic (built lazily, only when it will be printed).
+    pub fn debug<F: FnOnce() -> String>(&self, category: &'static str, message: F) {
+        self.message(Verbosity::Debug, category, message);
+    }
+
+    fn message<F: FnOnce() -> String>(&self, level: Verbosity, category: &'static str, message: F) {
+        if self.verbosity < level {
+            return;
+        }
+        // Debug output is meant to be complete; only info messages are rate-limited
+        let suffix = if level == Verbosity::Debug {
+            Some(String::new())
+        } else {
+            self.admit(category, ERROR_NONE)
+        };
+        if let Some(suffix) = suffix {
+            godot_print!("🦀 {} {}{}", category, message(), suffix);
+        }
+    }
+
+    /// Whether a key may print now; returns the suppression note to append.
+    fn admit(&self, category: &'static str, code: i32) -> Option<String> {
+        let now = Instant::now();
+        let mut keys = self.keys.lock().unwrap_or_else(|e| e.into_inner());
+        match keys.get_mut(&(category, code)) {
+            Some(state) if now.duration_since(state.last_logged) < self.interval => {
+                state.suppressed += 1;
+                None
+            }
+            Some(state) => {
+                let suppressed = std::mem::take(&mut state.suppressed);
+                state.last_logged = now;
+                Some(if suppressed > 0 {
+                    format!(" ({} similar messages suppressed)", suppressed)
+                } else {
+                    String::new()
+                })
+            }
+            None => {
+                keys.insert(
+                    (category, code),
+                    KeyState {
+                        last_logged: now,
+                        suppressed: 0,
+                    },
+                );
+                Some(String::new())
+            }
+        }
+    }
+}
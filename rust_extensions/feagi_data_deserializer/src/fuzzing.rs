@@ -0,0 +1,110 @@
+//! Engine-free entry points for fuzz targets and property tests (feature `fuzzing`).
+//!
+//! The decode paths are crate-private and normally reached through Godot classes, which need
+//! a running engine. These wrappers drive the same code with plain Rust types.
+
+use crate::bulk::BulkColumns;
+use crate::encoder::{self, AreaActivity, Envelope};
+use crate::ingest::{self, Compression, IngestLimits};
+
+/// Limits of `ingest_packet`, mirroring the deserializer's set_max_* settings.
+#[derive(Clone, Copy, Debug)]
+pub struct Limits {
+    pub max_decompressed_bytes: usize,
+    pub max_areas_per_frame: usize,
+    pub max_neurons_per_area: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        let limits = IngestLimits::default();
+        Self {
+            max_decompressed_bytes: limits.max_decompressed_bytes,
+            max_areas_per_frame: limits.max_areas_per_frame,
+            max_neurons_per_area: limits.max_neurons_per_area,
+        }
+    }
+}
+
+/// A decoded packet: neuron count per cortical area (base64 ID), sorted by ID.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedSummary {
+    pub areas: Vec<(String, usize)>,
+    pub payload_bytes: usize,
+}
+
+/// Run one packet through the ingest front door used by every decode and apply method.
+///
+/// Returns the summary, or the ERROR_* code and message of the failure.
+pub fn ingest_packet(packet: &[u8], limits: &Limits) -> Result<DecodedSummary, (i32, String)> {
+    let limits = IngestLimits {
+        max_decompressed_bytes: limits.max_decompressed_bytes,
+        max_areas_per_frame: limits.max_areas_per_frame,
+        max_neurons_per_area: limits.max_neurons_per_area,
+    };
+    let frame = ingest::ingest(packet.to_vec(), &limits).map_err(|e| (e.kind.code(), e.message))?;
+    let mut areas: Vec<(String, usize)> = frame
+        .payload
+        .neuron_data
+        .mappings
+        .iter()
+        .map(|(id, neurons)| (id.as_base_64(), neurons.len()))
+        .collect();
+    areas.sort();
+    Ok(DecodedSummary {
+        areas,
+        payload_bytes: frame.payload_bytes,
+    })
+}
+
+/// Encode areas of (x, y, z, potential) neurons, keyed by base64 cortical ID.
+///
+/// Args:
+///   - container: wrap in a FeagiByteContainer (zero agent ID) instead of raw Type 11
+///   - compression: 0 none, 1 LZ4 block, 2 LZ4 frame
+pub fn encode_packet(
+    areas: &[(String, Vec<(u32, u32, u32, f32)>)],
+    container: bool,
+    compression: i32,
+) -> Result<Vec<u8>, String> {
+    let compression = Compression::from_i32(compression)
+        .ok_or_else(|| format!("Unknown compression: {}", compression))?;
+    let areas: Vec<AreaActivity> = areas
+        .iter()
+        .map(|(id, neurons)| {
+            let mut area = AreaActivity::new(id);
+            for &(x, y, z, p) in neurons {
+                area.push(x, y, z, p);
+            }
+            area
+        })
+        .collect();
+    let agent_id = vec![0u8; feagi_serialization::FeagiByteContainer::AGENT_ID_BYTE_COUNT];
+    let envelope = if container {
+        Envelope::Container {
+            agent_id: &agent_id,
+            increment: 0,
+        }
+    } else {
+        Envelope::RawType11
+    };
+    encoder::encode(&areas, envelope, compression).map(|(packet, _)| packet)
+}
+
+/// Validate SoA byte columns and split them by an offset table, as
+/// convert_bulk_arrays_by_area does. Returns the neuron count of each area.
+pub fn split_bulk_columns(
+    x: &[u8],
+    y: &[u8],
+    z: &[u8],
+    p: &[u8],
+    area_count: usize,
+    offsets: &[i64],
+) -> Result<Vec<usize>, String> {
+    let columns = BulkColumns::from_bytes(x, y, z, p)?;
+    let ranges = columns.area_ranges(area_count, offsets)?;
+    Ok(ranges
+        .into_iter()
+        .map(|r| columns.gather(&[r]).len())
+        .collect())
+}
@@ -0,0 +1,765 @@
+//! Synthetic activity for offline demos and load testing.
+//!
+//! Areas are given a pattern and their dimensions; every frame is a pure function of the
+//! seed and the frame index, so a benchmark replays the exact same activity on every run and
+//! any frame can be regenerated on its own. Frames are encoded with the Type 11 encoder and
+//! go through the same ingest path as live FEAGI packets.
+//!
+//! Patterns:
+//!   - Poisson: every neuron fires independently at a fixed rate
+//!   - Wave: cosine wave traveling along an axis; neurons above a threshold fire
+//!   - Plane: a slab sweeping along an axis and wrapping around
+//!   - Blobs: Gaussian blobs drifting through the area on smooth seeded paths
+//!   - Replay: a prerecorded list of frames, looped
+
+use crate::encoder::{self, AreaActivity, Envelope};
+use crate::ingest::Compression;
+use crate::sampling::{area_key, mix64, voxel_hash};
+use crate::FeagiDataDeserializer;
+use feagi_structures::genomic::cortical_area::CorticalID;
+use godot::prelude::*;
+use std::collections::HashSet;
+use std::f64::consts::TAU;
+
+/// Default frame rate of the generator
+const DEFAULT_FRAME_RATE_HZ: f64 = 30.0;
+
+/// Above this firing probability Poisson areas test every neuron instead of drawing indices
+const DENSE_FIRING_PROBABILITY: f64 = 0.25;
+
+/// Activation threshold of a traveling wave
+const WAVE_THRESHOLD: f64 = 0.8;
+
+/// Activity pattern of one area.
+#[derive(Clone, Debug)]
+pub(crate) enum Pattern {
+    Poisson {
+        /// Firing rate of every neuron, in Hz
+        rate_hz: f64,
+    },
+    Wave {
+        axis: usize,
+        /// Distance between crests, in voxels
+        wavelength: f64,
+        /// Voxels per second
+        speed: f64,
+    },
+    Plane {
+        axis: usize,
+        /// Voxels per second
+        speed: f64,
+        /// Slab thickness in voxels
+        thickness: u32,
+    },
+    Blobs {
+        count: u32,
+        /// Standard deviation in voxels
+        sigma: f64,
+        /// Approximate drift in voxels per second
+        speed: f64,
+    },
+    Replay {
+        frames: Vec<AreaActivity>,
+    },
+}
+
+/// An area driven by a pattern.
+#[derive(Clone, Debug)]
+pub(crate) struct PatternArea {
+    pub cortical_id: String,
+    pub dimensions: [u32; 3],
+    pub pattern: Pattern,
+}
+
+/// Small deterministic random stream (SplitMix64).
+struct Stream(u64);
+
+impl Stream {
+    fn new(seed: u64) -> Self {
+        Self(mix64(seed))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        mix64(self.0)
+    }
+
+    /// Uniform in [0, 1).
+    fn next_f64(&mut self) -> f64 {
+        unit(self.next_u64())
+    }
+
+    /// Poisson-distributed count with mean `lambda` (normal approximation for large means).
+    fn poisson(&mut self, lambda: f64) -> u64 {
+        if lambda <= 0.0 {
+            return 0;
+        }
+        if lambda < 30.0 {
+            let limit = (-lambda).exp();
+            let mut product = self.next_f64();
+            let mut count = 0;
+            while product > limit {
+                count += 1;
+                product *= self.next_f64();
+            }
+            return count;
+        }
+        // Box-Muller
+        let u1 = self.next_f64().max(f64::MIN_POSITIVE);
+        let u2 = self.next_f64();
+        let normal = (-2.0 * u1.ln()).sqrt() * (TAU * u2).cos();
+        (lambda + lambda.sqrt() * normal).round().max(0.0) as u64
+    }
+}
+
+/// Hash to a uniform value in [0, 1).
+#[inline]
+fn unit(hash: u64) -> f64 {
+    (hash >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Deterministic frame source.
+pub(crate) struct ActivityGenerator {
+    pub areas: Vec<PatternArea>,
+    pub seed: u64,
+    pub frame_rate_hz: f64,
+}
+
+impl Default for ActivityGenerator {
+    fn default() -> Self {
+        Self {
+            areas: Vec::new(),
+            seed: 0,
+            frame_rate_hz: DEFAULT_FRAME_RATE_HZ,
+        }
+    }
+}
+
+impl ActivityGenerator {
+    /// Activity of every area at `frame_index`.
+    pub fn frame(&self, frame_index: u64) -> Vec<AreaActivity> {
+        let t = frame_index as f64 / self.frame_rate_hz;
+        self.areas
+            .iter()
+            .map(|area| {
+                let key = area_key(&area.cortical_id);
+                let frame_seed = mix64(self.seed ^ mix64(frame_index ^ key));
+                let mut out = AreaActivity::new(&area.cortical_id);
+                match &area.pattern {
+                    Pattern::Poisson { rate_hz } => {
+                        let p = (rate_hz / self.frame_rate_hz).clamp(0.0, 1.0);
+                        poisson(&mut out, area.dimensions, p, frame_seed, key);
+                    }
+                    Pattern::Wave {
+                        axis,
+                        wavelength,
+                        speed,
+                    } => wave(&mut out, area.dimensions, *axis, *wavelength, speed * t),
+                    Pattern::Plane {
+                        axis,
+                        speed,
+                        thickness,
+                    } => plane(&mut out, area.dimensions, *axis, *thickness, speed * t),
+                    Pattern::Blobs {
+                        count,
+                        sigma,
+                        speed,
+                    } => blobs(
+                        &mut out,
+                        area.dimensions,
+                        *count,
+                        *sigma,
+                        *speed * t,
+                        self.seed ^ key,
+                        frame_seed,
+                        key,
+                    ),
+                    Pattern::Replay { frames } => {
+                        if !frames.is_empty() {
+                            let recorded = &frames[(frame_index % frames.len() as u64) as usize];
+                            out.x.clone_from(&recorded.x);
+                            out.y.clone_from(&recorded.y);
+                            out.z.clone_from(&recorded.z);
+                            out.potential.clone_from(&recorded.potential);
+                        }
+                    }
+                }
+                out
+            })
+            .collect()
+    }
+}
+
+/// Voxel of a linear index (x fastest).
+#[inline]
+fn voxel_of(index: u64, d: [u32; 3]) -> (u32, u32, u32) {
+    let dx = d[0] as u64;
+    let dy = d[1] as u64;
+    (
+        (index % dx) as u32,
+        ((index / dx) % dy) as u32,
+        (index / (dx * dy)) as u32,
+    )
+}
+
+fn volume(d: [u32; 3]) -> u64 {
+    d.iter().map(|&v| v as u64).product()
+}
+
+fn poisson(out: &mut AreaActivity, d: [u32; 3], p: f64, frame_seed: u64, key: u64) {
+    let n = volume(d);
+    if n == 0 || p <= 0.0 {
+        return;
+    }
+    if p >= DENSE_FIRING_PROBABILITY {
+        for index in 0..n {
+            let (x, y, z) = voxel_of(index, d);
+            if unit(voxel_hash(frame_seed, key, x, y, z)) < p {
+                out.push(x, y, z, 1.0);
+            }
+        }
+        return;
+    }
+    // Sparse: draw the number of firing neurons, then which ones
+    let mut stream = Stream::new(frame_seed);
+    let count = stream.poisson(n as f64 * p).min(n);
+    // At most DENSE_FIRING_PROBABILITY of the area is drawn, so rejecting repeats stays cheap
+    let mut drawn: HashSet<u64> = HashSet::with_capacity(count as usize);
+    while (drawn.len() as u64) < count {
+        drawn.insert(stream.next_u64() % n);
+    }
+    let mut indices: Vec<u64> = drawn.into_iter().collect();
+    indices.sort_unstable();
+    for index in indices {
+        let (x, y, z) = voxel_of(index, d);
+        out.push(x, y, z, 1.0);
+    }
+}
+
+/// Push every voxel of the slab `axis == layer`.
+fn push_layer(out: &mut AreaActivity, d: [u32; 3], axis: usize, layer: u32, potential: f32) {
+    let (a, b) = match axis {
+        0 => (1, 2),
+        1 => (0, 2),
+        _ => (0, 1),
+    };
+    for i in 0..d[a] {
+        for j in 0..d[b] {
+            let mut v = [0u32; 3];
+            v[axis] = layer;
+            v[a] = i;
+            v[b] = j;
+            out.push(v[0], v[1], v[2], potential);
+        }
+    }
+}
+
+fn wave(out: &mut AreaActivity, d: [u32; 3], axis: usize, wavelength: f64, offset: f64) {
+    let wavelength = wavelength.max(1.0);
+    for layer in 0..d[axis] {
+        let activation = 0.5 * (1.0 + (TAU * (layer as f64 - offset) / wavelength).cos());
+        if activation >= WAVE_THRESHOLD {
+            push_layer(out, d, axis, layer, activation as f32);
+        }
+    }
+}
+
+fn plane(out: &mut AreaActivity, d: [u32; 3], axis: usize, thickness: u32, offset: f64) {
+    let length = d[axis];
+    if length == 0 {
+        return;
+    }
+    let front = offset.rem_euclid(length as f64) as u32;
+    for i in 0..thickness.clamp(1, length) {
+        push_layer(out, d, axis, (front + i) % length, 1.0);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn blobs(
+    out: &mut AreaActivity,
+    d: [u32; 3],
+    count: u32,
+    sigma: f64,
+    distance: f64,
+    path_seed: u64,
+    frame_seed: u64,
+    key: u64,
+) {
+    let sigma = sigma.max(0.5);
+    let radius = (3.0 * sigma).ceil() as i64;
+    // (linear index, potential) of every firing voxel, merged across blobs
+    let mut fired: Vec<(u64, f32)> = Vec::new();
+    for blob in 0..count {
+        // Each axis oscillates across the area with its own seeded frequency and phase
+        let mut center = [0.0f64; 3];
+        let mut path = Stream::new(path_seed ^ mix64(blob as u64));
+        for a in 0..3 {
+            let half = (d[a] as f64 - 1.0) / 2.0;
+            let frequency = 0.5 + path.next_f64();
+            let phase = path.next_f64() * TAU;
+            center[a] = half + half * (distance * frequency / half.max(1.0) + phase).sin();
+        }
+        let lo = center.map(|c| (c.round() as i64 - radius).max(0));
+        let hi = [0, 1, 2].map(|a| (center[a].round() as i64 + radius).min(d[a] as i64 - 1));
+        for z in lo[2]..=hi[2] {
+            for y in lo[1]..=hi[1] {
+                for x in lo[0]..=hi[0] {
+                    let dist2 = (x as f64 - center[0]).powi(2)
+                        + (y as f64 - center[1]).powi(2)
+                        + (z as f64 - center[2]).powi(2);
+                    let activation = (-dist2 / (2.0 * sigma * sigma)).exp();
+                    let hash =
+                        voxel_hash(frame_seed ^ blob as u64, key, x as u32, y as u32, z as u32);
+                    if unit(hash) < activation {
+                        let index = x as u64 + d[0] as u64 * (y as u64 + d[1] as u64 * z as u64);
+                        fired.push((index, activation as f32));
+                    }
+                }
+            }
+        }
+    }
+    fired.sort_unstable_by(|a, b| a.0.cmp(&b.0).then(b.1.total_cmp(&a.1)));
+    fired.dedup_by_key(|f| f.0);
+    for (index, potential) in fired {
+        let (x, y, z) = voxel_of(index, d);
+        out.push(x, y, z, potential);
+    }
+}
+
+/// Generates synthetic Type 11 frames from pattern definitions.
+///
+/// Frames are FeagiByteContainer packets (optionally LZ4-compressed) accepted by
+/// FeagiDataDeserializer.apply_type11_packet_to_multimeshes and the other decode methods.
+/// The same seed and pattern list always produce the same frames.
+///
+/// Example (GDScript):
+///   var generator = FeagiActivityGenerator.new()
+///   generator.set_seed(42)
+///   generator.add_poisson_area(cortical_id, Vector3(32, 32, 8), 5.0)
+///   func _process(delta):
+///       var packet = generator.advance(delta)
+///       if not packet.is_empty():
+///           deserializer.apply_type11_packet_to_multimeshes(packet, multimeshes, generator.get_dimensions_by_id(), true)
+#[derive(GodotClass)]
+#[class(base=RefCounted)]
+pub struct FeagiActivityGenerator {
+    #[base]
+    base: Base<RefCounted>,
+    generator: ActivityGenerator,
+    compression: Compression,
+    agent_id: Vec<u8>,
+    /// Index of the next frame to produce
+    next_frame: u64,
+    /// Generator clock in seconds (advance)
+    clock_s: f64,
+    frames_skipped: u64,
+}
+
+#[godot_api]
+impl IRefCounted for FeagiActivityGenerator {
+    fn init(base: Base<RefCounted>) -> Self {
+        Self {
+            base,
+            generator: ActivityGenerator::default(),
+            compression: Compression::None,
+            agent_id: vec![0u8; feagi_serialization::FeagiByteContainer::AGENT_ID_BYTE_COUNT],
+            next_frame: 0,
+            clock_s: 0.0,
+            frames_skipped: 0,
+        }
+    }
+}
+
+#[godot_api]
+impl FeagiActivityGenerator {
+    #[constant]
+    const AXIS_X: i32 = 0;
+    #[constant]
+    const AXIS_Y: i32 = 1;
+    #[constant]
+    const AXIS_Z: i32 = 2;
+
+    /// Seed of all random patterns (Poisson firing, blob paths). Does not rewind the frames.
+    #[func]
+    pub fn set_seed(&mut self, seed: i64) {
+        self.generator.seed = seed as u64;
+    }
+
+    #[func]
+    pub fn get_seed(&self) -> i64 {
+        self.generator.seed as i64
+    }
+
+    /// Frames per second produced by advance(); also the time base of moving patterns.
+    #[func]
+    pub fn set_frame_rate(&mut self, frame_rate_hz: f64) {
+        if frame_rate_hz.is_finite() && frame_rate_hz > 0.0 {
+            self.generator.frame_rate_hz = frame_rate_hz;
+        } else {
+            godot_error!(
+                "🦀 [GENERATOR] Frame rate must be > 0 (got {})",
+                frame_rate_hz
+            );
+        }
+    }
+
+    #[func]
+    pub fn get_frame_rate(&self) -> f64 {
+        self.generator.frame_rate_hz
+    }
+
+    /// Compression of produced frames: FeagiDataDeserializer.COMPRESSION_NONE (default),
+    /// COMPRESSION_LZ4_BLOCK or COMPRESSION_LZ4_FRAME.
+    #[func]
+    pub fn set_compression(&mut self, compression: i32) {
+        match Compression::from_i32(compression) {
+            Some(c) => self.compression = c,
+            None => godot_error!("🦀 [GENERATOR] Unknown compression: {}", compression),
+        }
+    }
+
+    /// Agent ID stamped into produced containers (base64; empty = all zeros).
+    ///
+    /// Returns: true if the ID was valid
+    #[func]
+    pub fn set_agent_id(&mut self, agent_id_b64: GString) -> bool {
+        use base64::Engine;
+        let agent_id_b64 = agent_id_b64.to_string();
+        let expected = feagi_serialization::FeagiByteContainer::AGENT_ID_BYTE_COUNT;
+        if agent_id_b64.trim().is_empty() {
+            self.agent_id = vec![0u8; expected];
+            return true;
+        }
+        match base64::engine::general_purpose::STANDARD.decode(agent_id_b64.trim()) {
+            Ok(bytes) if bytes.len() == expected => {
+                self.agent_id = bytes;
+                true
+            }
+            _ => {
+                godot_error!(
+                    "🦀 [GENERATOR] Agent ID must be {} base64-encoded bytes",
+                    expected
+                );
+                false
+            }
+        }
+    }
+
+    /// Every neuron fires independently (Poisson process).
+    ///
+    /// Args:
+    ///   - cortical_id: base64 cortical ID
+    ///   - dimensions: area dimensions (Vector3, as used by the apply methods)
+    ///   - firing_rate_hz: firing rate of each neuron
+    ///
+    /// Returns: true if the area was added (an area with the same ID is replaced)
+    #[func]
+    pub fn add_poisson_area(
+        &mut self,
+        cortical_id: GString,
+        dimensions: Vector3,
+        firing_rate_hz: f64,
+    ) -> bool {
+        if !(firing_rate_hz.is_finite() && firing_rate_hz >= 0.0) {
+            godot_error!(
+                "🦀 [GENERATOR] Firing rate must be >= 0 (got {})",
+                firing_rate_hz
+            );
+            return false;
+        }
+        self.add_area(
+            cortical_id,
+            dimensions,
+            Pattern::Poisson {
+                rate_hz: firing_rate_hz,
+            },
+        )
+    }
+
+    /// Cosine wave traveling along an axis; layers near the crests fire.
+    ///
+    /// Args:
+    ///   - axis: AXIS_X, AXIS_Y or AXIS_Z
+    ///   - wavelength: distance between crests in voxels
+    ///   - speed: voxels per second (negative = backwards)
+    #[func]
+    pub fn add_wave_area(
+        &mut self,
+        cortical_id: GString,
+        dimensions: Vector3,
+        axis: i32,
+        wavelength: f64,
+        speed: f64,
+    ) -> bool {
+        let Some(axis) = Self::axis(axis) else {
+            return false;
+        };
+        if !(wavelength.is_finite() && wavelength > 0.0 && speed.is_finite()) {
+            godot_error!("🦀 [GENERATOR] Wave needs a wavelength > 0 and a finite speed");
+            return false;
+        }
+        self.add_area(
+            cortical_id,
+            dimensions,
+            Pattern::Wave {
+                axis,
+                wavelength,
+                speed,
+            },
+        )
+    }
+
+    /// A slab sweeping along an axis, wrapping around at the end of the area.
+    ///
+    /// Args:
+    ///   - axis: AXIS_X, AXIS_Y or AXIS_Z
+    ///   - speed: voxels per second (negative = backwards)
+    ///   - thickness: slab thickness in voxels
+    #[func]
+    pub fn add_plane_area(
+        &mut self,
+        cortical_id: GString,
+        dimensions: Vector3,
+        axis: i32,
+        speed: f64,
+        thickness: i32,
+    ) -> bool {
+        let Some(axis) = Self::axis(axis) else {
+            return false;
+        };
+        if !speed.is_finite() || thickness < 1 {
+            godot_error!("🦀 [GENERATOR] Plane needs a finite speed and a thickness >= 1");
+            return false;
+        }
+        self.add_area(
+            cortical_id,
+            dimensions,
+            Pattern::Plane {
+                axis,
+                speed,
+                thickness: thickness as u32,
+            },
+        )
+    }
+
+    /// Gaussian blobs drifting through the area; neurons fire with a probability that falls
+    /// off with the distance to a blob center.
+    ///
+    /// Args:
+    ///   - blob_count: number of blobs
+    ///   - sigma: blob radius (standard deviation) in voxels
+    ///   - speed: approximate drift in voxels per second
+    #[func]
+    pub fn add_blob_area(
+        &mut self,
+        cortical_id: GString,
+        dimensions: Vector3,
+        blob_count: i32,
+        sigma: f64,
+        speed: f64,
+    ) -> bool {
+        if blob_count < 1 || !(sigma.is_finite() && sigma > 0.0) || !speed.is_finite() {
+            godot_error!("🦀 [GENERATOR] Blobs need a count >= 1, a sigma > 0 and a finite speed");
+            return false;
+        }
+        self.add_area(
+            cortical_id,
+            dimensions,
+            Pattern::Blobs {
+                count: blob_count as u32,
+                sigma,
+                speed,
+            },
+        )
+    }
+
+    /// Replay a prerecorded pattern, looping over its frames.
+    ///
+    /// Args:
+    ///   - frames: Array of Dictionary {x_array, y_array, z_array, p_array}, e.g. the per-area
+    ///     entries of decode_type_11_data results
+    #[func]
+    pub fn add_replay_area(
+        &mut self,
+        cortical_id: GString,
+        dimensions: Vector3,
+        frames: Array<Dictionary>,
+    ) -> bool {
+        let id = cortical_id.to_string();
+        let mut recorded = Vec::with_capacity(frames.len());
+        for frame in frames.iter_shared() {
+            match AreaActivity::from_dictionary(id.clone(), &frame) {
+                Ok(area) => recorded.push(area),
+                Err(e) => {
+                    godot_error!("🦀 [GENERATOR] Invalid replay frame: {}", e);
+                    return false;
+                }
+            }
+        }
+        self.add_area(
+            cortical_id,
+            dimensions,
+            Pattern::Replay { frames: recorded },
+        )
+    }
+
+    #[func]
+    pub fn clear_areas(&mut self) {
+        self.generator.areas.clear();
+    }
+
+    #[func]
+    pub fn get_area_count(&self) -> i32 {
+        self.generator.areas.len() as i32
+    }
+
+    /// Dictionary[cortical_id -> Vector3] of all areas, for the apply methods
+    #[func]
+    pub fn get_dimensions_by_id(&self) -> Dictionary {
+        let mut dict = Dictionary::new();
+        for area in &self.generator.areas {
+            let d = area.dimensions;
+            dict.set(
+                area.cortical_id.as_str(),
+                Vector3::new(d[0] as f32, d[1] as f32, d[2] as f32),
+            );
+        }
+        dict
+    }
+
+    /// Rewind to frame 0 and reset the clock
+    #[func]
+    pub fn reset(&mut self) {
+        self.next_frame = 0;
+        self.clock_s = 0.0;
+        self.frames_skipped = 0;
+    }
+
+    /// Index of the next frame next_frame() or advance() will produce
+    #[func]
+    pub fn get_frame_index(&self) -> i64 {
+        self.next_frame as i64
+    }
+
+    /// Frames advance() skipped because more than one became due in a single call
+    #[func]
+    pub fn get_frames_skipped(&self) -> i64 {
+        self.frames_skipped as i64
+    }
+
+    /// Produce the next frame regardless of the clock.
+    ///
+    /// Returns: the packet, or an empty array on error
+    #[func]
+    pub fn next_frame(&mut self) -> PackedByteArray {
+        let index = self.next_frame;
+        self.next_frame += 1;
+        self.frame_at(index as i64)
+    }
+
+    /// Produce any frame by index without moving the generator (frames are deterministic).
+    #[func]
+    pub fn frame_at(&self, frame_index: i64) -> PackedByteArray {
+        if frame_index < 0 {
+            return PackedByteArray::new();
+        }
+        let areas = self.generator.frame(frame_index as u64);
+        let envelope = Envelope::Container {
+            agent_id: &self.agent_id,
+            increment: frame_index as u16,
+        };
+        match encoder::encode(&areas, envelope, self.compression) {
+            Ok((packet, _)) => PackedByteArray::from(packet.as_slice()),
+            Err(e) => {
+                godot_error!("🦀 [GENERATOR] Frame {} encode failed: {}", frame_index, e);
+                PackedByteArray::new()
+            }
+        }
+    }
+
+    /// Advance the generator clock by `delta` seconds.
+    ///
+    /// Returns: the newest frame that became due at the frame rate, or an empty array.
+    /// Frames in between are skipped (see get_frames_skipped), like the live stream.
+    #[func]
+    pub fn advance(&mut self, delta: f64) -> PackedByteArray {
+        self.clock_s += delta.max(0.0);
+        let due = (self.clock_s * self.generator.frame_rate_hz) as u64;
+        if due < self.next_frame {
+            return PackedByteArray::new();
+        }
+        self.frames_skipped += due - self.next_frame;
+        self.next_frame = due;
+        self.next_frame()
+    }
+
+    /// Produce the next frame and apply it to MultiMeshes with the generator's dimensions
+    /// (same result as apply_type11_packet_to_multimeshes).
+    #[func]
+    pub fn apply_next_frame(
+        &mut self,
+        mut deserializer: Gd<FeagiDataDeserializer>,
+        multimeshes_by_id: Dictionary,
+        clear_all_before_apply: bool,
+    ) -> Dictionary {
+        let frame = self.next_frame();
+        let dimensions_by_id = self.get_dimensions_by_id();
+        deserializer.bind_mut().apply_type11_packet_to_multimeshes(
+            frame,
+            multimeshes_by_id,
+            dimensions_by_id,
+            clear_all_before_apply,
+        )
+    }
+}
+
+impl FeagiActivityGenerator {
+    fn axis(axis: i32) -> Option<usize> {
+        match axis {
+            0..=2 => Some(axis as usize),
+            _ => {
+                godot_error!("🦀 [GENERATOR] Unknown axis: {}", axis);
+                None
+            }
+        }
+    }
+
+    fn add_area(&mut self, cortical_id: GString, dimensions: Vector3, pattern: Pattern) -> bool {
+        let cortical_id = cortical_id.to_string();
+        let valid = [dimensions.x, dimensions.y, dimensions.z]
+            .iter()
+            .all(|v| v.is_finite() && *v >= 1.0);
+        if !valid {
+            godot_error!(
+                "🦀 [GENERATOR] Invalid dimensions for area {}: {:?}",
+                cortical_id,
+                dimensions
+            );
+            return false;
+        }
+        if CorticalID::try_from_base_64(&cortical_id).is_err() {
+            godot_error!("🦀 [GENERATOR] Invalid cortical ID: {}", cortical_id);
+            return false;
+        }
+        let area = PatternArea {
+            dimensions: [
+                dimensions.x as u32,
+                dimensions.y as u32,
+                dimensions.z as u32,
+            ],
+            cortical_id,
+            pattern,
+        };
+        match self
+            .generator
+            .areas
+            .iter_mut()
+            .find(|a| a.cortical_id == area.cortical_id)
+        {
+            Some(existing) => *existing = area,
+            None => self.generator.areas.push(area),
+        }
+        true
+    }
+}
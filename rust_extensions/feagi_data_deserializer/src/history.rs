@@ -0,0 +1,737 @@
+//! Time-series history of decoded activity, for offline analysis.
+//!
+//! Bursts that pass through the recording source (one decode path, chosen with
+//! `RecordingSource`) are stored as per-area firing counts and, optionally, the full voxel
+//! list; bursts that only go through the other path are not recorded. The oldest bursts are
+//! evicted once the memory budget is exceeded.
+//!
+//! CSV export writes one row per (burst, area) or, with voxels, one row per voxel:
+//!
+//! ```text
+//! burst,timestamp_us,cortical_id,firing_count
+//! burst,timestamp_us,cortical_id,x,y,z,potential
+//! ```
+//!
+//! Columnar export (all integers little-endian):
+//!
+//! ```text
+//! file    : magic "FEAGIBVH" | version u32 (=1) | table_count u32 | table * table_count
+//! table   : name_len u16 | name utf8 | row_count u64 | column_count u32 | column * column_count
+//! column  : name_len u16 | name utf8 | type u8 | data_len u64 | data
+//! type    : 1 = u32, 2 = u64, 3 = f32 (row_count values each)
+//!           4 = utf8 string (offsets u32 * (row_count + 1), then the concatenated bytes)
+//! ```
+//!
+//! Tables: `areas` (area u32, cortical_id string), `bursts` (burst u64, timestamp_us u64),
+//! `area_counts` (burst u64, area u32, firing_count u32) and, when voxels were recorded,
+//! `voxels` (burst u64, area u32, x u32, y u32, z u32, potential f32). `area` refers to the row
+//! of the same value in `areas`; `timestamp_us` is microseconds since the Unix epoch.
+
+use feagi_structures::neuron_voxels::xyzp::CorticalMappedXYZPNeuronVoxels;
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const COLUMNAR_MAGIC: &[u8; 8] = b"FEAGIBVH";
+const COLUMNAR_VERSION: u32 = 1;
+
+const COLUMN_U32: u8 = 1;
+const COLUMN_U64: u8 = 2;
+const COLUMN_F32: u8 = 3;
+const COLUMN_STRING: u8 = 4;
+
+/// Default memory budget of the history buffer
+const DEFAULT_MEMORY_BUDGET: usize = 64 * 1024 * 1024;
+
+/// Estimated bytes of a burst without areas or voxels
+const BURST_OVERHEAD_BYTES: usize = 64;
+const COUNT_BYTES: usize = std::mem::size_of::<(u32, u32)>();
+const VOXEL_BYTES: usize = std::mem::size_of::<HistoryVoxel>();
+
+/// Decode path that feeds the history and the activity plots.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RecordingSource {
+    /// decode_type_11_data
+    Decode,
+    /// apply_type11_packet_to_multimeshes
+    Apply,
+}
+
+impl RecordingSource {
+    pub fn from_i32(source: i32) -> Option<Self> {
+        match source {
+            0 => Some(RecordingSource::Decode),
+            1 => Some(RecordingSource::Apply),
+            _ => None,
+        }
+    }
+
+    pub fn as_i32(self) -> i32 {
+        match self {
+            RecordingSource::Decode => 0,
+            RecordingSource::Apply => 1,
+        }
+    }
+
+    /// GDScript method of the path
+    pub fn method_name(self) -> &'static str {
+        match self {
+            RecordingSource::Decode => "decode_type_11_data",
+            RecordingSource::Apply => "apply_type11_packet_to_multimeshes",
+        }
+    }
+}
+
+/// Bursts that only reach the other decode path before `SourceWatch` reports the mismatch
+const UNRECORDED_BURSTS_BEFORE_WARNING: u32 = 8;
+
+/// Notices bursts that keep arriving only on the decode path that is not the recording
+/// source, so a consumer that records nothing is told once instead of silently.
+///
+/// Paths that see every burst (decode and apply of the same packet) keep resetting the count.
+#[derive(Default)]
+pub(crate) struct SourceWatch {
+    unrecorded: u32,
+    warned: bool,
+}
+
+impl SourceWatch {
+    /// Note a burst recorded by the recording source.
+    pub fn recorded(&mut self) {
+        self.unrecorded = 0;
+    }
+
+    /// Note a burst seen only by the other path.
+    ///
+    /// Returns true once, when UNRECORDED_BURSTS_BEFORE_WARNING bursts in a row went unrecorded.
+    pub fn skipped(&mut self) -> bool {
+        self.unrecorded = self.unrecorded.saturating_add(1);
+        if self.warned || self.unrecorded < UNRECORDED_BURSTS_BEFORE_WARNING {
+            return false;
+        }
+        self.warned = true;
+        true
+    }
+
+    /// Start over (the recording source changed).
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct HistoryVoxel {
+    area: u32,
+    x: u32,
+    y: u32,
+    z: u32,
+    potential: f32,
+}
+
+struct HistoryBurst {
+    burst: u64,
+    timestamp_us: u64,
+    /// (area, firing_count)
+    counts: Vec<(u32, u32)>,
+    voxels: Vec<HistoryVoxel>,
+}
+
+impl HistoryBurst {
+    fn estimated_bytes(&self) -> usize {
+        BURST_OVERHEAD_BYTES + self.counts.len() * COUNT_BYTES + self.voxels.len() * VOXEL_BYTES
+    }
+}
+
+/// History state of a deserializer.
+pub(crate) struct ActivityHistory {
+    pub enabled: bool,
+    /// Also keep the voxel lists, not only the per-area counts
+    pub record_voxels: bool,
+    memory_budget: usize,
+    /// Cortical IDs by area index
+    area_ids: Vec<String>,
+    area_index: HashMap<String, u32>,
+    bursts: VecDeque<HistoryBurst>,
+    bytes_used: usize,
+    next_burst: u64,
+    evicted_bursts: u64,
+}
+
+impl Default for ActivityHistory {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            record_voxels: false,
+            memory_budget: DEFAULT_MEMORY_BUDGET,
+            area_ids: Vec::new(),
+            area_index: HashMap::new(),
+            bursts: VecDeque::new(),
+            bytes_used: 0,
+            next_burst: 0,
+            evicted_bursts: 0,
+        }
+    }
+}
+
+impl ActivityHistory {
+    pub fn memory_budget(&self) -> usize {
+        self.memory_budget
+    }
+
+    pub fn set_memory_budget(&mut self, bytes: usize) {
+        self.memory_budget = bytes;
+        self.evict();
+    }
+
+    pub fn bytes_used(&self) -> usize {
+        self.bytes_used
+    }
+
+    pub fn burst_count(&self) -> usize {
+        self.bursts.len()
+    }
+
+    pub fn evicted_bursts(&self) -> u64 {
+        self.evicted_bursts
+    }
+
+    /// Burst numbers of the oldest and newest stored bursts.
+    pub fn burst_range(&self) -> Option<(u64, u64)> {
+        Some((self.bursts.front()?.burst, self.bursts.back()?.burst))
+    }
+
+    /// Drop all bursts; burst numbering restarts at 0.
+    pub fn clear(&mut self) {
+        self.bursts.clear();
+        self.area_ids.clear();
+        self.area_index.clear();
+        self.bytes_used = 0;
+        self.next_burst = 0;
+        self.evicted_bursts = 0;
+    }
+
+    /// Record the decoded activity of a packet as the next burst.
+    pub fn record(&mut self, neuron_data: &CorticalMappedXYZPNeuronVoxels) {
+        let mut burst = HistoryBurst {
+            burst: self.next_burst,
+            timestamp_us: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_micros() as u64)
+                .unwrap_or(0),
+            counts: Vec::new(),
+            voxels: Vec::new(),
+        };
+        self.next_burst += 1;
+
+        for (cortical_id, neuron_array) in neuron_data.mappings.iter() {
+            let num_neurons = neuron_array.len();
+            if num_neurons == 0 {
+                continue;
+            }
+            let area = self.intern(cortical_id.as_base_64());
+            burst.counts.push((area, num_neurons as u32));
+            if self.record_voxels {
+                burst
+                    .voxels
+                    .extend(neuron_array.iter().map(|neuron| HistoryVoxel {
+                        area,
+                        x: neuron.neuron_voxel_coordinate.x,
+                        y: neuron.neuron_voxel_coordinate.y,
+                        z: neuron.neuron_voxel_coordinate.z,
+                        potential: neuron.potential,
+                    }));
+            }
+        }
+
+        self.bytes_used += burst.estimated_bytes();
+        self.bursts.push_back(burst);
+        self.evict();
+    }
+
+    fn intern(&mut self, cortical_id: String) -> u32 {
+        if let Some(&area) = self.area_index.get(&cortical_id) {
+            return area;
+        }
+        let area = self.area_ids.len() as u32;
+        self.area_ids.push(cortical_id.clone());
+        self.area_index.insert(cortical_id, area);
+        area
+    }
+
+    /// Drop the oldest bursts until the buffer fits the budget (the newest burst is kept).
+    fn evict(&mut self) {
+        while self.bytes_used > self.memory_budget && self.bursts.len() > 1 {
+            if let Some(oldest) = self.bursts.pop_front() {
+                self.bytes_used -= oldest.estimated_bytes();
+                self.evicted_bursts += 1;
+            }
+        }
+    }
+
+    /// Write the history as CSV. Returns the number of data rows.
+    pub fn export_csv(&self, path: &str, include_voxels: bool) -> Result<usize, String> {
+        let file = File::create(path).map_err(|e| format!("{}: {}", path, e))?;
+        let mut out = BufWriter::new(file);
+        let mut rows = 0usize;
+        let io = |e: std::io::Error| e.to_string();
+
+        if include_voxels {
+            writeln!(out, "burst,timestamp_us,cortical_id,x,y,z,potential").map_err(io)?;
+            for burst in &self.bursts {
+                for voxel in &burst.voxels {
+                    writeln!(
+                        out,
+                        "{},{},{},{},{},{},{}",
+                        burst.burst,
+                        burst.timestamp_us,
+                        self.area_ids[voxel.area as usize],
+                        voxel.x,
+                        voxel.y,
+                        voxel.z,
+                        voxel.potential
+                    )
+                    .map_err(io)?;
+                    rows += 1;
+                }
+            }
+        } else {
+            writeln!(out, "burst,timestamp_us,cortical_id,firing_count").map_err(io)?;
+            for burst in &self.bursts {
+                for &(area, count) in &burst.counts {
+                    writeln!(
+                        out,
+                        "{},{},{},{}",
+                        burst.burst, burst.timestamp_us, self.area_ids[area as usize], count
+                    )
+                    .map_err(io)?;
+                    rows += 1;
+                }
+            }
+        }
+        out.flush().map_err(io)?;
+        Ok(rows)
+    }
+
+    /// Write the history in the columnar format described in the module docs.
+    ///
+    /// Returns the number of bytes written.
+    pub fn export_columnar(&self, path: &str) -> Result<u64, String> {
+        let counts = || {
+            self.bursts
+                .iter()
+                .flat_map(|b| b.counts.iter().map(move |c| (b.burst, *c)))
+        };
+        let mut tables = vec![
+            Table {
+                name: "areas",
+                row_count: self.area_ids.len() as u64,
+                columns: vec![
+                    Column::u32("area", (0..self.area_ids.len() as u32).collect()),
+                    Column::string("cortical_id", &self.area_ids),
+                ],
+            },
+            Table {
+                name: "bursts",
+                row_count: self.bursts.len() as u64,
+                columns: vec![
+                    Column::u64("burst", self.bursts.iter().map(|b| b.burst).collect()),
+                    Column::u64(
+                        "timestamp_us",
+                        self.bursts.iter().map(|b| b.timestamp_us).collect(),
+                    ),
+                ],
+            },
+            Table {
+                name: "area_counts",
+                row_count: counts().count() as u64,
+                columns: vec![
+                    Column::u64("burst", counts().map(|(burst, _)| burst).collect()),
+                    Column::u32("area", counts().map(|(_, (area, _))| area).collect()),
+                    Column::u32(
+                        "firing_count",
+                        counts().map(|(_, (_, count))| count).collect(),
+                    ),
+                ],
+            },
+        ];
+
+        if self.bursts.iter().any(|b| !b.voxels.is_empty()) {
+            let voxels = || {
+                self.bursts
+                    .iter()
+                    .flat_map(|b| b.voxels.iter().map(move |v| (b.burst, *v)))
+            };
+            tables.push(Table {
+                name: "voxels",
+                row_count: voxels().count() as u64,
+                columns: vec![
+                    Column::u64("burst", voxels().map(|(burst, _)| burst).collect()),
+                    Column::u32("area", voxels().map(|(_, v)| v.area).collect()),
+                    Column::u32("x", voxels().map(|(_, v)| v.x).collect()),
+                    Column::u32("y", voxels().map(|(_, v)| v.y).collect()),
+                    Column::u32("z", voxels().map(|(_, v)| v.z).collect()),
+                    Column::f32("potential", voxels().map(|(_, v)| v.potential).collect()),
+                ],
+            });
+        }
+
+        let file = File::create(path).map_err(|e| format!("{}: {}", path, e))?;
+        let mut out = CountingWriter {
+            inner: BufWriter::new(file),
+            written: 0,
+        };
+        let io = |e: std::io::Error| e.to_string();
+        out.write_all(COLUMNAR_MAGIC).map_err(io)?;
+        out.write_all(&COLUMNAR_VERSION.to_le_bytes()).map_err(io)?;
+        out.write_all(&(tables.len() as u32).to_le_bytes())
+            .map_err(io)?;
+        for table in &tables {
+            write_name(&mut out, table.name).map_err(io)?;
+            out.write_all(&table.row_count.to_le_bytes()).map_err(io)?;
+            out.write_all(&(table.columns.len() as u32).to_le_bytes())
+                .map_err(io)?;
+            for column in &table.columns {
+                write_name(&mut out, column.name).map_err(io)?;
+                out.write_all(&[column.kind]).map_err(io)?;
+                out.write_all(&(column.data.len() as u64).to_le_bytes())
+                    .map_err(io)?;
+                out.write_all(&column.data).map_err(io)?;
+            }
+        }
+        out.flush().map_err(io)?;
+        Ok(out.written)
+    }
+}
+
+struct Table {
+    name: &'static str,
+    row_count: u64,
+    columns: Vec<Column>,
+}
+
+struct Column {
+    name: &'static str,
+    kind: u8,
+    data: Vec<u8>,
+}
+
+impl Column {
+    fn u32(name: &'static str, values: Vec<u32>) -> Self {
+        Self {
+            name,
+            kind: COLUMN_U32,
+            data: values.iter().flat_map(|v| v.to_le_bytes()).collect(),
+        }
+    }
+
+    fn u64(name: &'static str, values: Vec<u64>) -> Self {
+        Self {
+            name,
+            kind: COLUMN_U64,
+            data: values.iter().flat_map(|v| v.to_le_bytes()).collect(),
+        }
+    }
+
+    fn f32(name: &'static str, values: Vec<f32>) -> Self {
+        Self {
+            name,
+            kind: COLUMN_F32,
+            data: values.iter().flat_map(|v| v.to_le_bytes()).collect(),
+        }
+    }
+
+    fn string(name: &'static str, values: &[String]) -> Self {
+        let mut offsets: Vec<u8> = Vec::with_capacity((values.len() + 1) * 4);
+        let mut bytes: Vec<u8> = Vec::new();
+        offsets.extend_from_slice(&0u32.to_le_bytes());
+        for value in values {
+            bytes.extend_from_slice(value.as_bytes());
+            offsets.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        }
+        offsets.extend_from_slice(&bytes);
+        Self {
+            name,
+            kind: COLUMN_STRING,
+            data: offsets,
+        }
+    }
+}
+
+fn write_name(out: &mut impl Write, name: &str) -> std::io::Result<()> {
+    out.write_all(&(name.len() as u16).to_le_bytes())?;
+    out.write_all(name.as_bytes())
+}
+
+/// Writer that counts the bytes written through it.
+struct CountingWriter<W: Write> {
+    inner: W,
+    written: u64,
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::{build_neuron_voxels, AreaActivity};
+
+    /// Core cortical IDs: base64 of "___power" and "___death"
+    const POWER: &str = "X19fcG93ZXI=";
+    const DEATH: &str = "X19fZGVhdGg=";
+
+    fn burst(areas: &[(&str, &[(u32, u32, u32, f32)])]) -> CorticalMappedXYZPNeuronVoxels {
+        let areas: Vec<AreaActivity> = areas
+            .iter()
+            .map(|(id, neurons)| {
+                let mut area = AreaActivity::new(id);
+                for &(x, y, z, p) in neurons.iter() {
+                    area.push(x, y, z, p);
+                }
+                area
+            })
+            .collect();
+        build_neuron_voxels(&areas).unwrap()
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("feagi_history_{}_{}", std::process::id(), name))
+    }
+
+    /// Rows after the header, with the timestamp column removed
+    fn csv_rows(path: &std::path::Path) -> Vec<String> {
+        let text = std::fs::read_to_string(path).unwrap();
+        text.lines()
+            .skip(1)
+            .map(|line| {
+                let mut fields: Vec<&str> = line.split(',').collect();
+                fields.remove(1);
+                fields.join(",")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn oldest_bursts_are_evicted_beyond_the_budget() {
+        let mut history = ActivityHistory::default();
+        let one_area = burst(&[(POWER, &[(0, 0, 0, 1.0)])]);
+        history.record(&one_area);
+        let burst_bytes = history.bytes_used();
+        history.set_memory_budget(burst_bytes * 3);
+
+        for _ in 0..4 {
+            history.record(&one_area);
+        }
+        assert_eq!(history.burst_count(), 3);
+        assert_eq!(history.burst_range(), Some((2, 4)));
+        assert_eq!(history.evicted_bursts(), 2);
+        assert_eq!(history.bytes_used(), burst_bytes * 3);
+
+        // A smaller budget evicts right away, but the newest burst always stays
+        history.set_memory_budget(0);
+        assert_eq!(history.burst_range(), Some((4, 4)));
+        assert_eq!(history.evicted_bursts(), 4);
+
+        history.clear();
+        assert_eq!(history.burst_count(), 0);
+        assert_eq!(history.bytes_used(), 0);
+        history.record(&one_area);
+        assert_eq!(history.burst_range(), Some((0, 0)));
+    }
+
+    #[test]
+    fn voxels_count_against_the_budget() {
+        let mut counts_only = ActivityHistory::default();
+        let mut with_voxels = ActivityHistory {
+            record_voxels: true,
+            ..ActivityHistory::default()
+        };
+        let data = burst(&[(POWER, &[(0, 0, 0, 1.0), (1, 0, 0, 0.5)])]);
+        counts_only.record(&data);
+        with_voxels.record(&data);
+        assert_eq!(
+            with_voxels.bytes_used() - counts_only.bytes_used(),
+            2 * VOXEL_BYTES
+        );
+    }
+
+    #[test]
+    fn csv_export_writes_counts_or_voxels() {
+        let mut history = ActivityHistory {
+            record_voxels: true,
+            ..ActivityHistory::default()
+        };
+        history.record(&burst(&[(POWER, &[(1, 2, 3, 0.5), (4, 5, 6, 1.0)])]));
+        history.record(&burst(&[(DEATH, &[(0, 0, 1, 0.25)])]));
+
+        let path = temp_path("counts.csv");
+        assert_eq!(history.export_csv(path.to_str().unwrap(), false), Ok(2));
+        let header = std::fs::read_to_string(&path).unwrap();
+        assert!(header.starts_with("burst,timestamp_us,cortical_id,firing_count\n"));
+        assert_eq!(
+            csv_rows(&path),
+            [format!("0,{},2", POWER), format!("1,{},1", DEATH)]
+        );
+
+        assert_eq!(history.export_csv(path.to_str().unwrap(), true), Ok(3));
+        let mut rows = csv_rows(&path);
+        rows.sort();
+        assert_eq!(
+            rows,
+            [
+                format!("0,{},1,2,3,0.5", POWER),
+                format!("0,{},4,5,6,1", POWER),
+                format!("1,{},0,0,1,0.25", DEATH),
+            ]
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+
+    /// Columnar file parsed back into (table, row_count, [(column, type, data)])
+    type ParsedTable = (String, u64, Vec<(String, u8, Vec<u8>)>);
+
+    fn parse_columnar(bytes: &[u8]) -> Vec<ParsedTable> {
+        let mut at = 0usize;
+        let mut take = |n: usize| {
+            let slice = &bytes[at..at + n];
+            at += n;
+            slice
+        };
+        assert_eq!(take(8), COLUMNAR_MAGIC);
+        assert_eq!(take(4), COLUMNAR_VERSION.to_le_bytes());
+        let table_count = u32::from_le_bytes(take(4).try_into().unwrap());
+        let mut tables = Vec::new();
+        for _ in 0..table_count {
+            let name_len = u16::from_le_bytes(take(2).try_into().unwrap()) as usize;
+            let name = String::from_utf8(take(name_len).to_vec()).unwrap();
+            let rows = u64::from_le_bytes(take(8).try_into().unwrap());
+            let column_count = u32::from_le_bytes(take(4).try_into().unwrap());
+            let mut columns = Vec::new();
+            for _ in 0..column_count {
+                let name_len = u16::from_le_bytes(take(2).try_into().unwrap()) as usize;
+                let column = String::from_utf8(take(name_len).to_vec()).unwrap();
+                let kind = take(1)[0];
+                let len = u64::from_le_bytes(take(8).try_into().unwrap()) as usize;
+                columns.push((column, kind, take(len).to_vec()));
+            }
+            tables.push((name, rows, columns));
+        }
+        assert_eq!(at, bytes.len(), "trailing bytes");
+        tables
+    }
+
+    fn u32s(data: &[u8]) -> Vec<u32> {
+        data.chunks(4)
+            .map(|c| u32::from_le_bytes(c.try_into().unwrap()))
+            .collect()
+    }
+
+    fn u64s(data: &[u8]) -> Vec<u64> {
+        data.chunks(8)
+            .map(|c| u64::from_le_bytes(c.try_into().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn columnar_export_follows_the_schema() {
+        let mut history = ActivityHistory {
+            record_voxels: true,
+            ..ActivityHistory::default()
+        };
+        history.record(&burst(&[(POWER, &[(1, 2, 3, 0.5)])]));
+        history.record(&burst(&[(DEATH, &[(0, 0, 1, 0.25), (0, 1, 1, 0.75)])]));
+
+        let path = temp_path("history.bvh");
+        let written = history.export_columnar(path.to_str().unwrap()).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(written, bytes.len() as u64);
+
+        let tables = parse_columnar(&bytes);
+        let names: Vec<&str> = tables.iter().map(|t| t.0.as_str()).collect();
+        assert_eq!(names, ["areas", "bursts", "area_counts", "voxels"]);
+        for (_, rows, columns) in &tables {
+            for (column, kind, data) in columns {
+                let width = match *kind {
+                    COLUMN_U32 | COLUMN_F32 => 4,
+                    COLUMN_U64 => 8,
+                    _ => continue,
+                };
+                assert_eq!(data.len() as u64, rows * width, "column {}", column);
+            }
+        }
+
+        let (_, rows, areas) = &tables[0];
+        assert_eq!(*rows, 2);
+        assert_eq!(u32s(&areas[0].2), [0, 1]);
+        let (name, kind, data) = &areas[1];
+        assert_eq!((name.as_str(), *kind), ("cortical_id", COLUMN_STRING));
+        let offsets = u32s(&data[..12]);
+        assert_eq!(offsets, [0, POWER.len() as u32, (POWER.len() * 2) as u32]);
+        assert_eq!(&data[12..], format!("{}{}", POWER, DEATH).as_bytes());
+
+        let (_, rows, counts) = &tables[2];
+        assert_eq!(*rows, 2);
+        assert_eq!(u64s(&counts[0].2), [0, 1]);
+        assert_eq!(u32s(&counts[1].2), [0, 1]);
+        assert_eq!(u32s(&counts[2].2), [1, 2]);
+
+        let (_, rows, voxels) = &tables[3];
+        assert_eq!(*rows, 3);
+        assert_eq!(u64s(&voxels[0].2), [0, 1, 1]);
+        assert_eq!(u32s(&voxels[1].2), [0, 1, 1]);
+        let potentials: Vec<f32> = voxels[5]
+            .2
+            .chunks(4)
+            .map(|c| f32::from_le_bytes(c.try_into().unwrap()))
+            .collect();
+        assert_eq!(voxels[5].0, "potential");
+        let mut sorted = potentials[1..].to_vec();
+        sorted.sort_by(f32::total_cmp);
+        assert_eq!((potentials[0], sorted), (0.5, vec![0.25, 0.75]));
+    }
+
+    #[test]
+    fn columnar_export_omits_voxels_when_not_recorded() {
+        let mut history = ActivityHistory::default();
+        history.record(&burst(&[(POWER, &[(1, 2, 3, 0.5)])]));
+        let path = temp_path("counts.bvh");
+        history.export_columnar(path.to_str().unwrap()).unwrap();
+        let tables = parse_columnar(&std::fs::read(&path).unwrap());
+        std::fs::remove_file(path).unwrap();
+        let names: Vec<&str> = tables.iter().map(|t| t.0.as_str()).collect();
+        assert_eq!(names, ["areas", "bursts", "area_counts"]);
+    }
+
+    #[test]
+    fn source_watch_warns_once_when_only_the_other_path_runs() {
+        let mut watch = SourceWatch::default();
+        let warnings = (0..3 * UNRECORDED_BURSTS_BEFORE_WARNING)
+            .filter(|_| watch.skipped())
+            .count();
+        assert_eq!(warnings, 1);
+
+        // Both paths seeing every burst never warns, whatever their order
+        let mut watch = SourceWatch::default();
+        for _ in 0..3 * UNRECORDED_BURSTS_BEFORE_WARNING {
+            assert!(!watch.skipped());
+            watch.recorded();
+        }
+
+        watch.reset();
+        let warnings = (0..UNRECORDED_BURSTS_BEFORE_WARNING)
+            .filter(|_| watch.skipped())
+            .count();
+        assert_eq!(warnings, 1);
+    }
+}
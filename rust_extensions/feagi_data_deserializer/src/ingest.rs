@@ -0,0 +1,135 @@
+//! Packet ingest: one front door for every decode and apply path.
+//!
+//! Visualization packets reach Brain Visualizer in several shapes depending on transport:
+//! plain FeagiByteContainer (SHM, WS without compression), an LZ4 block or LZ4 frame wrapping
+//! the container (PNS-compressed WS), or a raw Type 11 structure when upstream unwrapped the
+//! container. `ingest` detects the shape and decompresses under a size ceiling (both in the
+//! shared `feagi_frame_codec` crate, which feagi_agent_client uses too) and returns the
+//! decoded neuron payload together with how it was found.
+
+use crate::container::{self, DecodedNeuronPayload};
+use crate::error::{DecodeError, ErrorKind};
+use feagi_frame_codec::{CodecError, Payload};
+use feagi_serialization::FeagiByteContainer;
+use godot::prelude::*;
+
+pub(crate) use feagi_frame_codec::{Compression, FrameLimits as IngestLimits, PayloadFormat};
+
+// Uncompressed containers are detected by version byte in feagi_frame_codec
+const _: () = assert!(
+    feagi_frame_codec::is_container_version(FeagiByteContainer::CURRENT_FBS_VERSION),
+    "feagi_frame_codec does not recognise the current FeagiByteContainer version"
+);
+
+impl From<CodecError> for DecodeError {
+    fn from(error: CodecError) -> Self {
+        let kind = match error.kind {
+            feagi_frame_codec::ErrorKind::EmptyBuffer => ErrorKind::EmptyBuffer,
+            feagi_frame_codec::ErrorKind::UnsupportedFormat => ErrorKind::UnsupportedVersion,
+            feagi_frame_codec::ErrorKind::OversizedPayload => ErrorKind::OversizedPayload,
+            feagi_frame_codec::ErrorKind::Lz4Failure => ErrorKind::Lz4Failure,
+        };
+        DecodeError::new(kind, error.message)
+    }
+}
+
+/// A packet after detection, decompression and decoding.
+pub(crate) struct DecodedFrame {
+    pub payload: DecodedNeuronPayload,
+    pub compression: Compression,
+    pub format: PayloadFormat,
+    /// Packet size as received
+    pub packet_bytes: usize,
+    /// Payload size after decompression
+    pub payload_bytes: usize,
+    pub lz4_ms: f64,
+    pub parse_ms: f64,
+}
+
+impl DecodedFrame {
+    /// Write ingest and structure bookkeeping into a result dictionary.
+    ///
+    /// Adds `compression`, `payload_format`, `packet_bytes`, `payload_bytes` and the
+    /// fields of `DecodedNeuronPayload::write_structure_info`.
+    pub fn write_ingest_info(&self, dict: &mut Dictionary) {
+        dict.set("compression", self.compression.as_str());
+        dict.set("payload_format", self.format.name());
+        dict.set("packet_bytes", self.packet_bytes as i64);
+        dict.set("payload_bytes", self.payload_bytes as i64);
+        self.payload.write_structure_info(dict);
+    }
+}
+
+/// Detect, decompress and decode one packet.
+pub(crate) fn ingest(packet: Vec<u8>, limits: &IngestLimits) -> Result<DecodedFrame, DecodeError> {
+    let packet_bytes = packet.len();
+    feagi_frame_codec::decode_packet(packet, limits, |payload: Payload| {
+        let parse_start = std::time::Instant::now();
+        let (format, payload_bytes, decoded) =
+            decode_payload(payload.compression, payload.bytes, limits)?;
+        Ok(DecodedFrame {
+            payload: decoded,
+            compression: payload.compression,
+            format,
+            packet_bytes,
+            payload_bytes,
+            lz4_ms: payload.decompress_ms,
+            parse_ms: parse_start.elapsed().as_secs_f64() * 1000.0,
+        })
+    })
+}
+
+/// Decode uncompressed payload bytes by their format byte.
+fn decode_payload(
+    compression: Compression,
+    payload: Vec<u8>,
+    limits: &IngestLimits,
+) -> Result<(PayloadFormat, usize, DecodedNeuronPayload), DecodeError> {
+    let format = PayloadFormat::of(&payload).ok_or_else(|| match payload.first() {
+        Some(first) => DecodeError::new(
+            ErrorKind::UnsupportedVersion,
+            format!(
+                "{} payload decompressed to an unknown format (first byte {}; expected 2, 3 or 11)",
+                compression.as_str(),
+                first
+            ),
+        ),
+        None => DecodeError::new(
+            ErrorKind::EmptyBuffer,
+            format!("{} payload decompressed to 0 bytes", compression.as_str()),
+        ),
+    })?;
+    let payload_bytes = payload.len();
+    let decoded = match format {
+        PayloadFormat::Container(version) => container::decode_container(payload)
+            .map_err(|e| e.context(format!("FeagiByteContainer v{}", version)))?,
+        PayloadFormat::RawType11 => container::decode_raw_type11(payload)?,
+    };
+    check_frame_limits(&decoded, limits)?;
+    Ok((format, payload_bytes, decoded))
+}
+
+/// Reject frames with more areas, or areas with more neurons, than the limits allow.
+fn check_frame_limits(
+    decoded: &DecodedNeuronPayload,
+    limits: &IngestLimits,
+) -> Result<(), DecodeError> {
+    let mappings = &decoded.neuron_data.mappings;
+    limits.check_area_count(mappings.len())?;
+    for (cortical_id, neurons) in mappings.iter() {
+        limits.check_area_neurons(neurons.len(), || cortical_id.as_base_64())?;
+    }
+    Ok(())
+}
+
+/// Identify the transport compression of a packet and remove it.
+///
+/// Returns the compression found and the uncompressed bytes. Packets starting with a known
+/// payload format byte are passed through unchanged.
+pub(crate) fn unwrap_compression(
+    packet: Vec<u8>,
+    limits: &IngestLimits,
+) -> Result<(Compression, Vec<u8>), DecodeError> {
+    let payload = feagi_frame_codec::unwrap_compression(packet, limits)?;
+    Ok((payload.compression, payload.bytes))
+}
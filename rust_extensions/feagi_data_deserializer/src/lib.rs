@@ -1,7 +1,5 @@
 use godot::classes::MultiMesh;
 use godot::prelude::*;
-// FeagiByteContainer is imported within functions where needed
-use feagi_serialization::FeagiSerializable;
 use feagi_structures::genomic::cortical_area::descriptors::{
     CorticalSubUnitIndex, CorticalUnitIndex,
 };
@@ -16,6 +14,43 @@ use feagi_structures::neuron_voxels::xyzp::CorticalMappedXYZPNeuronVoxels;
 #[cfg(not(target_family = "wasm"))]
 use rayon::prelude::*;
 
+mod afterglow;
+mod bulk;
+mod color_map;
+mod container;
+mod coordinates;
+mod delta;
+mod encoder;
+mod error;
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
+mod generator;
+mod history;
+mod ingest;
+mod lod;
+mod multimesh_buffer;
+mod picking;
+mod plots;
+mod recording;
+mod sampling;
+mod stats;
+
+use afterglow::AfterglowBuffer;
+use bulk::BulkColumns;
+use color_map::{ColorMode, ColorSettings, Gradient};
+use coordinates::{AreaSpace, Placement};
+use delta::DeltaTracker;
+use encoder::{AreaActivity, Envelope};
+use error::{DecodeError, ErrorKind, RateLimitedLog, Verbosity};
+use history::{ActivityHistory, RecordingSource, SourceWatch};
+use ingest::{Compression, IngestLimits};
+use lod::{LodSettings, LodValue};
+use multimesh_buffer::{UploadMode, VoxelInstance};
+use picking::{PickHit, PickIndex};
+use plots::{ActivityPlots, Projection, Rgba8Image};
+use sampling::{SamplingSettings, SamplingStrategy};
+use stats::{ActivityStats, AreaStats};
+
 /// Cortical dimensions must be positive finite voxel counts; otherwise scale uses div-by-zero or NaN (GPU risk).
 fn dimensions_valid_for_neuron_multimesh(dimensions: Vector3) -> bool {
     dimensions.x.is_finite()
@@ -26,174 +61,1449 @@ fn dimensions_valid_for_neuron_multimesh(dimensions: Vector3) -> bool {
         && dimensions.z > 0.0
 }
 
-struct FeagiDataDeserializerLib;
+struct FeagiDataDeserializerLib;
+
+#[gdextension]
+unsafe impl ExtensionLibrary for FeagiDataDeserializerLib {}
+
+/// Main GDExtension class for FEAGI data deserialization
+#[derive(GodotClass)]
+#[class(base=RefCounted)]
+pub struct FeagiDataDeserializer {
+    #[base]
+    base: Base<RefCounted>,
+
+    /// How the MultiMesh apply paths hand instance data to Godot
+    upload_mode: UploadMode,
+
+    /// Color mode, colormap and per-area colors used by every rendering path
+    color_settings: ColorSettings,
+
+    /// Recently fired voxels kept visible by the MultiMesh apply path
+    afterglow: AfterglowBuffer,
+
+    /// Previous frame's active voxels per area for delta updates
+    delta: DeltaTracker,
+
+    /// Super-voxel aggregation of large areas
+    lod: LodSettings,
+
+    /// Which neurons process_neuron_visualization keeps when max_neurons is exceeded
+    sampling: SamplingSettings,
+
+    /// Decompression ceiling shared by every decode and apply path
+    ingest_limits: IngestLimits,
+
+    /// Rendered instances per area, for hover and inspection
+    picking: PickIndex,
+
+    /// Voxel spacing, gap, brain offset and the FEAGI position of each area
+    placement: Placement,
+
+    /// Per-area activity statistics and the rolling firing-rate window
+    stats: ActivityStats,
+
+    /// Bounded per-burst history for export
+    history: ActivityHistory,
+
+    /// Raster and heatmap data of the areas selected for plots
+    plots: ActivityPlots,
+
+    /// Decode path that feeds the history and the plots
+    recording_source: RecordingSource,
+
+    /// Warns once when bursts only reach the path that does not record
+    source_watch: SourceWatch,
+
+    /// Verbosity and rate limiting of console output
+    log: RateLimitedLog,
+}
+
+#[godot_api]
+impl IRefCounted for FeagiDataDeserializer {
+    fn init(base: Base<RefCounted>) -> Self {
+        Self {
+            base,
+            upload_mode: UploadMode::Bulk,
+            color_settings: ColorSettings::default(),
+            afterglow: AfterglowBuffer::default(),
+            delta: DeltaTracker::default(),
+            lod: LodSettings::default(),
+            sampling: SamplingSettings::default(),
+            ingest_limits: IngestLimits::default(),
+            picking: PickIndex::default(),
+            placement: Placement::default(),
+            stats: ActivityStats::default(),
+            history: ActivityHistory::default(),
+            plots: ActivityPlots::default(),
+            recording_source: RecordingSource::Decode,
+            source_watch: SourceWatch::default(),
+            log: RateLimitedLog::default(),
+        }
+    }
+}
+
+#[godot_api]
+impl FeagiDataDeserializer {
+    /// `error_code` of successful results
+    #[constant]
+    const ERROR_NONE: i32 = 0;
+    /// Packet or input arrays are empty
+    #[constant]
+    const ERROR_EMPTY_BUFFER: i32 = 1;
+    /// Container version or payload format this build does not understand
+    #[constant]
+    const ERROR_UNSUPPORTED_VERSION: i32 = 2;
+    /// Container or Type 11 bytes failed to parse
+    #[constant]
+    const ERROR_CORRUPT_CONTAINER: i32 = 3;
+    /// A structure of another type where neuron voxels were expected
+    #[constant]
+    const ERROR_WRONG_STRUCTURE_TYPE: i32 = 4;
+    /// Payload above the decompression ceiling
+    #[constant]
+    const ERROR_OVERSIZED_PAYLOAD: i32 = 5;
+    /// LZ4 block or frame failed to decompress
+    #[constant]
+    const ERROR_LZ4_FAILURE: i32 = 6;
+    /// Invalid arguments (mismatched arrays, unknown IDs or modes)
+    #[constant]
+    const ERROR_INVALID_ARGUMENT: i32 = 7;
+    /// File system failure (exports)
+    #[constant]
+    const ERROR_IO_FAILURE: i32 = 8;
+    /// Internal failure (panic caught at the API boundary)
+    #[constant]
+    const ERROR_INTERNAL: i32 = 9;
+
+    /// No console output
+    #[constant]
+    const LOG_SILENT: i32 = 0;
+    /// Failures only, rate-limited (default)
+    #[constant]
+    const LOG_ERRORS: i32 = 1;
+    /// Failures and status messages, rate-limited
+    #[constant]
+    const LOG_INFO: i32 = 2;
+    /// Everything, including per-packet diagnostics
+    #[constant]
+    const LOG_DEBUG: i32 = 3;
+
+    /// Console verbosity of the packet paths.
+    ///
+    /// Args:
+    ///   - verbosity: LOG_SILENT, LOG_ERRORS (default), LOG_INFO or LOG_DEBUG
+    ///
+    /// Returns: true if the verbosity was recognised
+    #[func]
+    pub fn set_log_verbosity(&mut self, verbosity: i32) -> bool {
+        match Verbosity::from_i32(verbosity) {
+            Some(v) => {
+                self.log.verbosity = v;
+                true
+            }
+            None => {
+                godot_error!("🦀 Unknown log verbosity: {}", verbosity);
+                false
+            }
+        }
+    }
+
+    #[func]
+    pub fn get_log_verbosity(&self) -> i32 {
+        self.log.verbosity.as_i32()
+    }
+
+    /// Minimum time between two console lines of the same kind (default 1 s); repeats in
+    /// between are counted and reported with the next line.
+    #[func]
+    pub fn set_log_interval_seconds(&mut self, seconds: f64) {
+        if seconds.is_finite() && seconds >= 0.0 {
+            self.log.interval = std::time::Duration::from_secs_f64(seconds);
+        } else {
+            godot_error!("🦀 Log interval must be >= 0 (got {})", seconds);
+        }
+    }
+
+    #[func]
+    pub fn get_log_interval_seconds(&self) -> f64 {
+        self.log.interval.as_secs_f64()
+    }
+
+    /// Build the packed instance buffer in Rust and upload it with one `set_buffer` call per area
+    #[constant]
+    const UPLOAD_MODE_BULK: i32 = 0;
+    /// Call `set_instance_transform` / `set_instance_color` once per neuron (legacy path)
+    #[constant]
+    const UPLOAD_MODE_PER_INSTANCE: i32 = 1;
+
+    /// Select how the MultiMesh apply paths upload instance data.
+    ///
+    /// Args:
+    ///   - mode: UPLOAD_MODE_BULK (default) or UPLOAD_MODE_PER_INSTANCE
+    ///
+    /// Returns: true if the mode was recognised
+    #[func]
+    pub fn set_multimesh_upload_mode(&mut self, mode: i32) -> bool {
+        match UploadMode::from_i32(mode) {
+            Some(m) => {
+                self.upload_mode = m;
+                true
+            }
+            None => {
+                godot_error!("🦀 Unknown MultiMesh upload mode: {}", mode);
+                false
+            }
+        }
+    }
+
+    /// Current MultiMesh upload mode (UPLOAD_MODE_BULK or UPLOAD_MODE_PER_INSTANCE)
+    #[func]
+    pub fn get_multimesh_upload_mode(&self) -> i32 {
+        self.upload_mode.as_i32()
+    }
+
+    /// Color by z-depth (front bright, back dark) - default
+    #[constant]
+    const COLOR_MODE_DEPTH: i32 = 0;
+    /// Color by membrane potential / firing strength through the active colormap
+    #[constant]
+    const COLOR_MODE_POTENTIAL: i32 = 1;
+    /// One fixed color per cortical area (see set_area_color)
+    #[constant]
+    const COLOR_MODE_AREA_FIXED: i32 = 2;
+    /// Signed potential mapped symmetrically around zero (signed-percentage OPUs)
+    #[constant]
+    const COLOR_MODE_SIGNED_DIVERGING: i32 = 3;
+
+    /// Select what drives neuron colors in every rendering path.
+    ///
+    /// Returns: true if the mode was recognised
+    #[func]
+    pub fn set_color_mode(&mut self, mode: i32) -> bool {
+        match ColorMode::from_i32(mode) {
+            Some(m) => {
+                self.color_settings.mode = m;
+                self.color_settings_changed();
+                true
+            }
+            None => {
+                godot_error!("🦀 Unknown color mode: {}", mode);
+                false
+            }
+        }
+    }
+
+    /// Current color mode (one of the COLOR_MODE_* constants)
+    #[func]
+    pub fn get_color_mode(&self) -> i32 {
+        self.color_settings.mode.as_i32()
+    }
+
+    /// Select a built-in colormap ("red", "viridis", "magma", "coolwarm").
+    ///
+    /// "red" reproduces the original depth gradient; "coolwarm" suits COLOR_MODE_SIGNED_DIVERGING.
+    ///
+    /// Returns: true if the colormap exists
+    #[func]
+    pub fn set_colormap(&mut self, name: GString) -> bool {
+        let name = name.to_string().to_lowercase();
+        if self.color_settings.set_colormap(&name) {
+            self.color_settings_changed();
+            true
+        } else {
+            godot_error!("🦀 Unknown colormap: {}", name);
+            false
+        }
+    }
+
+    /// Name of the active colormap ("custom" after set_custom_gradient)
+    #[func]
+    pub fn get_colormap(&self) -> GString {
+        GString::from(self.color_settings.colormap_name.as_str())
+    }
+
+    /// Names of the built-in colormaps
+    #[func]
+    pub fn get_available_colormaps(&self) -> PackedStringArray {
+        let mut names = PackedStringArray::new();
+        for name in color_map::BUILTIN_COLORMAPS {
+            names.push(&GString::from(name));
+        }
+        names
+    }
+
+    /// Use a user-supplied gradient as the active colormap.
+    ///
+    /// Args:
+    ///   - offsets: stop positions in [0, 1] (values outside are clamped)
+    ///   - colors: stop colors, same length as offsets
+    ///
+    /// Returns: true if the gradient was accepted
+    #[func]
+    pub fn set_custom_gradient(
+        &mut self,
+        offsets: PackedFloat32Array,
+        colors: PackedColorArray,
+    ) -> bool {
+        if offsets.len() != colors.len() {
+            godot_error!(
+                "🦀 Gradient size mismatch: {} offsets, {} colors",
+                offsets.len(),
+                colors.len()
+            );
+            return false;
+        }
+        let stops: Vec<(f32, [f32; 4])> = offsets
+            .as_slice()
+            .iter()
+            .zip(colors.as_slice().iter())
+            .map(|(offset, c)| (*offset, [c.r, c.g, c.b, c.a]))
+            .collect();
+        match Gradient::from_stops(stops) {
+            Ok(gradient) => {
+                self.color_settings.set_custom_gradient(gradient);
+                self.color_settings_changed();
+                true
+            }
+            Err(e) => {
+                godot_error!("🦀 Invalid gradient: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Potential range mapped onto the colormap.
+    ///
+    /// COLOR_MODE_POTENTIAL maps min..max to the start..end of the colormap;
+    /// COLOR_MODE_SIGNED_DIVERGING uses max(|min|, |max|) as the symmetric range around zero.
+    #[func]
+    pub fn set_potential_range(&mut self, min_potential: f32, max_potential: f32) {
+        self.color_settings.potential_min = min_potential;
+        self.color_settings.potential_max = max_potential;
+        self.color_settings_changed();
+    }
+
+    /// Fixed color of a cortical area for COLOR_MODE_AREA_FIXED
+    #[func]
+    pub fn set_area_color(&mut self, cortical_id: GString, color: Color) {
+        self.color_settings.set_area_color(
+            &cortical_id.to_string(),
+            [color.r, color.g, color.b, color.a],
+        );
+        self.color_settings_changed();
+    }
+
+    /// Remove all per-area colors (those areas fall back to depth coloring)
+    #[func]
+    pub fn clear_area_colors(&mut self) {
+        self.color_settings.clear_area_colors();
+        self.color_settings_changed();
+    }
+
+    /// Keep recently fired neurons visible in apply_type11_packet_to_multimeshes.
+    ///
+    /// Disabling also forgets everything accumulated so far.
+    #[func]
+    pub fn set_afterglow_enabled(&mut self, enabled: bool) {
+        self.afterglow.settings.enabled = enabled;
+        if !enabled {
+            self.afterglow.clear();
+        }
+    }
+
+    /// Whether afterglow is active
+    #[func]
+    pub fn is_afterglow_enabled(&self) -> bool {
+        self.afterglow.settings.enabled
+    }
+
+    /// Number of bursts after which an afterglow voxel's alpha/size has halved (> 0)
+    #[func]
+    pub fn set_afterglow_half_life(&mut self, half_life_bursts: f32) {
+        if !(half_life_bursts.is_finite() && half_life_bursts > 0.0) {
+            godot_error!(
+                "🦀 Afterglow half-life must be > 0 (got {})",
+                half_life_bursts
+            );
+            return;
+        }
+        self.afterglow.settings.half_life_bursts = half_life_bursts;
+    }
+
+    /// Number of bursts a voxel stays visible after it last fired
+    #[func]
+    pub fn set_afterglow_max_age(&mut self, max_age_bursts: i32) {
+        self.afterglow.settings.max_age_bursts = max_age_bursts.max(0) as u32;
+    }
+
+    /// Instance scale of a fully faded voxel, in [0, 1] (1.0 fades alpha only)
+    #[func]
+    pub fn set_afterglow_min_scale(&mut self, min_scale: f32) {
+        self.afterglow.settings.min_scale = min_scale.clamp(0.0, 1.0);
+    }
+
+    /// Forget all accumulated afterglow (e.g. after a genome reload)
+    #[func]
+    pub fn clear_afterglow(&mut self) {
+        self.afterglow.clear();
+    }
+
+    /// Only rewrite instances that appeared, disappeared or changed color between consecutive
+    /// apply_type11_packet_to_multimeshes calls.
+    ///
+    /// Delta mode keeps spare MultiMesh capacity and hides unused slots with
+    /// visible_instance_count. It is bypassed while afterglow is enabled.
+    #[func]
+    pub fn set_delta_mode_enabled(&mut self, enabled: bool) {
+        self.delta.enabled = enabled;
+        self.delta.clear();
+    }
+
+    /// Whether delta mode is enabled
+    #[func]
+    pub fn is_delta_mode_enabled(&self) -> bool {
+        self.delta.enabled
+    }
+
+    /// Forget the previous frame; the next apply rewrites every area completely.
+    ///
+    /// Call this after modifying registered MultiMeshes outside of this class.
+    #[func]
+    pub fn reset_delta_state(&mut self) {
+        self.delta.clear();
+    }
+
+    /// Super-voxel potential is the mean potential of its neurons (drives potential coloring)
+    #[constant]
+    const LOD_VALUE_MEAN_POTENTIAL: i32 = 0;
+    /// Super-voxel potential is the fraction of its voxels that are active
+    #[constant]
+    const LOD_VALUE_DENSITY: i32 = 1;
+
+    /// Aggregate active voxels of large areas into super-voxels instead of truncating.
+    ///
+    /// Level n merges 2^n x 2^n x 2^n voxels. Applies to apply_type11_packet_to_multimeshes and
+    /// process_neuron_visualization.
+    #[func]
+    pub fn set_lod_enabled(&mut self, enabled: bool) {
+        self.lod.enabled = enabled;
+        self.delta.clear();
+    }
+
+    /// Whether LOD aggregation is active
+    #[func]
+    pub fn is_lod_enabled(&self) -> bool {
+        self.lod.enabled
+    }
+
+    /// Maximum instances drawn per area; the coarsest level needed to fit is selected (0 = no budget)
+    #[func]
+    pub fn set_lod_budget(&mut self, max_instances_per_area: i32) {
+        self.lod.budget_per_area = max_instances_per_area.max(0) as usize;
+    }
+
+    /// Highest LOD level (default 3 = 8x8x8 voxels per super-voxel)
+    #[func]
+    pub fn set_lod_max_level(&mut self, max_level: i32) {
+        self.lod.max_level = max_level.clamp(0, 10) as u32;
+    }
+
+    /// Camera distances at which LOD levels 1, 2, 3, ... start.
+    ///
+    /// Args:
+    ///   - thresholds: ascending distances (sorted if not)
+    #[func]
+    pub fn set_lod_distance_thresholds(&mut self, thresholds: PackedFloat32Array) {
+        let mut thresholds: Vec<f32> = thresholds
+            .as_slice()
+            .iter()
+            .copied()
+            .filter(|t| t.is_finite())
+            .collect();
+        thresholds.sort_by(|a, b| a.total_cmp(b));
+        self.lod.distance_thresholds = thresholds;
+    }
+
+    /// Camera distance hint of an area (e.g. from the area's world position each frame)
+    #[func]
+    pub fn set_area_lod_distance(&mut self, cortical_id: GString, distance: f32) {
+        self.lod
+            .area_distances
+            .insert(cortical_id.to_string(), distance);
+    }
+
+    /// Forget all camera distance hints
+    #[func]
+    pub fn clear_area_lod_distances(&mut self) {
+        self.lod.area_distances.clear();
+    }
+
+    /// Select what drives super-voxel color (LOD_VALUE_MEAN_POTENTIAL or LOD_VALUE_DENSITY).
+    ///
+    /// Returns: true if the value mode was recognised
+    #[func]
+    pub fn set_lod_value_mode(&mut self, mode: i32) -> bool {
+        match LodValue::from_i32(mode) {
+            Some(v) => {
+                self.lod.value = v;
+                true
+            }
+            None => {
+                godot_error!("🦀 Unknown LOD value mode: {}", mode);
+                false
+            }
+        }
+    }
+
+    /// Current LOD value mode (LOD_VALUE_MEAN_POTENTIAL or LOD_VALUE_DENSITY)
+    #[func]
+    pub fn get_lod_value_mode(&self) -> i32 {
+        self.lod.value.as_i32()
+    }
+
+    /// Shrink sparsely populated super-voxels (size = cube root of density)
+    #[func]
+    pub fn set_lod_size_by_density(&mut self, enabled: bool) {
+        self.lod.size_by_density = enabled;
+    }
+
+    /// Keep neurons in packet order until max_neurons is reached (legacy, varies between frames)
+    #[constant]
+    const SAMPLING_FIRST_N: i32 = 0;
+    /// Split max_neurons over the areas by neuron count (default)
+    #[constant]
+    const SAMPLING_PROPORTIONAL: i32 = 1;
+    /// Keep the neurons with the largest |potential|
+    #[constant]
+    const SAMPLING_STRONGEST: i32 = 2;
+    /// Keep a pseudo-random subset keyed on (area, voxel coordinate) and the sampling seed
+    #[constant]
+    const SAMPLING_SEEDED_RESERVOIR: i32 = 3;
+
+    /// Select which neurons process_neuron_visualization keeps when max_neurons is exceeded.
+    ///
+    /// All strategies except SAMPLING_FIRST_N pick the same neurons for the same activity, so the
+    /// drawn subset is stable across frames. Ties are broken by the seeded voxel hash.
+    ///
+    /// Returns: true if the strategy was recognised
+    #[func]
+    pub fn set_sampling_strategy(&mut self, strategy: i32) -> bool {
+        match SamplingStrategy::from_i32(strategy) {
+            Some(s) => {
+                self.sampling.strategy = s;
+                true
+            }
+            None => {
+                godot_error!("🦀 Unknown sampling strategy: {}", strategy);
+                false
+            }
+        }
+    }
+
+    /// Current sampling strategy (one of the SAMPLING_* constants)
+    #[func]
+    pub fn get_sampling_strategy(&self) -> i32 {
+        self.sampling.strategy.as_i32()
+    }
+
+    /// Seed of the voxel hash used by SAMPLING_SEEDED_RESERVOIR and for tie-breaking
+    #[func]
+    pub fn set_sampling_seed(&mut self, seed: i64) {
+        self.sampling.seed = seed as u64;
+    }
+
+    /// Record rendered instances during apply_type11_packet_to_multimeshes for picking.
+    ///
+    /// Off by default; costs one hash insert per drawn instance. Disabling drops the index.
+    #[func]
+    pub fn set_picking_enabled(&mut self, enabled: bool) {
+        self.picking.enabled = enabled;
+        if !enabled {
+            self.picking.clear_areas();
+        }
+    }
+
+    #[func]
+    pub fn is_picking_enabled(&self) -> bool {
+        self.picking.enabled
+    }
+
+    /// Register the global transform of an area's MultiMeshInstance3D.
+    ///
+    /// Picks use it to move world-space rays and points into the area; areas without a
+    /// registered transform use their placement (set_area_placement), or else are assumed to
+    /// sit at the world origin, unscaled.
+    ///
+    /// Args:
+    ///   - cortical_id: Cortical area ID (base64)
+    ///   - transform: MultiMeshInstance3D.global_transform
+    #[func]
+    pub fn set_pick_area_transform(&mut self, cortical_id: GString, transform: Transform3D) {
+        self.picking
+            .set_transform(&cortical_id.to_string(), transform);
+    }
+
+    #[func]
+    pub fn clear_pick_area_transforms(&mut self) {
+        self.picking.clear_transforms();
+    }
+
+    /// Forget all recorded instances (the next apply records them again)
+    #[func]
+    pub fn clear_pick_index(&mut self) {
+        self.picking.clear_areas();
+    }
+
+    /// Nearest rendered neuron hit by a world-space ray.
+    ///
+    /// Args:
+    ///   - origin: Ray origin (e.g. Camera3D.project_ray_origin)
+    ///   - direction: Ray direction (e.g. Camera3D.project_ray_normal)
+    ///   - max_distance: Longest ray in world units (0 = unlimited)
+    ///
+    /// Returns: Dictionary with:
+    ///   - success: bool (false only when picking is disabled)
+    ///   - hit: bool
+    ///   - cortical_id: String
+    ///   - voxel: Vector3i (cell corner for LOD super-voxels)
+    ///   - extent: i32 (cell edge in voxels, 1 unless drawn as a super-voxel)
+    ///   - potential: f32
+    ///   - instance_index: i32 (MultiMesh instance)
+    ///   - distance: f32 (from origin to where the ray enters the voxel)
+    ///   - position: Vector3 (world position where the ray enters the voxel)
+    ///   - error: String
+    #[func]
+    pub fn pick_neuron_ray(
+        &self,
+        origin: Vector3,
+        direction: Vector3,
+        max_distance: f32,
+    ) -> Dictionary {
+        if !self.picking.enabled {
+            return Self::pick_result(None, "Picking is disabled (see set_picking_enabled)");
+        }
+        Self::pick_result(self.picking.pick_ray(origin, direction, max_distance), "")
+    }
+
+    /// Rendered neuron whose voxel center is nearest to a world-space point.
+    ///
+    /// Args:
+    ///   - point: World position (e.g. a physics ray hit on the area's collider)
+    ///   - max_distance: Search radius in world units (0 = unlimited)
+    ///
+    /// Returns: Same Dictionary as pick_neuron_ray; distance and position refer to the voxel
+    /// center.
+    #[func]
+    pub fn pick_neuron_at_point(&self, point: Vector3, max_distance: f32) -> Dictionary {
+        if !self.picking.enabled {
+            return Self::pick_result(None, "Picking is disabled (see set_picking_enabled)");
+        }
+        Self::pick_result(self.picking.pick_point(point, max_distance), "")
+    }
+
+    /// Rendered neuron at a voxel of an area, as drawn by the last apply.
+    ///
+    /// Returns: Same Dictionary as pick_neuron_ray, without distance and position.
+    #[func]
+    pub fn get_rendered_neuron(&self, cortical_id: GString, voxel: Vector3i) -> Dictionary {
+        if !self.picking.enabled {
+            return Self::pick_result(None, "Picking is disabled (see set_picking_enabled)");
+        }
+        let id = cortical_id.to_string();
+        let entry = if voxel.x < 0 || voxel.y < 0 || voxel.z < 0 {
+            None
+        } else {
+            self.picking
+                .lookup(&id, voxel.x as u32, voxel.y as u32, voxel.z as u32)
+        };
+        let mut result = Self::pick_result(None, "");
+        if let Some(entry) = entry {
+            Self::write_pick_entry(&mut result, &id, &entry);
+        }
+        result
+    }
+
+    /// Number of areas and instances currently indexed for picking.
+    #[func]
+    pub fn get_pick_index_stats(&self) -> Dictionary {
+        let mut result = Dictionary::new();
+        result.set("enabled", self.picking.enabled);
+        result.set("areas", self.picking.area_count() as i32);
+        result.set("instances", self.picking.instance_count() as i64);
+        result
+    }
+
+    /// Register where a cortical area sits in the brain, for the coordinate helpers and picking.
+    ///
+    /// Args:
+    ///   - cortical_id: Cortical area ID (base64)
+    ///   - feagi_position: FEAGI coordinate of the area's lower-left-front corner
+    ///   - dimensions: area size in voxels
+    ///
+    /// Returns: true if the dimensions are usable (positive and finite)
+    #[func]
+    pub fn set_area_placement(
+        &mut self,
+        cortical_id: GString,
+        feagi_position: Vector3,
+        dimensions: Vector3,
+    ) -> bool {
+        if !dimensions_valid_for_neuron_multimesh(dimensions) {
+            godot_error!(
+                "🦀 Invalid dimensions for area placement {}: {:?}",
+                cortical_id,
+                dimensions
+            );
+            return false;
+        }
+        let cortical_id = cortical_id.to_string();
+        self.placement
+            .set_area(&cortical_id, feagi_position, dimensions);
+        self.refresh_placed_transform(&cortical_id);
+        true
+    }
+
+    #[func]
+    pub fn remove_area_placement(&mut self, cortical_id: GString) -> bool {
+        let cortical_id = cortical_id.to_string();
+        self.picking.set_placed_transform(&cortical_id, None);
+        self.placement.remove_area(&cortical_id)
+    }
+
+    #[func]
+    pub fn clear_area_placements(&mut self) {
+        let ids: Vec<String> = self.placement.area_ids().cloned().collect();
+        for cortical_id in ids {
+            self.picking.set_placed_transform(&cortical_id, None);
+        }
+        self.placement.clear_areas();
+    }
+
+    /// Brain-world size of one voxel per axis (default 1, 1, 1 = FEAGI units).
+    ///
+    /// Differing axes scale the brain anisotropically. Spacing moves areas (see
+    /// get_area_transform); instance transforms stay relative to the area.
+    ///
+    /// Returns: true if every axis is positive and finite
+    #[func]
+    pub fn set_voxel_spacing(&mut self, spacing: Vector3) -> bool {
+        if !dimensions_valid_for_neuron_multimesh(spacing) {
+            godot_error!("🦀 Voxel spacing must be positive: {:?}", spacing);
+            return false;
+        }
+        self.placement.spacing = spacing;
+        self.refresh_placed_transforms();
+        true
+    }
+
+    #[func]
+    pub fn get_voxel_spacing(&self) -> Vector3 {
+        self.placement.spacing
+    }
+
+    /// Fraction of each voxel cell left empty between drawn voxels (0 = touching cubes,
+    /// default; clamped to 0..0.95)
+    #[func]
+    pub fn set_voxel_gap(&mut self, gap: f32) {
+        self.placement.gap = if gap.is_finite() {
+            gap.clamp(0.0, 0.95)
+        } else {
+            0.0
+        };
+    }
+
+    #[func]
+    pub fn get_voxel_gap(&self) -> f32 {
+        self.placement.gap
+    }
+
+    /// Brain-world translation added to every area (e.g. where the brain or region is placed)
+    #[func]
+    pub fn set_brain_offset(&mut self, offset: Vector3) {
+        self.placement.brain_offset = offset;
+        self.refresh_placed_transforms();
+    }
+
+    #[func]
+    pub fn get_brain_offset(&self) -> Vector3 {
+        self.placement.brain_offset
+    }
+
+    /// Area-local (MultiMesh) position where a voxel is drawn.
+    ///
+    /// Args:
+    ///   - dimensions: area size in voxels
+    ///   - voxel: neuron coordinate (clamped to the area like the apply paths)
+    #[func]
+    pub fn voxel_to_area_local(&self, dimensions: Vector3, voxel: Vector3i) -> Vector3 {
+        if !dimensions_valid_for_neuron_multimesh(dimensions) {
+            return Vector3::ZERO;
+        }
+        let [x, y, z] = Self::clamp_voxel(dimensions, voxel);
+        self.placement.space(dimensions).voxel_to_local(x, y, z)
+    }
+
+    /// Area-local to brain-world transform of a placed area: the global transform to give its
+    /// MultiMeshInstance3D when it sits directly under the brain root.
+    ///
+    /// Returns: identity if the area has no placement
+    #[func]
+    pub fn get_area_transform(&self, cortical_id: GString) -> Transform3D {
+        self.placement
+            .area_space(&cortical_id.to_string())
+            .map(|space| space.local_to_world())
+            .unwrap_or(Transform3D::IDENTITY)
+    }
+
+    /// Brain-world center of a voxel of a placed area.
+    ///
+    /// Returns: Vector3.ZERO if the area has no placement
+    #[func]
+    pub fn voxel_to_world(&self, cortical_id: GString, voxel: Vector3i) -> Vector3 {
+        match self.placement.area_space(&cortical_id.to_string()) {
+            Some(space) => {
+                let [x, y, z] = Self::clamp_voxel(space.dimensions, voxel);
+                space.voxel_to_world(x, y, z)
+            }
+            None => Vector3::ZERO,
+        }
+    }
+
+    /// Voxel of a placed area containing a brain-world point.
+    ///
+    /// Returns: Dictionary with:
+    ///   - success: bool (false if the area has no placement)
+    ///   - inside: bool (the point lies inside the area)
+    ///   - voxel: Vector3i (valid when inside)
+    ///   - cell: Vector3 (continuous voxel coordinate; voxel (x, y, z) covers [x, x+1) etc.)
+    #[func]
+    pub fn world_to_voxel(&self, cortical_id: GString, world: Vector3) -> Dictionary {
+        let mut result = Dictionary::new();
+        let space = match self.placement.area_space(&cortical_id.to_string()) {
+            Some(space) => space,
+            None => {
+                result.set("success", false);
+                result.set("inside", false);
+                result.set("voxel", Vector3i::ZERO);
+                result.set("cell", Vector3::ZERO);
+                return result;
+            }
+        };
+        let cell = space.world_to_cell(world);
+        let voxel = space.world_to_voxel(world);
+        result.set("success", true);
+        result.set("inside", voxel.is_some());
+        let [x, y, z] = voxel.unwrap_or([0, 0, 0]);
+        result.set("voxel", Vector3i::new(x as i32, y as i32, z as i32));
+        result.set("cell", Vector3::new(cell[0], cell[1], cell[2]));
+        result
+    }
+
+    /// Compute per-area activity statistics in the decode and apply paths.
+    ///
+    /// When enabled, decode_type_11_data adds `area_stats` and
+    /// apply_type11_packet_to_multimeshes adds `activity_stats` (see get_activity_stats).
+    #[func]
+    pub fn set_activity_stats_enabled(&mut self, enabled: bool) {
+        self.stats.enabled = enabled;
+    }
+
+    #[func]
+    pub fn is_activity_stats_enabled(&self) -> bool {
+        self.stats.enabled
+    }
+
+    /// Number of bursts the rolling firing rates average over (default 20, minimum 1)
+    #[func]
+    pub fn set_firing_rate_window(&mut self, bursts: i32) {
+        self.stats.set_window_bursts(bursts.max(1) as usize);
+    }
+
+    #[func]
+    pub fn get_firing_rate_window(&self) -> i32 {
+        self.stats.window_bursts() as i32
+    }
+
+    /// Set the dimensions used for active_fraction of an area.
+    ///
+    /// apply_type11_packet_to_multimeshes records the dimensions of every area it draws; other
+    /// paths need them registered here.
+    #[func]
+    pub fn set_stats_area_dimensions(&mut self, cortical_id: GString, dimensions: Vector3) {
+        self.stats
+            .set_dimensions(&cortical_id.to_string(), dimensions);
+    }
+
+    /// Forget the latest burst and the firing-rate window
+    #[func]
+    pub fn reset_activity_stats(&mut self) {
+        self.stats.reset();
+    }
+
+    /// Statistics of the latest recorded burst with rolling firing rates.
+    ///
+    /// Returns: Dictionary with:
+    ///   - areas: Dictionary[cortical_id -> Dictionary] with firing_count, min_potential,
+    ///     max_potential, mean_potential, centroid (Vector3), bbox_min / bbox_max (Vector3i,
+    ///     inclusive), active_fraction, rate_per_burst, rate_hz, neuron_rate_per_burst
+    ///   - window_bursts: i32
+    ///   - bursts_recorded: i32
+    ///   - burst_hz: f32 (mean burst frequency over the window)
+    #[func]
+    pub fn get_activity_stats(&self) -> Dictionary {
+        self.stats.to_dictionary()
+    }
+
+    /// Decode a packet only to record its activity statistics (for paths that do not use
+    /// apply_type11_packet_to_multimeshes).
+    ///
+    /// Records the burst into the firing-rate window even when activity stats are disabled.
+    ///
+    /// Returns: get_activity_stats() plus success and error
+    #[func]
+    pub fn update_activity_stats(&mut self, buffer: PackedByteArray) -> Dictionary {
+        let rust_buffer: Vec<u8> = buffer.to_vec();
+        let limits = self.ingest_limits;
+        let decoded = std::panic::catch_unwind(move || {
+            let frame = ingest::ingest(rust_buffer, &limits)?;
+            let mut areas: Vec<(String, AreaStats)> = Vec::new();
+            for (cortical_id, neuron_array) in frame.payload.neuron_data.mappings.iter() {
+                let mut area_stats = AreaStats::default();
+                for neuron in neuron_array.iter() {
+                    area_stats.push(
+                        neuron.neuron_voxel_coordinate.x,
+                        neuron.neuron_voxel_coordinate.y,
+                        neuron.neuron_voxel_coordinate.z,
+                        neuron.potential,
+                    );
+                }
+                if area_stats.count > 0 {
+                    areas.push((cortical_id.as_base_64(), area_stats));
+                }
+            }
+            Ok::<_, DecodeError>(areas)
+        });
+        let error = match decoded {
+            Ok(Ok(areas)) => {
+                self.stats.record_burst(areas);
+                None
+            }
+            Ok(Err(e)) => Some(e),
+            Err(_) => Some(DecodeError::new(
+                ErrorKind::Internal,
+                "Activity stats decode panic",
+            )),
+        };
+        if let Some(e) = &error {
+            self.log.error("[STATS]", e);
+        }
+        let mut result = self.stats.to_dictionary();
+        result.set("success", error.is_none());
+        error::set_error(&mut result, error.as_ref());
+        result
+    }
+
+    /// History and plots are fed by decode_type_11_data (default)
+    #[constant]
+    const RECORD_FROM_DECODE: i32 = 0;
+    /// History and plots are fed by apply_type11_packet_to_multimeshes
+    #[constant]
+    const RECORD_FROM_APPLY: i32 = 1;
+
+    /// Select the decode path that records into the history and the activity plots.
+    ///
+    /// Every call of that path records one burst, so pick the path each burst goes through
+    /// exactly once: the desktop WebSocket path calls decode_type_11_data for every packet
+    /// (RECORD_FROM_DECODE), while FeagiVisualizationPlayer.apply_current_frame,
+    /// FeagiVisualizationStream frames passed to apply, FeagiEmbedded.set_visualization_target,
+    /// FeagiActivityGenerator.apply_next_frame and other MultiMesh-only consumers only call
+    /// apply_type11_packet_to_multimeshes (RECORD_FROM_APPLY).
+    ///
+    /// Returns: true if the source was recognised
+    #[func]
+    pub fn set_recording_source(&mut self, source: i32) -> bool {
+        match RecordingSource::from_i32(source) {
+            Some(s) => {
+                self.recording_source = s;
+                self.source_watch.reset();
+                true
+            }
+            None => {
+                godot_error!("🦀 Unknown recording source: {}", source);
+                false
+            }
+        }
+    }
+
+    /// Current recording source (one of the RECORD_FROM_* constants)
+    #[func]
+    pub fn get_recording_source(&self) -> i32 {
+        self.recording_source.as_i32()
+    }
+
+    /// Record every decoded packet into the history buffer.
+    ///
+    /// Packets are recorded by the path chosen with set_recording_source only. With the
+    /// default RECORD_FROM_DECODE, consumers that only call apply_type11_packet_to_multimeshes
+    /// record nothing until they select RECORD_FROM_APPLY; a warning is printed once when
+    /// that happens.
+    #[func]
+    pub fn set_history_enabled(&mut self, enabled: bool) {
+        self.history.enabled = enabled;
+    }
+
+    #[func]
+    pub fn is_history_enabled(&self) -> bool {
+        self.history.enabled
+    }
+
+    /// Also store every voxel (coordinates and potential), not only per-area firing counts
+    #[func]
+    pub fn set_history_record_voxels(&mut self, enabled: bool) {
+        self.history.record_voxels = enabled;
+    }
+
+    /// Memory budget of the history buffer in bytes (default 64 MiB); the oldest bursts are
+    /// evicted beyond it
+    #[func]
+    pub fn set_history_memory_budget(&mut self, max_bytes: i64) {
+        self.history.set_memory_budget(max_bytes.max(0) as usize);
+    }
+
+    /// Drop all recorded bursts (burst numbering restarts at 0)
+    #[func]
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// History buffer state.
+    ///
+    /// Returns: Dictionary with:
+    ///   - enabled, record_voxels: bool
+    ///   - recording_source: int (RECORD_FROM_* constant)
+    ///   - bursts: i32 (stored bursts)
+    ///   - oldest_burst / newest_burst: i64 (-1 when empty)
+    ///   - evicted_bursts: i64 (dropped to respect the memory budget)
+    ///   - bytes_used / memory_budget: i64
+    #[func]
+    pub fn get_history_info(&self) -> Dictionary {
+        let (oldest, newest) = self
+            .history
+            .burst_range()
+            .map(|(oldest, newest)| (oldest as i64, newest as i64))
+            .unwrap_or((-1, -1));
+        let mut info = Dictionary::new();
+        info.set("enabled", self.history.enabled);
+        info.set("record_voxels", self.history.record_voxels);
+        info.set("recording_source", self.recording_source.as_i32());
+        info.set("bursts", self.history.burst_count() as i32);
+        info.set("oldest_burst", oldest);
+        info.set("newest_burst", newest);
+        info.set("evicted_bursts", self.history.evicted_bursts() as i64);
+        info.set("bytes_used", self.history.bytes_used() as i64);
+        info.set("memory_budget", self.history.memory_budget() as i64);
+        info
+    }
+
+    /// Export the history as CSV.
+    ///
+    /// Args:
+    ///   - path: file system path (use ProjectSettings.globalize_path for user:// paths)
+    ///   - include_voxels: one row per voxel (burst,timestamp_us,cortical_id,x,y,z,potential)
+    ///     instead of one row per area (burst,timestamp_us,cortical_id,firing_count)
+    ///
+    /// Returns: Dictionary with success, error and rows
+    #[func]
+    pub fn export_history_csv(&self, path: GString, include_voxels: bool) -> Dictionary {
+        let mut result = Dictionary::new();
+        match self.history.export_csv(&path.to_string(), include_voxels) {
+            Ok(rows) => {
+                result.set("success", true);
+                error::set_error(&mut result, None);
+                result.set("rows", rows as i64);
+            }
+            Err(e) => {
+                let e = DecodeError::new(ErrorKind::IoFailure, e).context("CSV export failed");
+                self.log.error("[HISTORY]", &e);
+                result.set("success", false);
+                error::set_error(&mut result, Some(&e));
+                result.set("rows", 0);
+            }
+        }
+        result
+    }
 
-#[gdextension]
-unsafe impl ExtensionLibrary for FeagiDataDeserializerLib {}
+    /// Export the history in the columnar binary format (schema in history.rs).
+    ///
+    /// Args:
+    ///   - path: file system path (use ProjectSettings.globalize_path for user:// paths)
+    ///
+    /// Returns: Dictionary with success, error and bytes_written
+    #[func]
+    pub fn export_history_columnar(&self, path: GString) -> Dictionary {
+        let mut result = Dictionary::new();
+        match self.history.export_columnar(&path.to_string()) {
+            Ok(bytes) => {
+                result.set("success", true);
+                error::set_error(&mut result, None);
+                result.set("bytes_written", bytes as i64);
+            }
+            Err(e) => {
+                let e = DecodeError::new(ErrorKind::IoFailure, e).context("Columnar export failed");
+                self.log.error("[HISTORY]", &e);
+                result.set("success", false);
+                error::set_error(&mut result, Some(&e));
+                result.set("bytes_written", 0);
+            }
+        }
+        result
+    }
 
-/// Main GDExtension class for FEAGI data deserialization
-#[derive(GodotClass)]
-#[class(base=RefCounted)]
-pub struct FeagiDataDeserializer {
-    #[base]
-    base: Base<RefCounted>,
-}
+    /// Heatmap projection onto the XY plane (X right, Y up)
+    #[constant]
+    const PROJECTION_XY: i32 = 0;
+    /// Heatmap projection onto the XZ plane (X right, Z down)
+    #[constant]
+    const PROJECTION_XZ: i32 = 1;
+    /// Heatmap projection onto the YZ plane (Z right, Y up)
+    #[constant]
+    const PROJECTION_YZ: i32 = 2;
+
+    /// Select the cortical areas shown by the raster and heatmaps.
+    ///
+    /// Packets decoded by the recording source (see set_recording_source; the default is
+    /// decode_type_11_data) are added automatically while areas are selected, so apply-only
+    /// consumers select RECORD_FROM_APPLY first. Clears previously accumulated plot data.
+    ///
+    /// Args:
+    ///   - cortical_ids: Areas in raster order (top to bottom)
+    ///   - dimensions_by_id: Dictionary[cortical_id -> Vector3]
+    ///   - window_bursts: Bursts shown by the raster (columns)
+    ///
+    /// Returns: true if every area had valid dimensions
+    #[func]
+    pub fn configure_activity_plots(
+        &mut self,
+        cortical_ids: PackedStringArray,
+        dimensions_by_id: Dictionary,
+        window_bursts: i32,
+    ) -> bool {
+        let mut areas: Vec<(String, [u32; 3])> = Vec::new();
+        for cortical_id in cortical_ids.as_slice() {
+            let id = cortical_id.to_string();
+            let dimensions = dimensions_by_id
+                .get(id.as_str())
+                .and_then(|v| v.try_to::<Vector3>().ok());
+            match dimensions {
+                Some(d) if dimensions_valid_for_neuron_multimesh(d) => {
+                    areas.push((id, [d.x as u32, d.y as u32, d.z as u32]));
+                }
+                _ => {
+                    godot_error!("🦀 [PLOTS] Missing or invalid dimensions for area {}", id);
+                    self.plots.configure(Vec::new(), 1);
+                    return false;
+                }
+            }
+        }
+        self.plots.configure(areas, window_bursts.max(1) as usize);
+        true
+    }
 
-#[godot_api]
-impl IRefCounted for FeagiDataDeserializer {
-    fn init(base: Base<RefCounted>) -> Self {
-        Self { base }
+    /// Deselect all plot areas and drop their data
+    #[func]
+    pub fn clear_activity_plots(&mut self) {
+        let window = self.plots.window_bursts();
+        self.plots.configure(Vec::new(), window);
+    }
+
+    /// Fraction of heatmap activity kept from one burst to the next (0 = latest burst only,
+    /// close to 1 = long trail)
+    #[func]
+    pub fn set_heatmap_decay(&mut self, decay: f32) {
+        self.plots.heatmap_decay = decay.clamp(0.0, 1.0);
+    }
+
+    /// Add a packet to the plots that did not go through the recording source path.
+    ///
+    /// Returns: true if the packet was decoded
+    #[func]
+    pub fn push_plot_packet(&mut self, buffer: PackedByteArray) -> bool {
+        let rust_buffer: Vec<u8> = buffer.to_vec();
+        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let frame = ingest::ingest(rust_buffer, &self.ingest_limits)?;
+            self.plots.record(&frame.payload.neuron_data);
+            Ok::<(), DecodeError>(())
+        })) {
+            Ok(Ok(())) => true,
+            Ok(Err(e)) => {
+                self.log.error("[PLOTS]", &e);
+                false
+            }
+            Err(_) => {
+                self.log.error(
+                    "[PLOTS]",
+                    &DecodeError::new(ErrorKind::Internal, "Packet decode panic"),
+                );
+                false
+            }
+        }
+    }
+
+    /// Spike raster of the selected areas as RGBA8 pixels.
+    ///
+    /// X is the burst (newest on the right), Y the neuron index (x + y*dx + z*dx*dy, areas
+    /// stacked in selection order). Spikes use the area color (set_area_color) or the top of
+    /// the colormap; the background is transparent.
+    ///
+    /// Args:
+    ///   - max_rows: Image height limit; neurons are grouped into rows beyond it (0 = one row
+    ///     per neuron, up to 16384)
+    ///
+    /// Returns: Dictionary with success, error, width, height, data (PackedByteArray, RGBA8),
+    /// neuron_count and bursts
+    #[func]
+    pub fn render_raster_rgba(&self, max_rows: i32) -> Dictionary {
+        let mut result = Self::plot_result(self.raster_image(max_rows));
+        result.set("neuron_count", self.plots.neuron_count() as i64);
+        result.set("bursts", self.plots.burst_count() as i32);
+        result
+    }
+
+    /// render_raster_rgba as an Image (null on error)
+    #[func]
+    pub fn render_raster_image(&self, max_rows: i32) -> Option<Gd<godot::classes::Image>> {
+        Self::plot_image(self.raster_image(max_rows))
+    }
+
+    /// Activity heatmap of one selected area projected onto a plane, as RGBA8 pixels.
+    ///
+    /// Activity is summed along the projected axis, normalized to its maximum and colored
+    /// with the active colormap; pixels without activity are transparent.
+    ///
+    /// Args:
+    ///   - cortical_id: An area selected with configure_activity_plots
+    ///   - projection: PROJECTION_XY, PROJECTION_XZ or PROJECTION_YZ
+    ///
+    /// Returns: Dictionary with success, error, width, height and data (PackedByteArray, RGBA8)
+    #[func]
+    pub fn render_heatmap_rgba(&self, cortical_id: GString, projection: i32) -> Dictionary {
+        Self::plot_result(self.heatmap_image(cortical_id, projection))
+    }
+
+    /// render_heatmap_rgba as an Image (null on error)
+    #[func]
+    pub fn render_heatmap_image(
+        &self,
+        cortical_id: GString,
+        projection: i32,
+    ) -> Option<Gd<godot::classes::Image>> {
+        Self::plot_image(self.heatmap_image(cortical_id, projection))
+    }
+
+    /// Largest decompressed payload accepted from an LZ4 packet (default 256 MiB).
+    ///
+    /// Packets that declare or produce more are rejected before the memory is allocated.
+    ///
+    /// Args:
+    ///   - max_bytes: ceiling in bytes (values below 1 are ignored)
+    #[func]
+    pub fn set_max_decompressed_bytes(&mut self, max_bytes: i64) {
+        if max_bytes < 1 {
+            godot_error!(
+                "🦀 Ignoring non-positive decompression ceiling: {}",
+                max_bytes
+            );
+            return;
+        }
+        self.ingest_limits.max_decompressed_bytes = max_bytes as usize;
+    }
+
+    /// Current decompression ceiling in bytes
+    #[func]
+    pub fn get_max_decompressed_bytes(&self) -> i64 {
+        self.ingest_limits.max_decompressed_bytes as i64
+    }
+
+    /// Most cortical areas accepted in one frame (default 4096).
+    ///
+    /// Frames with more areas are rejected with ERROR_OVERSIZED_PAYLOAD.
+    ///
+    /// Args:
+    ///   - max_areas: limit (values below 1 are ignored)
+    #[func]
+    pub fn set_max_areas_per_frame(&mut self, max_areas: i64) {
+        if max_areas < 1 {
+            godot_error!("🦀 Ignoring non-positive area limit: {}", max_areas);
+            return;
+        }
+        self.ingest_limits.max_areas_per_frame = max_areas as usize;
+    }
+
+    #[func]
+    pub fn get_max_areas_per_frame(&self) -> i64 {
+        self.ingest_limits.max_areas_per_frame as i64
+    }
+
+    /// Most neurons accepted in one cortical area of a frame (default 16M).
+    ///
+    /// Frames with a larger area are rejected with ERROR_OVERSIZED_PAYLOAD.
+    ///
+    /// Args:
+    ///   - max_neurons: limit (values below 1 are ignored)
+    #[func]
+    pub fn set_max_neurons_per_area(&mut self, max_neurons: i64) {
+        if max_neurons < 1 {
+            godot_error!("🦀 Ignoring non-positive neuron limit: {}", max_neurons);
+            return;
+        }
+        self.ingest_limits.max_neurons_per_area = max_neurons as usize;
+    }
+
+    #[func]
+    pub fn get_max_neurons_per_area(&self) -> i64 {
+        self.ingest_limits.max_neurons_per_area as i64
+    }
+
+    /// Detect the format of a visualization packet without drawing it.
+    ///
+    /// Runs the same ingest path as the decode and apply methods.
+    ///
+    /// Returns: Dictionary with:
+    ///   - success: bool
+    ///   - compression: "none", "lz4_block" or "lz4_frame"
+    ///   - payload_format: "container_v2", "container_v3" or "raw_type11"
+    ///   - packet_bytes / payload_bytes: i64
+    ///   - structure_count, neuron_structure_count, unknown_structures
+    ///   - error: String (precise reason when the format is not recognised)
+    ///   - error_code: int (ERROR_* constant; ERROR_NONE on success)
+    #[func]
+    pub fn detect_packet_format(&self, buffer: PackedByteArray) -> Dictionary {
+        let mut result = Dictionary::new();
+        result.set("success", false);
+        result.set("compression", "");
+        result.set("payload_format", "");
+        result.set("packet_bytes", buffer.len() as i64);
+        result.set("payload_bytes", 0);
+        error::set_error(&mut result, None);
+
+        let rust_buffer: Vec<u8> = buffer.to_vec();
+        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
+            ingest::ingest(rust_buffer, &self.ingest_limits)
+        })) {
+            Ok(Ok(frame)) => {
+                frame.write_ingest_info(&mut result);
+                result.set("success", true);
+            }
+            Ok(Err(e)) => {
+                error::set_error(&mut result, Some(&e));
+            }
+            Err(_) => {
+                let e = DecodeError::new(ErrorKind::Internal, "Packet ingest panic");
+                error::set_error(&mut result, Some(&e));
+            }
+        }
+        result
     }
-}
 
-#[godot_api]
-impl FeagiDataDeserializer {
     /// Decompress LZ4-compressed data from FEAGI PNS layer
     ///
     /// ARCHITECTURE: FEAGI PNS → LZ4 compress → ZMQ → Bridge PASSTHROUGH → WebSocket → BV DECOMPRESS
     ///
+    /// Not needed before the decode/apply methods, which detect LZ4 themselves.
+    ///
     /// Args:
-    ///   - compressed_buffer: LZ4-compressed PackedByteArray from WebSocket
+    ///   - compressed_buffer: LZ4 block or LZ4 frame PackedByteArray from WebSocket; an
+    ///     uncompressed container or Type 11 payload is returned unchanged
     ///
     /// Returns: PackedByteArray (decompressed raw FEAGI data) or empty array on error
     #[func]
     pub fn decompress_lz4(&self, compressed_buffer: PackedByteArray) -> PackedByteArray {
-        if compressed_buffer.is_empty() {
-            godot_error!("🦀 [LZ4] Empty buffer - nothing to decompress");
-            return PackedByteArray::new();
-        }
-
         // Convert PackedByteArray to Vec<u8> for Rust processing
         let compressed_data: Vec<u8> = compressed_buffer.to_vec();
-
-        // Log first 20 bytes for debugging
-        let preview: String = compressed_data
-            .iter()
-            .take(20)
-            .map(|b| format!("{:02x}", b))
-            .collect::<Vec<_>>()
-            .join(" ");
-
-        godot_print!(
-            "🦀 [LZ4] Attempting decompression: {} bytes, first 20 bytes: {}",
-            compressed_data.len(),
-            preview
-        );
-
-        // Decompress with LZ4
-        match lz4::block::decompress(&compressed_data, None) {
-            Ok(decompressed) => {
-                let compression_ratio =
-                    (compressed_data.len() as f64 / decompressed.len() as f64) * 100.0;
-                godot_print!(
-                    "🦀 [LZ4] ✅ Decompressed {} bytes → {} bytes ({:.1}% of original)",
-                    compressed_data.len(),
-                    decompressed.len(),
-                    compression_ratio
-                );
+        let input_len = compressed_data.len();
+
+        // Decompress with LZ4 (block or frame), bounded by the decompression ceiling
+        match ingest::unwrap_compression(compressed_data, &self.ingest_limits) {
+            Ok((_, decompressed)) => {
+                self.log.debug("[LZ4]", || {
+                    format!(
+                        "Decompressed {} bytes → {} bytes ({:.1}% of original)",
+                        input_len,
+                        decompressed.len(),
+                        input_len as f64 / decompressed.len().max(1) as f64 * 100.0
+                    )
+                });
 
                 // Convert Vec<u8> back to PackedByteArray for Godot
                 PackedByteArray::from(decompressed.as_slice())
             }
             Err(e) => {
-                godot_error!(
-                    "🦀 [LZ4] ❌ Decompression failed: {:?} (input size: {} bytes)",
-                    e,
-                    compressed_data.len()
+                let preview: String = compressed_buffer
+                    .as_slice()
+                    .iter()
+                    .take(20)
+                    .map(|b| format!("{:02x}", b))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                self.log.error(
+                    "[LZ4]",
+                    &e.context(format!(
+                        "Decompression failed (input size: {} bytes, first 20 bytes: {})",
+                        input_len, preview
+                    )),
                 );
-                godot_error!("🦀 [LZ4] First 20 bytes: {}", preview);
                 PackedByteArray::new()
             }
         }
     }
 
-    /// Decode Type 11 neuron data (handles both raw Type 11 and FeagiByteContainer wrappers)
+    /// Decode Type 11 neuron data (LZ4 block/frame, FeagiByteContainer v2/v3 or raw Type 11)
+    ///
+    /// Every structure of a container is visited; multiple neuron voxel blocks are merged
+    /// and other structure types are listed under `unknown_structures`.
+    ///
+    /// With activity stats enabled, `area_stats` holds this packet's per-area statistics
+    /// (without rolling rates; see update_activity_stats).
+    ///
+    /// With RECORD_FROM_DECODE (the default, see set_recording_source), the packet is recorded
+    /// into the history (see set_history_enabled) and the raster and heatmaps of the selected
+    /// plot areas (see configure_activity_plots).
+    ///
+    /// Failures set `error` and `error_code` (ERROR_* constant).
     #[func]
-    pub fn decode_type_11_data(&self, buffer: PackedByteArray) -> Dictionary {
+    pub fn decode_type_11_data(&mut self, buffer: PackedByteArray) -> Dictionary {
         // Convert PackedByteArray to Vec<u8> for Rust processing
         let rust_buffer: Vec<u8> = buffer.to_vec();
 
         if rust_buffer.is_empty() {
-            return self.create_error_dict("Empty buffer".to_string());
+            return self
+                .create_error_dict(DecodeError::new(ErrorKind::EmptyBuffer, "Empty buffer"));
         }
 
-        // Detect format based on first byte
-        let first_byte = rust_buffer[0];
-
-        // Log for debugging
-        let _preview: String = rust_buffer
-            .iter()
-            .take(20)
-            .map(|b| format!("{:02x}", b))
-            .collect::<Vec<_>>()
-            .join(" ");
-        // godot_print!("🦀 [PROC] Buf: {} bytes, first byte: 0x{:02x}, preview: {}",
-        //             rust_buffer.len(), first_byte, preview);
-
         // Canonical pipeline (transport-independent):
         // - FEAGI produces FeagiByteContainer (v2 first byte == 2, v3 first byte == 3) containing Type 11 structures
         // - SHM transports the bytes as-is (no compression required)
-        // - WS may optionally compress at the transport layer, but BV should only decode the canonical bytes
+        // - WS may compress at the transport layer (LZ4 block or frame)
         //
-        // Therefore, we do NOT require LZ4 here. We decode either:
-        // - FeagiByteContainer v2 or v3 (first byte == 2 or 3)
-        // - Raw Type 11 struct bytes (first byte == 11) if the container was unwrapped upstream
-        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
-            use feagi_serialization::FeagiByteContainer;
-            use feagi_structures::neuron_voxels::xyzp::CorticalMappedXYZPNeuronVoxels;
-
-            let is_container =
-                first_byte == 2 || first_byte == FeagiByteContainer::CURRENT_FBS_VERSION;
-            if is_container {
-                // FeagiByteContainer v2 or v3
-                let mut byte_container = FeagiByteContainer::new_empty();
-                let mut data_vec = rust_buffer;
-
-                if let Err(e) =
-                    byte_container.try_write_data_to_container_and_verify(&mut |bytes| {
-                        std::mem::swap(bytes, &mut data_vec);
-                        Ok(())
-                    })
-                {
-                    return Err(format!("{:?}", e));
-                }
-
-                let num_structures = byte_container
-                    .try_get_number_contained_structures()
-                    .map_err(|e| format!("{:?}", e))?;
-                if num_structures == 0 {
-                    return Err("Empty container".to_string());
-                }
-
-                let boxed_struct = byte_container
-                    .try_create_new_struct_from_index(0)
-                    .map_err(|e| format!("{:?}", e))?;
-
-                let neuron_data = boxed_struct
-                    .as_any()
-                    .downcast_ref::<CorticalMappedXYZPNeuronVoxels>()
-                    .ok_or_else(|| "Wrong structure type".to_string())?;
-
-                Ok(self.convert_neuron_data_to_godot(neuron_data))
-            } else if first_byte == 11 {
-                // Raw Type 11 struct bytes
-                let mut neuron_data = CorticalMappedXYZPNeuronVoxels::new();
-                neuron_data
-                    .try_deserialize_and_update_self_from_byte_slice(&rust_buffer)
-                    .map_err(|e| format!("{:?}", e))?;
-                Ok(self.convert_neuron_data_to_godot(&neuron_data))
-            } else {
-                Err(format!("Unsupported first byte: {}", first_byte))
-            }
+        // The ingest front door detects all of these, plus raw Type 11 struct bytes
+        // (first byte == 11) if the container was unwrapped upstream.
+        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let frame = ingest::ingest(rust_buffer, &self.ingest_limits)?;
+            self.record_activity(RecordingSource::Decode, &frame.payload.neuron_data);
+            let mut dict = self.convert_neuron_data_to_godot(&frame.payload.neuron_data);
+            frame.write_ingest_info(&mut dict);
+            Ok::<Dictionary, DecodeError>(dict)
         })) {
             Ok(Ok(dict)) => dict,
             Ok(Err(e)) => {
-                godot_error!("🦀 [DECODE] Type 11 decode failed: {}", e);
+                self.log.error("[DECODE]", &e);
                 self.create_error_dict(e)
             }
             Err(_) => {
-                godot_error!("🦀 [DECODE] Type 11 decode PANICKED!");
-                self.create_error_dict("Type 11 decode panic".to_string())
+                let e = DecodeError::new(ErrorKind::Internal, "Type 11 decode panic");
+                self.log.error("[DECODE]", &e);
+                self.create_error_dict(e)
             }
         }
     }
@@ -208,13 +1518,203 @@ impl FeagiDataDeserializer {
         buffer[0] as i32
     }
 
+    /// Convert little-endian SoA byte columns to Godot arrays in one pass.
+    ///
+    /// Args:
+    ///   - x_bytes, y_bytes, z_bytes: int32 coordinates, 4 bytes per neuron
+    ///   - p_bytes: float32 potentials, 4 bytes per neuron
+    ///
+    /// Returns: Dictionary with:
+    ///   - success: bool
+    ///   - x_array, y_array, z_array: PackedInt32Array
+    ///   - p_array: PackedFloat32Array
+    ///   - total_neurons: int
+    ///   - error: String (column sizes that are not a multiple of 4 or differ from each other)
+    ///   - error_code: int (ERROR_* constant; ERROR_NONE on success)
+    #[func]
+    pub fn convert_bulk_arrays_to_godot(
+        &self,
+        x_bytes: PackedByteArray,
+        y_bytes: PackedByteArray,
+        z_bytes: PackedByteArray,
+        p_bytes: PackedByteArray,
+    ) -> Dictionary {
+        let columns = match BulkColumns::from_bytes(
+            x_bytes.as_slice(),
+            y_bytes.as_slice(),
+            z_bytes.as_slice(),
+            p_bytes.as_slice(),
+        ) {
+            Ok(columns) => columns,
+            Err(e) => {
+                let e = DecodeError::new(ErrorKind::InvalidArgument, e);
+                self.log.error("[BULK]", &e);
+                let mut result = BulkColumns::empty().to_area_dictionary(0..0);
+                result.set("success", false);
+                error::set_error(&mut result, Some(&e));
+                result.set("total_neurons", 0);
+                return result;
+            }
+        };
+        let mut result = columns.to_area_dictionary(0..columns.len());
+        result.set("success", true);
+        error::set_error(&mut result, None);
+        result.set("total_neurons", columns.len() as i32);
+        result
+    }
+
+    /// Convert little-endian SoA byte columns and split them into cortical areas.
+    ///
+    /// Args:
+    ///   - x_bytes, y_bytes, z_bytes, p_bytes: as for convert_bulk_arrays_to_godot
+    ///   - cortical_ids: one ID per area, in column order
+    ///   - offsets: first neuron of every area (ascending, starting at 0); may carry one extra
+    ///     entry equal to the neuron count
+    ///
+    /// Returns: Dictionary with success, areas (Dictionary[cortical_id -> {x_array, y_array,
+    /// z_array, p_array}], same shape as decode_type_11_data), total_neurons, error and error_code.
+    /// Areas listed twice are concatenated.
+    #[func]
+    pub fn convert_bulk_arrays_by_area(
+        &self,
+        x_bytes: PackedByteArray,
+        y_bytes: PackedByteArray,
+        z_bytes: PackedByteArray,
+        p_bytes: PackedByteArray,
+        cortical_ids: PackedStringArray,
+        offsets: PackedInt64Array,
+    ) -> Dictionary {
+        let split = BulkColumns::from_bytes(
+            x_bytes.as_slice(),
+            y_bytes.as_slice(),
+            z_bytes.as_slice(),
+            p_bytes.as_slice(),
+        )
+        .and_then(|columns| {
+            let ranges = columns.area_ranges(cortical_ids.len(), offsets.as_slice())?;
+            Ok((columns, ranges))
+        });
+        let (columns, ranges) = match split {
+            Ok(split) => split,
+            Err(e) => {
+                let e = DecodeError::new(ErrorKind::InvalidArgument, e);
+                self.log.error("[BULK]", &e);
+                return self.create_error_dict(e);
+            }
+        };
+
+        // Merge ranges of repeated IDs so every area appears once
+        let mut merged: Vec<(String, Vec<std::ops::Range<usize>>)> = Vec::new();
+        for (cortical_id, range) in cortical_ids.as_slice().iter().zip(ranges) {
+            let id = cortical_id.to_string();
+            match merged.iter_mut().find(|(existing, _)| *existing == id) {
+                Some((_, area_ranges)) => area_ranges.push(range),
+                None => merged.push((id, vec![range])),
+            }
+        }
+
+        let mut areas = Dictionary::new();
+        for (cortical_id, area_ranges) in merged {
+            let area = if area_ranges.len() == 1 {
+                columns.to_area_dictionary(area_ranges[0].clone())
+            } else {
+                columns
+                    .gather(&area_ranges)
+                    .to_area_dictionary(0..area_ranges.iter().map(|r| r.len()).sum())
+            };
+            areas.set(cortical_id.as_str(), area);
+        }
+
+        let mut result = Dictionary::new();
+        result.set("success", true);
+        error::set_error(&mut result, None);
+        result.set("areas", areas);
+        result.set("total_neurons", columns.len() as i32);
+        result
+    }
+
+    /// Encoded packet without compression
+    #[constant]
+    const COMPRESSION_NONE: i32 = 0;
+    /// LZ4 block with a 4-byte little-endian size prefix (PNS default)
+    #[constant]
+    const COMPRESSION_LZ4_BLOCK: i32 = 1;
+    /// Standard LZ4 frame
+    #[constant]
+    const COMPRESSION_LZ4_FRAME: i32 = 2;
+
+    /// Encode neuron activity into a FeagiByteContainer visualization packet.
+    ///
+    /// The inverse of decode_type_11_data: the areas Dictionary has the shape of the decode
+    /// result's `areas`, so a decoded packet can be re-encoded as-is.
+    ///
+    /// Args:
+    ///   - areas: Dictionary[cortical_id (base64) -> {x_array, y_array, z_array: PackedInt32Array,
+    ///     p_array: PackedFloat32Array}]
+    ///   - agent_id_b64: Base64 agent ID stamped into the container (empty = all zeros)
+    ///   - compression: COMPRESSION_NONE, COMPRESSION_LZ4_BLOCK or COMPRESSION_LZ4_FRAME
+    ///
+    /// Returns: Dictionary with:
+    ///   - success: bool
+    ///   - buffer: PackedByteArray (the packet)
+    ///   - total_neurons, area_count: int
+    ///   - payload_bytes / packet_bytes: i64 (before / after compression)
+    ///   - error: String
+    ///   - error_code: int (ERROR_* constant; ERROR_NONE on success)
+    #[func]
+    pub fn encode_type_11_data(
+        &self,
+        areas: Dictionary,
+        agent_id_b64: GString,
+        compression: i32,
+    ) -> Dictionary {
+        let agent_id_b64 = agent_id_b64.to_string();
+        let agent_id = if agent_id_b64.trim().is_empty() {
+            vec![0u8; feagi_serialization::FeagiByteContainer::AGENT_ID_BYTE_COUNT]
+        } else {
+            use base64::Engine;
+            match base64::engine::general_purpose::STANDARD.decode(agent_id_b64.trim()) {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    let e = DecodeError::new(
+                        ErrorKind::InvalidArgument,
+                        format!("Invalid agent ID: {}", e),
+                    );
+                    return Self::encode_result(Err(e), 0);
+                }
+            }
+        };
+        self.encode_packet(
+            &areas,
+            Envelope::Container {
+                agent_id: &agent_id,
+                increment: 0,
+            },
+            compression,
+        )
+    }
+
+    /// Encode neuron activity as a raw Type 11 structure (no container).
+    ///
+    /// Args:
+    ///   - areas: same shape as for encode_type_11_data
+    ///   - compression: COMPRESSION_NONE, COMPRESSION_LZ4_BLOCK or COMPRESSION_LZ4_FRAME
+    ///
+    /// Returns: same Dictionary as encode_type_11_data
+    #[func]
+    pub fn encode_type_11_raw(&self, areas: Dictionary, compression: i32) -> Dictionary {
+        self.encode_packet(&areas, Envelope::RawType11, compression)
+    }
+
     /// High-performance neuron visualization processor
     /// Processes neuron data and pre-calculates transforms and colors in parallel
     ///
     /// Args:
-    ///   - buffer: Raw Type 11 neuron data
+    ///   - buffer: Visualization packet (LZ4 block/frame, FeagiByteContainer v2/v3 or raw Type 11)
     ///   - dimensions: Cortical area dimensions (Vector3)
-    ///   - max_neurons: Maximum neurons to process (0 = unlimited)
+    ///   - max_neurons: Maximum neurons to process (0 = unlimited). Which neurons are kept is
+    ///     chosen by the sampling strategy (see set_sampling_strategy); with LOD enabled, areas
+    ///     are aggregated into super-voxels to fit instead.
     ///
     /// Returns: Dictionary with:
     ///   - success: bool
@@ -223,6 +1723,9 @@ impl FeagiDataDeserializer {
     ///   - neuron_count: i32
     ///   - processing_time_us: i64 (microseconds)
     ///   - error: String
+    ///   - error_code: int (ERROR_* constant; ERROR_NONE on success)
+    ///   - dropped_per_area: Dictionary[cortical_id -> neurons left out to respect max_neurons]
+    ///   - dropped_total: i32
     #[func]
     pub fn process_neuron_visualization(
         &self,
@@ -236,73 +1739,25 @@ impl FeagiDataDeserializer {
         let rust_buffer: Vec<u8> = buffer.to_vec();
         if rust_buffer.is_empty() {
             return self.create_visualization_error_dict(
-                "Empty buffer".to_string(),
-                start_time.elapsed().as_micros() as i64,
-            );
-        }
-        let first_byte = rust_buffer[0];
-
-        // Canonical pipeline (transport-independent):
-        // - FeagiByteContainer v2 or v3 (first byte == 2 or 3) containing Type 11
-        // - Or raw Type 11 (first byte == 11) if upstream unwrapped the container
-        // Always materialize an owned CorticalMappedXYZPNeuronVoxels so we can safely
-        // use it for the duration of this function without borrowing temporary objects.
-        use feagi_serialization::FeagiByteContainer;
-        let is_container = first_byte == 2 || first_byte == FeagiByteContainer::CURRENT_FBS_VERSION;
-        let neuron_data_owned: CorticalMappedXYZPNeuronVoxels = if is_container {
-            let mut byte_container = FeagiByteContainer::new_empty();
-            let mut data_vec = rust_buffer;
-            if let Err(e) = byte_container.try_write_data_to_container_and_verify(&mut |bytes| {
-                std::mem::swap(bytes, &mut data_vec);
-                Ok(())
-            }) {
-                godot_error!("🦀 Failed to load FeagiByteContainer: {:?}", e);
-                return self.create_visualization_error_dict(
-                    format!("FeagiByteContainer error: {:?}", e),
-                    start_time.elapsed().as_micros() as i64,
-                );
-            }
-            let boxed_struct = match byte_container.try_create_new_struct_from_index(0) {
-                Ok(s) => s,
-                Err(e) => {
-                    godot_error!("🦀 Failed to extract structure: {:?}", e);
-                    return self.create_visualization_error_dict(
-                        format!("Structure extract error: {:?}", e),
-                        start_time.elapsed().as_micros() as i64,
-                    );
-                }
-            };
-            match boxed_struct
-                .as_any()
-                .downcast_ref::<CorticalMappedXYZPNeuronVoxels>()
-            {
-                Some(nd) => nd.clone(),
-                None => {
-                    godot_error!("🦀 Structure is not CorticalMappedXYZPNeuronVoxels");
-                    return self.create_visualization_error_dict(
-                        "Wrong structure type".to_string(),
-                        start_time.elapsed().as_micros() as i64,
-                    );
-                }
-            }
-        } else if first_byte == 11 {
-            let mut nd = CorticalMappedXYZPNeuronVoxels::new();
-            if let Err(e) = nd.try_deserialize_and_update_self_from_byte_slice(&rust_buffer) {
-                godot_error!("🦀 Type 11 deserialize failed: {:?}", e);
-                return self.create_visualization_error_dict(
-                    format!("Type 11 deserialize error: {:?}", e),
-                    start_time.elapsed().as_micros() as i64,
-                );
-            }
-            nd
-        } else {
-            godot_error!("🦀 Unsupported payload first byte: {}", first_byte);
-            return self.create_visualization_error_dict(
-                format!("Unsupported payload first byte: {}", first_byte),
+                DecodeError::new(ErrorKind::EmptyBuffer, "Empty buffer"),
                 start_time.elapsed().as_micros() as i64,
             );
+        }
+        // Canonical pipeline (transport-independent):
+        // - FeagiByteContainer v2 or v3 (first byte == 2 or 3) containing Type 11
+        // - Or raw Type 11 (first byte == 11) if upstream unwrapped the container
+        // - Either of them optionally wrapped in an LZ4 block or frame
+        // Always materialize an owned CorticalMappedXYZPNeuronVoxels so we can safely
+        // use it for the duration of this function without borrowing temporary objects.
+        let frame = match ingest::ingest(rust_buffer, &self.ingest_limits) {
+            Ok(frame) => frame,
+            Err(e) => {
+                self.log.error("[VISUALIZATION]", &e);
+                return self
+                    .create_visualization_error_dict(e, start_time.elapsed().as_micros() as i64);
+            }
         };
-        let neuron_data_ref: &CorticalMappedXYZPNeuronVoxels = &neuron_data_owned;
+        let neuron_data_ref: &CorticalMappedXYZPNeuronVoxels = &frame.payload.neuron_data;
 
         // Count total neurons
         let total_neurons: usize = neuron_data_ref.mappings.values().map(|arr| arr.len()).sum();
@@ -314,43 +1769,64 @@ impl FeagiDataDeserializer {
             total_neurons
         };
 
-        // Pre-calculate constants
-        let half_dimensions =
-            Vector3::new(dimensions.x / 2.0, dimensions.y / 2.0, dimensions.z / 2.0);
-        let offset = Vector3::ZERO;
-        let scale = Vector3::new(
-            1.0 / dimensions.x,
-            1.0 / dimensions.y,
-            1.0 / -dimensions.z, // Note: negative Z
-        );
-
-        // Collect all neurons into a flat vector
-        let mut all_neurons = Vec::with_capacity(process_count);
-        for (_, neuron_array) in neuron_data_ref.mappings.iter() {
-            for neuron in neuron_array.iter() {
-                if all_neurons.len() >= process_count {
-                    break;
+        let space = self.placement.space(dimensions);
+
+        // Neurons left out to respect max_neurons, per cortical ID
+        let mut dropped_per_area = Dictionary::new();
+        let mut dropped_total = 0usize;
+        let (transforms, colors) = if self.lod.enabled {
+            // Aggregate instead of truncating: every area gets a share of max_neurons
+            self.process_neurons_lod(
+                neuron_data_ref,
+                dimensions,
+                total_neurons,
+                process_count,
+                &mut dropped_per_area,
+                &mut dropped_total,
+            )
+        } else {
+            // Collect neurons per area (area-fixed coloring needs to know which area a neuron is in)
+            let areas: Vec<(String, Vec<VoxelInstance>)> = neuron_data_ref
+                .mappings
+                .iter()
+                .map(|(cortical_id, neuron_array)| {
+                    let voxels = neuron_array
+                        .iter()
+                        .map(|neuron| {
+                            VoxelInstance::fired(
+                                neuron.neuron_voxel_coordinate.x,
+                                neuron.neuron_voxel_coordinate.y,
+                                neuron.neuron_voxel_coordinate.z,
+                                neuron.potential,
+                            )
+                        })
+                        .collect();
+                    (cortical_id.as_base_64(), voxels)
+                })
+                .collect();
+            let samples = sampling::sample(self.sampling, areas, process_count);
+
+            // Process neurons - use parallel processing on desktop, sequential on WASM
+            let mut transforms = Vec::with_capacity(process_count * 12);
+            let mut colors = Vec::with_capacity(process_count * 4);
+            for sample in samples.iter() {
+                if sample.dropped > 0 {
+                    dropped_per_area.set(sample.cortical_id.as_str(), sample.dropped as i32);
+                    dropped_total += sample.dropped;
                 }
-                all_neurons.push((
-                    neuron.neuron_voxel_coordinate.x,
-                    neuron.neuron_voxel_coordinate.y,
-                    neuron.neuron_voxel_coordinate.z,
-                    neuron.potential,
-                ));
-            }
-            if all_neurons.len() >= process_count {
-                break;
+                let batch: Vec<(u32, u32, u32, f32)> = sample
+                    .voxels
+                    .iter()
+                    .map(|v| (v.x, v.y, v.z, v.potential))
+                    .collect();
+                let area_color = self.color_settings.area_color(&sample.cortical_id);
+                let (area_transforms, area_colors) =
+                    self.process_neurons_internal(&batch, &space, dimensions.z, area_color);
+                transforms.extend(area_transforms);
+                colors.extend(area_colors);
             }
-        }
-
-        // Process neurons - use parallel processing on desktop, sequential on WASM
-        let (transforms, colors) = self.process_neurons_internal(
-            &all_neurons,
-            half_dimensions,
-            offset,
-            scale,
-            dimensions.z,
-        );
+            (transforms, colors)
+        };
         let actual_count = transforms.len() / 12;
 
         let mut transforms_array = PackedFloat32Array::new();
@@ -366,20 +1842,24 @@ impl FeagiDataDeserializer {
         let processing_time = start_time.elapsed().as_micros() as i64;
 
         #[cfg(not(target_family = "wasm"))]
-        godot_print!(
-            "🦀 [RUST-PARALLEL] Processed {} neurons in {} µs ({:.2} ms) using Rayon multi-threading",
-            actual_count,
-            processing_time,
-            processing_time as f64 / 1000.0
-        );
+        self.log.debug("[RUST-PARALLEL]", || {
+            format!(
+                "Processed {} neurons in {} µs ({:.2} ms) using Rayon multi-threading",
+                actual_count,
+                processing_time,
+                processing_time as f64 / 1000.0
+            )
+        });
 
         #[cfg(target_family = "wasm")]
-        godot_print!(
-            "🦀 [RUST-WASM] Processed {} neurons in {} µs ({:.2} ms) - sequential (still 3-4x faster than GDScript!)",
-            actual_count,
-            processing_time,
-            processing_time as f64 / 1000.0
-        );
+        self.log.debug("[RUST-WASM]", || {
+            format!(
+                "Processed {} neurons in {} µs ({:.2} ms) - sequential (still 3-4x faster than GDScript!)",
+                actual_count,
+                processing_time,
+                processing_time as f64 / 1000.0
+            )
+        });
 
         // Return result dictionary
         let mut result = Dictionary::new();
@@ -388,7 +1868,11 @@ impl FeagiDataDeserializer {
         result.set("colors", colors_array);
         result.set("neuron_count", actual_count as i32);
         result.set("processing_time_us", processing_time);
-        result.set("error", "");
+        error::set_error(&mut result, None);
+        result.set("sampling_strategy", self.sampling.strategy.as_i32());
+        result.set("dropped_per_area", dropped_per_area);
+        result.set("dropped_total", dropped_total as i32);
+        frame.write_ingest_info(&mut result);
 
         result
     }
@@ -416,11 +1900,12 @@ impl FeagiDataDeserializer {
         // Validate array sizes
         let array_len = x_array.len();
         if array_len != y_array.len() || array_len != z_array.len() {
-            godot_error!("🦀 Array size mismatch");
+            let e = DecodeError::new(ErrorKind::InvalidArgument, "Array size mismatch");
+            self.log.error("[MULTIMESH]", &e);
             multi_mesh.set_instance_count(0);
             let mut result = Dictionary::new();
             result.set("success", false);
-            result.set("error", "Array size mismatch");
+            error::set_error(&mut result, Some(&e));
             return result;
         }
 
@@ -433,50 +1918,29 @@ impl FeagiDataDeserializer {
         }
 
         if !dimensions_valid_for_neuron_multimesh(dimensions) {
-            godot_error!("Invalid cortical dimensions for multimesh (must be finite and > 0)");
+            let e = DecodeError::new(
+                ErrorKind::InvalidArgument,
+                "Invalid dimensions for multimesh (finite, > 0 required)",
+            );
+            self.log.error("[MULTIMESH]", &e);
             multi_mesh.set_instance_count(0);
             let mut result = Dictionary::new();
             result.set("success", false);
-            result.set("error", "Invalid dimensions for multimesh (finite, > 0 required)");
+            error::set_error(&mut result, Some(&e));
             return result;
         }
 
-        // Set instance count
-        multi_mesh.set_instance_count(array_len as i32);
-
-        // Pre-calculate constants.
-        let half_dimensions =
-            Vector3::new(dimensions.x / 2.0, dimensions.y / 2.0, dimensions.z / 2.0);
-        let offset = Vector3::ZERO;
-        let scale = Vector3::new(1.0 / dimensions.x, 1.0 / dimensions.y, 1.0 / -dimensions.z);
-        let z_max = dimensions.z;
-        let max_x = (dimensions.x as u32).saturating_sub(1);
-        let max_y = (dimensions.y as u32).saturating_sub(1);
-        let max_z = (dimensions.z as u32).saturating_sub(1);
+        // Negative coordinates wrap to large values and are clamped to the area edge,
+        // exactly as the per-instance path always did.
+        let neurons: Vec<VoxelInstance> = (0..array_len)
+            .map(|i| {
+                VoxelInstance::fired(x_array[i] as u32, y_array[i] as u32, z_array[i] as u32, 1.0)
+            })
+            .collect();
 
         // Apply transforms and colors directly (NO GDScript LOOP!)
-        for i in 0..array_len {
-            let x = (x_array[i] as u32).min(max_x);
-            let y = (y_array[i] as u32).min(max_y);
-            let z = (z_array[i] as u32).min(max_z);
-
-            let transform_data = Self::calculate_transform(x, y, z, half_dimensions, offset, scale);
-            let color_data = Self::calculate_color(z, z_max);
-
-            let basis = Basis::from_rows(
-                Vector3::new(transform_data[0], transform_data[1], transform_data[2]),
-                Vector3::new(transform_data[4], transform_data[5], transform_data[6]),
-                Vector3::new(transform_data[8], transform_data[9], transform_data[10]),
-            );
-            let origin = Vector3::new(transform_data[3], transform_data[7], transform_data[11]);
-            let transform = Transform3D::new(basis, origin);
-
-            let color =
-                Color::from_rgba(color_data[0], color_data[1], color_data[2], color_data[3]);
-
-            multi_mesh.set_instance_transform(i as i32, transform);
-            multi_mesh.set_instance_color(i as i32, color);
-        }
+        let (buffer_build_ms, buffer_upload_ms) =
+            self.write_area_instances(&mut multi_mesh, &neurons, dimensions, None);
 
         let elapsed = start_time.elapsed().as_micros() as i64;
 
@@ -484,6 +1948,9 @@ impl FeagiDataDeserializer {
         result.set("success", true);
         result.set("neuron_count", array_len as i32);
         result.set("processing_time_us", elapsed);
+        result.set("upload_mode", self.upload_mode.as_str());
+        result.set("buffer_build_ms", buffer_build_ms);
+        result.set("buffer_upload_ms", buffer_upload_ms);
         result
     }
 
@@ -491,15 +1958,37 @@ impl FeagiDataDeserializer {
     /// This avoids constructing per-area Dictionaries and large PackedArray payloads in GDScript.
     ///
     /// Args:
-    ///  - buffer: raw WebSocket packet (LZ4 header + compressed FeagiByteContainer)
+    ///  - buffer: visualization packet; the format is detected (LZ4 block or frame around a
+    ///    FeagiByteContainer v2/v3 or raw Type 11, or any of those uncompressed)
     ///  - multimeshes_by_id: Dictionary[cortical_id -> MultiMesh]
     ///  - dimensions_by_id: Dictionary[cortical_id -> Vector3]
     ///  - clear_all_before_apply: if true, sets instance_count=0 on all registered MultiMeshes first
     ///
-    /// Returns Dictionary with timing breakdown (ms) and per-area neuron counts.
+    /// When afterglow is enabled (see set_afterglow_enabled), recently fired neurons of every
+    /// registered area stay visible with decaying alpha/size.
+    ///
+    /// When delta mode is enabled (see set_delta_mode_enabled), only instances that changed since
+    /// the previous call are rewritten; delta_added / delta_removed / delta_unchanged report the
+    /// per-frame changes.
+    ///
+    /// When LOD is enabled (see set_lod_enabled), large areas are drawn as super-voxels;
+    /// lod_levels reports the level chosen per aggregated area.
+    ///
+    /// When picking is enabled (see set_picking_enabled), the drawn instances are indexed for
+    /// pick_neuron_ray / pick_neuron_at_point.
+    ///
+    /// When activity stats are enabled (see set_activity_stats_enabled), the burst is recorded
+    /// into the firing-rate window and activity_stats holds get_activity_stats().
+    ///
+    /// With RECORD_FROM_APPLY (see set_recording_source), the packet is recorded into the
+    /// history (see set_history_enabled) and the raster and heatmaps of the selected plot areas
+    /// (see configure_activity_plots).
+    ///
+    /// Returns Dictionary with timing breakdown (ms) and per-area neuron counts; failures set
+    /// `error` and `error_code` (ERROR_* constant).
     #[func]
     pub fn apply_type11_packet_to_multimeshes(
-        &self,
+        &mut self,
         buffer: PackedByteArray,
         multimeshes_by_id: Dictionary,
         dimensions_by_id: Dictionary,
@@ -509,179 +1998,254 @@ impl FeagiDataDeserializer {
 
         let mut out = Dictionary::new();
         out.set("success", false);
-        out.set("error", "");
+        error::set_error(&mut out, None);
         out.set("lz4_ms", 0.0);
         out.set("container_parse_ms", 0.0);
         out.set("clear_ms", 0.0);
         out.set("multimesh_apply_ms", 0.0);
+        out.set("buffer_build_ms", 0.0);
+        out.set("buffer_upload_ms", 0.0);
+        out.set("upload_mode", self.upload_mode.as_str());
+        out.set("afterglow_enabled", self.afterglow.settings.enabled);
+        out.set("afterglow_instances", 0);
+        let delta_active = self.delta.enabled && !self.afterglow.settings.enabled;
+        out.set("delta_mode", delta_active);
+        out.set("delta_added", 0);
+        out.set("delta_removed", 0);
+        out.set("delta_unchanged", 0);
+        out.set("delta_updated", 0);
+        out.set("delta_instances_written", 0);
+        out.set("lod_enabled", self.lod.enabled);
+        out.set("lod_levels", Dictionary::new());
+        out.set("lod_source_voxels", 0);
+        out.set("lod_instances", 0);
         out.set("total_ms", 0.0);
         out.set("areas_applied", 0);
         out.set("neurons_applied", 0);
         out.set("area_counts", Dictionary::new());
+        out.set("structure_count", 0);
+        out.set("neuron_structure_count", 0);
+        out.set("unknown_structures", Array::<Dictionary>::new());
+        out.set("compression", "");
+        out.set("payload_format", "");
+        out.set("packet_bytes", buffer.len() as i64);
+        out.set("payload_bytes", 0);
 
         let rust_buffer: Vec<u8> = buffer.to_vec();
         if rust_buffer.is_empty() {
-            out.set("error", "Empty buffer");
+            let e = DecodeError::new(ErrorKind::EmptyBuffer, "Empty buffer");
+            error::set_error(&mut out, Some(&e));
             out.set("total_ms", total_start.elapsed().as_secs_f64() * 1000.0);
             return out;
         }
 
         // Parse + apply inside one unwind boundary to avoid cloning decoded neuron data.
-        let parse_and_apply_start = std::time::Instant::now();
         let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
-            use feagi_serialization::FeagiByteContainer;
-
             // Canonical pipeline (transport-independent):
             // - FeagiByteContainer v2 or v3 (first byte == 2 or 3) containing Type 11 structures
             // - Or raw Type 11 struct bytes (first byte == 11) if upstream unwrapped it
-            let first_byte = rust_buffer[0];
-            let is_container = first_byte == 2
-                || first_byte == feagi_serialization::FeagiByteContainer::CURRENT_FBS_VERSION;
-            let neuron_data_owned: CorticalMappedXYZPNeuronVoxels = if is_container {
-                let mut byte_container = FeagiByteContainer::new_empty();
-                let mut data_vec = rust_buffer;
-
-                if let Err(e) =
-                    byte_container.try_write_data_to_container_and_verify(&mut |bytes| {
-                        std::mem::swap(bytes, &mut data_vec);
-                        Ok(())
-                    })
-                {
-                    return Err(format!("{:?}", e));
-                }
-
-                let num_structures = match byte_container.try_get_number_contained_structures() {
-                    Ok(n) => n,
-                    Err(e) => return Err(format!("{:?}", e)),
-                };
-                if num_structures == 0 {
-                    return Err("Empty container".to_string());
-                }
-
-                let boxed_struct = match byte_container.try_create_new_struct_from_index(0) {
-                    Ok(s) => s,
-                    Err(e) => return Err(format!("{:?}", e)),
-                };
-
-                let neuron_data_ref = match boxed_struct
-                    .as_any()
-                    .downcast_ref::<CorticalMappedXYZPNeuronVoxels>()
-                {
-                    Some(nd) => nd,
-                    None => return Err("Wrong structure type".to_string()),
-                };
-                neuron_data_ref.clone()
-            } else if first_byte == 11 {
-                let mut nd = CorticalMappedXYZPNeuronVoxels::new();
-                if let Err(e) = nd.try_deserialize_and_update_self_from_byte_slice(&rust_buffer) {
-                    return Err(format!("Type 11 deserialize error: {:?}", e));
-                }
-                nd
-            } else {
-                return Err(format!("Unsupported payload first byte: {}", first_byte));
-            };
-            let neuron_data_ref: &CorticalMappedXYZPNeuronVoxels = &neuron_data_owned;
+            // - Either of them optionally wrapped in an LZ4 block or frame
+            let frame = ingest::ingest(rust_buffer, &self.ingest_limits)?;
+            let neuron_data_ref: &CorticalMappedXYZPNeuronVoxels = &frame.payload.neuron_data;
 
-            let container_parse_ms = parse_and_apply_start.elapsed().as_secs_f64() * 1000.0;
+            let container_parse_ms = frame.parse_ms;
+            self.record_activity(RecordingSource::Apply, neuron_data_ref);
 
             // Clear all registered MultiMeshes first (optional but deterministic: no stale points).
+            // Delta mode only hides instances so the slot tables stay valid.
             let clear_start = std::time::Instant::now();
             if clear_all_before_apply {
                 for (_k, v) in multimeshes_by_id.iter_shared() {
                     if let Ok(mut mm) = v.try_to::<Gd<MultiMesh>>() {
-                        mm.set_instance_count(0);
+                        if delta_active {
+                            mm.set_visible_instance_count(0);
+                        } else {
+                            mm.set_instance_count(0);
+                        }
                     }
                 }
             }
+            if clear_all_before_apply {
+                self.picking.clear_areas();
+            }
             let clear_ms = clear_start.elapsed().as_secs_f64() * 1000.0;
 
             let apply_start = std::time::Instant::now();
             let mut areas_applied: i32 = 0;
             let mut neurons_applied: i32 = 0;
             let mut area_counts = Dictionary::new();
-
+            let mut buffer_build_ms = 0.0f64;
+            let mut buffer_upload_ms = 0.0f64;
+
+            let mut afterglow_instances: i32 = 0;
+            let mut delta_totals = delta::DeltaChanges::default();
+            let mut delta_written: usize = 0;
+            let mut lod_levels = Dictionary::new();
+            let mut lod_source_voxels: usize = 0;
+            let mut lod_instances: usize = 0;
+
+            // Neurons that fired in this burst, per area
+            let stats_enabled = self.stats.enabled;
+            let mut burst_stats: Vec<(String, AreaStats)> = Vec::new();
+            let mut fired_by_area: Vec<(String, Vec<VoxelInstance>)> = Vec::new();
             for (cortical_id, neuron_array) in neuron_data_ref.mappings.iter() {
                 let num_neurons = neuron_array.len();
                 if num_neurons == 0 {
                     continue;
                 }
-
+                let mut area_stats = AreaStats::default();
+                let fired: Vec<VoxelInstance> = neuron_array
+                    .iter()
+                    .map(|neuron| {
+                        let voxel = VoxelInstance::fired(
+                            neuron.neuron_voxel_coordinate.x,
+                            neuron.neuron_voxel_coordinate.y,
+                            neuron.neuron_voxel_coordinate.z,
+                            neuron.potential,
+                        );
+                        if stats_enabled {
+                            area_stats.push(voxel.x, voxel.y, voxel.z, voxel.potential);
+                        }
+                        voxel
+                    })
+                    .collect();
                 let cortical_id_str = cortical_id.as_base_64();
+                if stats_enabled {
+                    burst_stats.push((cortical_id_str.clone(), area_stats));
+                }
+                fired_by_area.push((cortical_id_str, fired));
+            }
 
-                let mm_var = multimeshes_by_id.get(cortical_id_str.as_str());
-                let dims_var = dimensions_by_id.get(cortical_id_str.as_str());
-                let (mm_var, dims_var) = match (mm_var, dims_var) {
-                    (Some(m), Some(d)) => (m, d),
-                    _ => continue,
-                };
+            // With afterglow, every area that still glows is redrawn, not only the ones
+            // present in this packet.
+            let draw_list = if self.afterglow.settings.enabled {
+                self.afterglow.begin_burst();
+                for (cortical_id_str, fired) in fired_by_area.iter() {
+                    self.afterglow.record(cortical_id_str, fired);
+                }
+                self.afterglow
+                    .area_ids()
+                    .into_iter()
+                    .map(|cortical_id_str| {
+                        let glowing = self.afterglow.take_glowing(&cortical_id_str);
+                        (cortical_id_str, glowing)
+                    })
+                    .collect()
+            } else if delta_active {
+                // Tracked areas missing from this packet have no active neurons anymore
+                if clear_all_before_apply {
+                    for cortical_id_str in self.delta.area_ids() {
+                        if !fired_by_area.iter().any(|(id, _)| *id == cortical_id_str) {
+                            fired_by_area.push((cortical_id_str, Vec::new()));
+                        }
+                    }
+                }
+                fired_by_area
+            } else {
+                fired_by_area
+            };
+            if self.afterglow.settings.enabled {
+                // Afterglow rewrites whole areas; slot tables would be stale afterwards
+                self.delta.clear();
+            }
 
-                let mut multi_mesh = match mm_var.try_to::<Gd<MultiMesh>>() {
-                    Ok(m) => m,
-                    Err(_) => continue,
+            for (cortical_id_str, instances) in draw_list.into_iter() {
+                let (mut multi_mesh, dimensions) = match Self::lookup_area_multimesh(
+                    &multimeshes_by_id,
+                    &dimensions_by_id,
+                    &cortical_id_str,
+                ) {
+                    Some(target) => target,
+                    None => continue,
                 };
-                let dimensions = match dims_var.try_to::<Vector3>() {
-                    Ok(d) => d,
-                    Err(_) => continue,
+
+                let num_fired = instances.iter().filter(|v| v.intensity >= 1.0).count();
+                afterglow_instances += (instances.len() - num_fired) as i32;
+
+                // Large areas are drawn as super-voxels
+                let instances = if self.lod.enabled {
+                    lod_source_voxels += instances.len();
+                    let (level, aggregated) = self.lod.apply(&cortical_id_str, instances, 0);
+                    lod_instances += aggregated.len();
+                    if level > 0 {
+                        lod_levels.set(cortical_id_str.as_str(), level as i32);
+                    }
+                    aggregated
+                } else {
+                    instances
                 };
-                if !dimensions_valid_for_neuron_multimesh(dimensions) {
-                    continue;
-                }
 
                 // Set instance count and apply transforms/colors directly.
-                multi_mesh.set_instance_count(num_neurons as i32);
-
-                let half_dimensions =
-                    Vector3::new(dimensions.x / 2.0, dimensions.y / 2.0, dimensions.z / 2.0);
-                let offset = Vector3::ZERO;
-                let scale =
-                    Vector3::new(1.0 / dimensions.x, 1.0 / dimensions.y, 1.0 / -dimensions.z);
-                let z_max = dimensions.z;
-                let max_x = (dimensions.x as u32).saturating_sub(1);
-                let max_y = (dimensions.y as u32).saturating_sub(1);
-                let max_z = (dimensions.z as u32).saturating_sub(1);
-
-                for (i, neuron) in neuron_array.iter().enumerate() {
-                    let x = neuron.neuron_voxel_coordinate.x.min(max_x);
-                    let y = neuron.neuron_voxel_coordinate.y.min(max_y);
-                    let z = neuron.neuron_voxel_coordinate.z.min(max_z);
-
-                    let transform_data =
-                        Self::calculate_transform(x, y, z, half_dimensions, offset, scale);
-                    let color_data = Self::calculate_color(z, z_max);
-
-                    let basis = Basis::from_rows(
-                        Vector3::new(transform_data[0], transform_data[1], transform_data[2]),
-                        Vector3::new(transform_data[4], transform_data[5], transform_data[6]),
-                        Vector3::new(transform_data[8], transform_data[9], transform_data[10]),
-                    );
-                    let origin =
-                        Vector3::new(transform_data[3], transform_data[7], transform_data[11]);
-                    let transform = Transform3D::new(basis, origin);
-
-                    let color = Color::from_rgba(
-                        color_data[0],
-                        color_data[1],
-                        color_data[2],
-                        color_data[3],
+                let area_color = self.color_settings.area_color(cortical_id_str.as_str());
+                let (build_ms, upload_ms) = if delta_active {
+                    let (changes, written, build_ms, upload_ms) = self.apply_area_delta(
+                        &mut multi_mesh,
+                        &cortical_id_str,
+                        &instances,
+                        dimensions,
+                        area_color,
                     );
+                    delta_totals.added += changes.added;
+                    delta_totals.removed += changes.removed;
+                    delta_totals.unchanged += changes.unchanged;
+                    delta_totals.updated += changes.updated;
+                    delta_written += written;
+                    (build_ms, upload_ms)
+                } else {
+                    self.write_area_instances(&mut multi_mesh, &instances, dimensions, area_color)
+                };
+                buffer_build_ms += build_ms;
+                buffer_upload_ms += upload_ms;
 
-                    multi_mesh.set_instance_transform(i as i32, transform);
-                    multi_mesh.set_instance_color(i as i32, color);
+                if stats_enabled {
+                    self.stats.set_dimensions(&cortical_id_str, dimensions);
+                }
+
+                if self.picking.enabled {
+                    let drawn = if delta_active {
+                        self.delta.active(&cortical_id_str)
+                    } else {
+                        &instances
+                    };
+                    self.picking
+                        .record_area(&cortical_id_str, dimensions, drawn);
                 }
 
                 areas_applied += 1;
-                neurons_applied += num_neurons as i32;
-                area_counts.set(cortical_id_str.as_str(), num_neurons as i32);
+                neurons_applied += num_fired as i32;
+                if num_fired > 0 {
+                    area_counts.set(cortical_id_str.as_str(), num_fired as i32);
+                }
             }
 
             let multimesh_apply_ms = apply_start.elapsed().as_secs_f64() * 1000.0;
-            Ok((
+            // Fields merged into the result as-is
+            let mut extra_fields = Dictionary::new();
+            frame.write_ingest_info(&mut extra_fields);
+            extra_fields.set("lz4_ms", frame.lz4_ms);
+            if stats_enabled {
+                self.stats.record_burst(burst_stats);
+                extra_fields.set("activity_stats", self.stats.to_dictionary());
+            }
+            extra_fields.set("buffer_build_ms", buffer_build_ms);
+            extra_fields.set("buffer_upload_ms", buffer_upload_ms);
+            extra_fields.set("afterglow_instances", afterglow_instances);
+            extra_fields.set("delta_added", delta_totals.added as i32);
+            extra_fields.set("delta_removed", delta_totals.removed as i32);
+            extra_fields.set("delta_unchanged", delta_totals.unchanged as i32);
+            extra_fields.set("delta_updated", delta_totals.updated as i32);
+            extra_fields.set("delta_instances_written", delta_written as i32);
+            extra_fields.set("lod_levels", lod_levels);
+            extra_fields.set("lod_source_voxels", lod_source_voxels as i32);
+            extra_fields.set("lod_instances", lod_instances as i32);
+            Ok::<_, DecodeError>((
                 container_parse_ms,
                 clear_ms,
                 multimesh_apply_ms,
                 areas_applied,
                 neurons_applied,
                 area_counts,
+                extra_fields,
             ))
         }));
 
@@ -693,6 +2257,7 @@ impl FeagiDataDeserializer {
                 areas_applied,
                 neurons_applied,
                 area_counts,
+                extra_fields,
             ))) => {
                 out.set("container_parse_ms", container_parse_ms);
                 out.set("clear_ms", clear_ms);
@@ -700,13 +2265,20 @@ impl FeagiDataDeserializer {
                 out.set("areas_applied", areas_applied);
                 out.set("neurons_applied", neurons_applied);
                 out.set("area_counts", area_counts);
+                for (key, value) in extra_fields.iter_shared() {
+                    out.set(key, value);
+                }
                 out.set("success", true);
             }
             Ok(Err(e)) => {
-                out.set("error", format!("FeagiByteContainer error: {}", e));
+                let e = e.context("Packet ingest error");
+                self.log.error("[APPLY]", &e);
+                error::set_error(&mut out, Some(&e));
             }
             Err(_) => {
-                out.set("error", "FeagiByteContainer panic");
+                let e = DecodeError::new(ErrorKind::Internal, "FeagiByteContainer panic");
+                self.log.error("[APPLY]", &e);
+                error::set_error(&mut out, Some(&e));
             }
         }
 
@@ -737,21 +2309,23 @@ impl FeagiDataDeserializer {
         // Validate array sizes
         let array_len = x_array.len();
         if array_len != y_array.len() || array_len != z_array.len() {
-            godot_error!(
-                "🦀 Array size mismatch: x={}, y={}, z={}",
-                array_len,
-                y_array.len(),
-                z_array.len()
-            );
-            return self.create_visualization_error_dict(
-                "Array size mismatch".to_string(),
-                start_time.elapsed().as_micros() as i64,
+            let e = DecodeError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Array size mismatch: x={}, y={}, z={}",
+                    array_len,
+                    y_array.len(),
+                    z_array.len()
+                ),
             );
+            self.log.error("[VISUALIZATION]", &e);
+            return self
+                .create_visualization_error_dict(e, start_time.elapsed().as_micros() as i64);
         }
 
         if array_len == 0 {
             return self.create_visualization_error_dict(
-                "Empty arrays".to_string(),
+                DecodeError::new(ErrorKind::EmptyBuffer, "Empty arrays"),
                 start_time.elapsed().as_micros() as i64,
             );
         }
@@ -763,11 +2337,7 @@ impl FeagiDataDeserializer {
             array_len
         };
 
-        // Pre-calculate constants
-        let half_dimensions =
-            Vector3::new(dimensions.x / 2.0, dimensions.y / 2.0, dimensions.z / 2.0);
-        let offset = Vector3::ZERO;
-        let scale = Vector3::new(1.0 / dimensions.x, 1.0 / dimensions.y, 1.0 / -dimensions.z);
+        let space = self.placement.space(dimensions);
         let max_x = (dimensions.x as i32).saturating_sub(1).max(0);
         let max_y = (dimensions.y as i32).saturating_sub(1).max(0);
         let max_z = (dimensions.z as i32).saturating_sub(1).max(0);
@@ -784,8 +2354,7 @@ impl FeagiDataDeserializer {
             .collect();
 
         // Process neurons - use parallel processing on desktop, sequential on WASM
-        let (transforms, colors) =
-            self.process_coords_internal(&coords, half_dimensions, offset, scale, dimensions.z);
+        let (transforms, colors) = self.process_coords_internal(&coords, &space, dimensions.z);
 
         // Convert to Godot PackedArrays
         let mut transforms_array = PackedFloat32Array::new();
@@ -801,20 +2370,24 @@ impl FeagiDataDeserializer {
         let processing_time = start_time.elapsed().as_micros() as i64;
 
         #[cfg(not(target_family = "wasm"))]
-        godot_print!(
-            "🦀 [RUST-PARALLEL] Processed {} neurons in {} µs ({:.2} ms) using Rayon multi-threading",
-            process_count,
-            processing_time,
-            processing_time as f64 / 1000.0
-        );
+        self.log.debug("[RUST-PARALLEL]", || {
+            format!(
+                "Processed {} neurons in {} µs ({:.2} ms) using Rayon multi-threading",
+                process_count,
+                processing_time,
+                processing_time as f64 / 1000.0
+            )
+        });
 
         #[cfg(target_family = "wasm")]
-        godot_print!(
-            "🦀 [RUST-WASM] Processed {} neurons in {} µs ({:.2} ms) - sequential (still 3-4x faster than GDScript!)",
-            process_count,
-            processing_time,
-            processing_time as f64 / 1000.0
-        );
+        self.log.debug("[RUST-WASM]", || {
+            format!(
+                "Processed {} neurons in {} µs ({:.2} ms) - sequential (still 3-4x faster than GDScript!)",
+                process_count,
+                processing_time,
+                processing_time as f64 / 1000.0
+            )
+        });
 
         // Return result dictionary
         let mut result = Dictionary::new();
@@ -823,7 +2396,7 @@ impl FeagiDataDeserializer {
         result.set("colors", colors_array);
         result.set("neuron_count", process_count as i32);
         result.set("processing_time_us", processing_time);
-        result.set("error", "");
+        error::set_error(&mut result, None);
 
         result
     }
@@ -1325,25 +2898,75 @@ impl FeagiDataDeserializer {
 
 // Private helper methods
 impl FeagiDataDeserializer {
+    /// Record a decoded burst into the history and plots if `source` is the recording source.
+    fn record_activity(
+        &mut self,
+        source: RecordingSource,
+        neuron_data: &CorticalMappedXYZPNeuronVoxels,
+    ) {
+        if source != self.recording_source {
+            if (self.history.enabled || self.plots.is_active()) && self.source_watch.skipped() {
+                godot_warn!(
+                    "🦀 [HISTORY] History or activity plots are on, but bursts only reach {} while the recording source is {}; nothing is recorded (see set_recording_source)",
+                    source.method_name(),
+                    self.recording_source.method_name()
+                );
+            }
+            return;
+        }
+        self.source_watch.recorded();
+        if self.history.enabled {
+            self.history.record(neuron_data);
+        }
+        if self.plots.is_active() {
+            self.plots.record(neuron_data);
+        }
+    }
+
+    /// Voxel coordinate clamped to the area bounds, as the apply paths draw it.
+    fn clamp_voxel(dimensions: Vector3, voxel: Vector3i) -> [u32; 3] {
+        let clamp = |v: i32, d: f32| (v.max(0) as u32).min((d as u32).saturating_sub(1));
+        [
+            clamp(voxel.x, dimensions.x),
+            clamp(voxel.y, dimensions.y),
+            clamp(voxel.z, dimensions.z),
+        ]
+    }
+
+    /// Update the picking fallback transform of a placed area.
+    fn refresh_placed_transform(&mut self, cortical_id: &str) {
+        let transform = self
+            .placement
+            .area_space(cortical_id)
+            .map(|space| space.local_to_world());
+        self.picking.set_placed_transform(cortical_id, transform);
+    }
+
+    fn refresh_placed_transforms(&mut self) {
+        let ids: Vec<String> = self.placement.area_ids().cloned().collect();
+        for cortical_id in ids {
+            self.refresh_placed_transform(&cortical_id);
+        }
+    }
     /// Process neurons - DESKTOP VERSION with Rayon parallel processing
     #[cfg(not(target_family = "wasm"))]
     fn process_neurons_internal(
         &self,
         neurons: &[(u32, u32, u32, f32)],
-        half_dimensions: Vector3,
-        offset: Vector3,
-        scale: Vector3,
+        space: &AreaSpace,
         z_max: f32,
+        area_color: Option<[f32; 4]>,
     ) -> (Vec<f32>, Vec<f32>) {
+        let color_settings = &self.color_settings;
+
         // Parallel fold + reduce - each thread builds its own chunk, then we concatenate
         let (transforms, colors) = neurons
             .par_iter()
             .fold(
                 || (Vec::with_capacity(1024 * 12), Vec::with_capacity(1024 * 4)),
-                |(mut transforms, mut colors), (x, y, z, _potential)| {
-                    let transform_data =
-                        Self::calculate_transform(*x, *y, *z, half_dimensions, offset, scale);
-                    let color_data = Self::calculate_color(*z, z_max);
+                |(mut transforms, mut colors), (x, y, z, potential)| {
+                    let transform_data = space.instance_transform(*x, *y, *z);
+                    let color_data = color_settings.color(*z, z_max, *potential, area_color);
                     transforms.extend_from_slice(&transform_data);
                     colors.extend_from_slice(&color_data);
                     (transforms, colors)
@@ -1366,19 +2989,18 @@ impl FeagiDataDeserializer {
     fn process_neurons_internal(
         &self,
         neurons: &[(u32, u32, u32, f32)],
-        half_dimensions: Vector3,
-        offset: Vector3,
-        scale: Vector3,
+        space: &AreaSpace,
         z_max: f32,
+        area_color: Option<[f32; 4]>,
     ) -> (Vec<f32>, Vec<f32>) {
+        let color_settings = &self.color_settings;
         let mut transforms = Vec::with_capacity(neurons.len() * 12);
         let mut colors = Vec::with_capacity(neurons.len() * 4);
 
         // Sequential processing (WASM - still faster than GDScript!)
-        for (x, y, z, _potential) in neurons.iter() {
-            let transform_data =
-                Self::calculate_transform(*x, *y, *z, half_dimensions, offset, scale);
-            let color_data = Self::calculate_color(*z, z_max);
+        for (x, y, z, potential) in neurons.iter() {
+            let transform_data = space.instance_transform(*x, *y, *z);
+            let color_data = color_settings.color(*z, z_max, *potential, area_color);
 
             transforms.extend_from_slice(&transform_data);
             colors.extend_from_slice(&color_data);
@@ -1392,24 +3014,18 @@ impl FeagiDataDeserializer {
     fn process_coords_internal(
         &self,
         coords: &[(i32, i32, i32)],
-        half_dimensions: Vector3,
-        offset: Vector3,
-        scale: Vector3,
+        space: &AreaSpace,
         z_max: f32,
     ) -> (Vec<f32>, Vec<f32>) {
+        // Coordinate-only input carries no potential; treat every neuron as fully fired
+        let color_settings = &self.color_settings;
+
         // Parallel processing using Rayon (desktop only)
         let results: Vec<([f32; 12], [f32; 4])> = coords
             .par_iter()
             .map(|(x, y, z)| {
-                let transform_data = Self::calculate_transform(
-                    *x as u32,
-                    *y as u32,
-                    *z as u32,
-                    half_dimensions,
-                    offset,
-                    scale,
-                );
-                let color_data = Self::calculate_color(*z as u32, z_max);
+                let transform_data = space.instance_transform(*x as u32, *y as u32, *z as u32);
+                let color_data = color_settings.color(*z as u32, z_max, 1.0, None);
                 (transform_data, color_data)
             })
             .collect();
@@ -1430,25 +3046,18 @@ impl FeagiDataDeserializer {
     fn process_coords_internal(
         &self,
         coords: &[(i32, i32, i32)],
-        half_dimensions: Vector3,
-        offset: Vector3,
-        scale: Vector3,
+        space: &AreaSpace,
         z_max: f32,
     ) -> (Vec<f32>, Vec<f32>) {
+        // Coordinate-only input carries no potential; treat every neuron as fully fired
+        let color_settings = &self.color_settings;
         let mut transforms = Vec::with_capacity(coords.len() * 12);
         let mut colors = Vec::with_capacity(coords.len() * 4);
 
         // Sequential processing (WASM - still faster than GDScript!)
         for (x, y, z) in coords.iter() {
-            let transform_data = Self::calculate_transform(
-                *x as u32,
-                *y as u32,
-                *z as u32,
-                half_dimensions,
-                offset,
-                scale,
-            );
-            let color_data = Self::calculate_color(*z as u32, z_max);
+            let transform_data = space.instance_transform(*x as u32, *y as u32, *z as u32);
+            let color_data = color_settings.color(*z as u32, z_max, 1.0, None);
 
             transforms.extend_from_slice(&transform_data);
             colors.extend_from_slice(&color_data);
@@ -1457,49 +3066,458 @@ impl FeagiDataDeserializer {
         (transforms, colors)
     }
 
-    /// Calculate transform matrix for a single neuron (shared by both versions)
-    /// Matches GDScript logic: transform.origin = centered_pos; transform = transform.scaled(scale)
-    #[inline(always)]
-    fn calculate_transform(
-        x: u32,
-        y: u32,
-        z: u32,
-        half_dimensions: Vector3,
-        offset: Vector3,
-        scale: Vector3,
-    ) -> [f32; 12] {
-        let feagi_pos = Vector3::new(x as f32, y as f32, z as f32);
-        // Centered position: feagi_pos - half_dimensions + offset (offset ZERO per NEURON_POSITION_SCALING_FIX.md)
-        let centered_pos = Vector3::new(
-            feagi_pos.x - half_dimensions.x + offset.x,
-            feagi_pos.y - half_dimensions.y + offset.y,
-            feagi_pos.z - half_dimensions.z + offset.z,
+    /// Resolve the MultiMesh and dimensions registered for a cortical area.
+    ///
+    /// Returns None if either is missing, has the wrong type, or the dimensions are unusable.
+    fn lookup_area_multimesh(
+        multimeshes_by_id: &Dictionary,
+        dimensions_by_id: &Dictionary,
+        cortical_id: &str,
+    ) -> Option<(Gd<MultiMesh>, Vector3)> {
+        let multi_mesh = multimeshes_by_id
+            .get(cortical_id)?
+            .try_to::<Gd<MultiMesh>>()
+            .ok()?;
+        let dimensions = dimensions_by_id
+            .get(cortical_id)?
+            .try_to::<Vector3>()
+            .ok()?;
+        if !dimensions_valid_for_neuron_multimesh(dimensions) {
+            return None;
+        }
+        Some((multi_mesh, dimensions))
+    }
+
+    /// Write one area's neurons into its MultiMesh using the selected upload mode.
+    ///
+    /// Coordinates are clamped to the area bounds. Returns (buffer_build_ms, buffer_upload_ms);
+    /// the per-instance path reports all of its time as upload.
+    fn write_area_instances(
+        &self,
+        multi_mesh: &mut Gd<MultiMesh>,
+        neurons: &[VoxelInstance],
+        dimensions: Vector3,
+        area_color: Option<[f32; 4]>,
+    ) -> (f64, f64) {
+        let timings = self.write_area_instances_with_capacity(
+            multi_mesh,
+            neurons,
+            dimensions,
+            area_color,
+            neurons.len(),
+        );
+        // Undo a visible_instance_count left behind by delta mode
+        multi_mesh.set_visible_instance_count(-1);
+        timings
+    }
+
+    /// Like `write_area_instances`, but allocates `capacity` (>= neurons.len()) instances;
+    /// the slots after the neurons are zeroed.
+    fn write_area_instances_with_capacity(
+        &self,
+        multi_mesh: &mut Gd<MultiMesh>,
+        neurons: &[VoxelInstance],
+        dimensions: Vector3,
+        area_color: Option<[f32; 4]>,
+        capacity: usize,
+    ) -> (f64, f64) {
+        multi_mesh.set_instance_count(capacity as i32);
+        let instance = self.instance_builder(dimensions, area_color);
+
+        let layout = match self.upload_mode {
+            UploadMode::Bulk => multimesh_buffer::BufferLayout::of(multi_mesh),
+            UploadMode::PerInstance => None,
+        };
+
+        if let Some(layout) = layout {
+            let build_start = std::time::Instant::now();
+            let mut buffer = multimesh_buffer::build_instance_buffer(neurons, layout, instance);
+            buffer.resize(capacity * layout.stride, 0.0);
+            let build_ms = build_start.elapsed().as_secs_f64() * 1000.0;
+
+            let upload_start = std::time::Instant::now();
+            multi_mesh.set_buffer(&PackedFloat32Array::from(buffer.as_slice()));
+            return (build_ms, upload_start.elapsed().as_secs_f64() * 1000.0);
+        }
+
+        let upload_start = std::time::Instant::now();
+        for (i, neuron) in neurons.iter().enumerate() {
+            let (transform_data, color_data) = instance(neuron);
+            let color =
+                Color::from_rgba(color_data[0], color_data[1], color_data[2], color_data[3]);
+            multi_mesh.set_instance_transform(
+                i as i32,
+                multimesh_buffer::transform_from_rows(&transform_data),
+            );
+            multi_mesh.set_instance_color(i as i32, color);
+        }
+        (0.0, upload_start.elapsed().as_secs_f64() * 1000.0)
+    }
+
+    /// Per-voxel (transform, color) for an area: coordinates clamped to the area bounds,
+    /// super-voxels centered on their cell, colored by the active color settings and faded by
+    /// the voxel's afterglow intensity.
+    fn instance_builder(
+        &self,
+        dimensions: Vector3,
+        area_color: Option<[f32; 4]>,
+    ) -> impl Fn(&VoxelInstance) -> ([f32; 12], [f32; 4]) + Sync + Send + '_ {
+        let space = self.placement.space(dimensions);
+        let z_max = dimensions.z;
+        let max_x = (dimensions.x as u32).saturating_sub(1);
+        let max_y = (dimensions.y as u32).saturating_sub(1);
+        let max_z = (dimensions.z as u32).saturating_sub(1);
+
+        let color_settings = &self.color_settings;
+        let afterglow = self.afterglow.settings;
+        move |voxel: &VoxelInstance| {
+            let x = voxel.x.min(max_x);
+            let y = voxel.y.min(max_y);
+            let z = voxel.z.min(max_z);
+            let mut transform = space.instance_transform(x, y, z);
+            if voxel.extent > 1 || voxel.size != 1.0 {
+                lod::resize_cell(
+                    &mut transform,
+                    voxel.extent,
+                    voxel.size,
+                    space.voxel_pitch(),
+                );
+            }
+            let mut color = color_settings.color(z, z_max, voxel.potential, area_color);
+            afterglow.fade(&mut transform, &mut color, voxel.intensity);
+            (transform, color)
+        }
+    }
+
+    /// Apply one area's frame in delta mode.
+    ///
+    /// Only slots whose voxel appeared, moved or changed color are rewritten. The whole area is
+    /// rewritten instead when its slot table is new or stale (instance count or dimensions
+    /// changed outside of delta mode), when it outgrew the spare capacity, or when more than
+    /// half of the active slots changed.
+    ///
+    /// Returns (changes, instances_written, buffer_build_ms, buffer_upload_ms)
+    fn apply_area_delta(
+        &mut self,
+        multi_mesh: &mut Gd<MultiMesh>,
+        cortical_id: &str,
+        frame: &[VoxelInstance],
+        dimensions: Vector3,
+        area_color: Option<[f32; 4]>,
+    ) -> (delta::DeltaChanges, usize, f64, f64) {
+        let potential_sensitive = matches!(
+            self.color_settings.mode,
+            ColorMode::Potential | ColorMode::SignedDiverging
         );
+        let mut area = self.delta.take_area(cortical_id);
+        let valid = area.capacity > 0
+            && multi_mesh.get_instance_count() as usize == area.capacity
+            && area.dimensions == dimensions;
+        if !valid {
+            area.reset();
+        }
 
-        // Transform3D as 3x4 matrix with SCALED basis vectors (matches GDScript: transform.scaled(scale))
-        // This applies scale to both the basis and the origin
-        [
-            scale.x,
-            0.0,
-            0.0,
-            centered_pos.x * scale.x, // Row 0: scaled X basis + scaled origin.x
-            0.0,
-            scale.y,
-            0.0,
-            centered_pos.y * scale.y, // Row 1: scaled Y basis + scaled origin.y
-            0.0,
-            0.0,
-            scale.z,
-            centered_pos.z * scale.z, // Row 2: scaled Z basis + scaled origin.z
-        ]
+        let changes = area.update(frame, potential_sensitive);
+        let active = area.active();
+        let full_rewrite =
+            !valid || active.len() > area.capacity || changes.dirty_slots.len() * 2 > active.len();
+        let capacity = if valid && active.len() <= area.capacity {
+            area.capacity
+        } else {
+            delta::capacity_for(active.len())
+        };
+
+        let (written, build_ms, upload_ms) = if full_rewrite {
+            let (build_ms, upload_ms) = self.write_area_instances_with_capacity(
+                multi_mesh, active, dimensions, area_color, capacity,
+            );
+            (active.len(), build_ms, upload_ms)
+        } else {
+            let upload_start = std::time::Instant::now();
+            let instance = self.instance_builder(dimensions, area_color);
+            let use_colors = multi_mesh.is_using_colors();
+            for &slot in &changes.dirty_slots {
+                let (transform_data, color_data) = instance(&active[slot]);
+                multi_mesh.set_instance_transform(
+                    slot as i32,
+                    multimesh_buffer::transform_from_rows(&transform_data),
+                );
+                if use_colors {
+                    multi_mesh.set_instance_color(
+                        slot as i32,
+                        Color::from_rgba(
+                            color_data[0],
+                            color_data[1],
+                            color_data[2],
+                            color_data[3],
+                        ),
+                    );
+                }
+            }
+            (
+                changes.dirty_slots.len(),
+                0.0,
+                upload_start.elapsed().as_secs_f64() * 1000.0,
+            )
+        };
+        multi_mesh.set_visible_instance_count(active.len() as i32);
+
+        area.capacity = capacity;
+        area.dimensions = dimensions;
+        self.delta.put_area(cortical_id, area);
+        (changes, written, build_ms, upload_ms)
+    }
+
+    fn raster_image(&self, max_rows: i32) -> Result<Rgba8Image, String> {
+        let top = self.color_settings.gradient().sample(1.0);
+        self.plots
+            .render_raster(max_rows.max(0) as usize, |cortical_id| {
+                self.color_settings
+                    .assigned_area_color(cortical_id)
+                    .unwrap_or(top)
+            })
+    }
+
+    fn heatmap_image(&self, cortical_id: GString, projection: i32) -> Result<Rgba8Image, String> {
+        let projection = Projection::from_i32(projection)
+            .ok_or_else(|| format!("Unknown projection: {}", projection))?;
+        self.plots.render_heatmap(
+            &cortical_id.to_string(),
+            projection,
+            self.color_settings.gradient(),
+        )
+    }
+
+    /// Shared body of the encode API.
+    fn encode_packet(
+        &self,
+        areas: &Dictionary,
+        envelope: Envelope,
+        compression: i32,
+    ) -> Dictionary {
+        let compression = match Compression::from_i32(compression) {
+            Some(c) => c,
+            None => {
+                let e = DecodeError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Unknown compression: {}", compression),
+                );
+                return Self::encode_result(Err(e), 0);
+            }
+        };
+        let areas = match Self::areas_from_godot(areas) {
+            Ok(areas) => areas,
+            Err(e) => {
+                return Self::encode_result(Err(DecodeError::new(ErrorKind::InvalidArgument, e)), 0)
+            }
+        };
+        let total_neurons: usize = areas.iter().map(|a| a.len()).sum();
+        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            encoder::encode(&areas, envelope, compression)
+        })) {
+            Ok(Ok((packet, payload_bytes))) => {
+                let mut result = Self::encode_result(Ok(packet), payload_bytes);
+                result.set("total_neurons", total_neurons as i32);
+                result.set("area_count", areas.len() as i32);
+                result
+            }
+            Ok(Err(e)) => {
+                let e = DecodeError::new(ErrorKind::InvalidArgument, e)
+                    .context("Type 11 encode failed");
+                self.log.error("[ENCODE]", &e);
+                Self::encode_result(Err(e), 0)
+            }
+            Err(_) => {
+                let e = DecodeError::new(ErrorKind::Internal, "Type 11 encode panic");
+                self.log.error("[ENCODE]", &e);
+                Self::encode_result(Err(e), 0)
+            }
+        }
+    }
+
+    /// Per-area arrays from a Dictionary shaped like the decode result's `areas`.
+    fn areas_from_godot(areas: &Dictionary) -> Result<Vec<AreaActivity>, String> {
+        let mut out = Vec::with_capacity(areas.len());
+        for (key, value) in areas.iter_shared() {
+            let cortical_id = key.to::<String>();
+            let area = value
+                .try_to::<Dictionary>()
+                .map_err(|_| format!("Area {}: value is not a Dictionary", cortical_id))?;
+            out.push(AreaActivity::from_dictionary(cortical_id, &area)?);
+        }
+        Ok(out)
+    }
+
+    /// Result dictionary of the encode API.
+    fn encode_result(packet: Result<Vec<u8>, DecodeError>, payload_bytes: usize) -> Dictionary {
+        let mut result = Dictionary::new();
+        result.set("total_neurons", 0);
+        result.set("area_count", 0);
+        match packet {
+            Ok(packet) => {
+                result.set("success", true);
+                error::set_error(&mut result, None);
+                result.set("payload_bytes", payload_bytes as i64);
+                result.set("packet_bytes", packet.len() as i64);
+                result.set("buffer", PackedByteArray::from(packet.as_slice()));
+            }
+            Err(e) => {
+                result.set("success", false);
+                error::set_error(&mut result, Some(&e));
+                result.set("payload_bytes", 0);
+                result.set("packet_bytes", 0);
+                result.set("buffer", PackedByteArray::new());
+            }
+        }
+        result
+    }
+
+    /// Result dictionary of the plot API.
+    fn plot_result(image: Result<Rgba8Image, String>) -> Dictionary {
+        let mut result = Dictionary::new();
+        match image {
+            Ok(image) => {
+                result.set("success", true);
+                error::set_error(&mut result, None);
+                result.set("width", image.width as i32);
+                result.set("height", image.height as i32);
+                result.set("data", PackedByteArray::from(image.data.as_slice()));
+            }
+            Err(e) => {
+                result.set("success", false);
+                error::set_error(
+                    &mut result,
+                    Some(&DecodeError::new(ErrorKind::InvalidArgument, e)),
+                );
+                result.set("width", 0);
+                result.set("height", 0);
+                result.set("data", PackedByteArray::new());
+            }
+        }
+        result
+    }
+
+    fn plot_image(image: Result<Rgba8Image, String>) -> Option<Gd<godot::classes::Image>> {
+        match image {
+            Ok(image) => godot::classes::Image::create_from_data(
+                image.width as i32,
+                image.height as i32,
+                false,
+                godot::classes::image::Format::RGBA8,
+                &PackedByteArray::from(image.data.as_slice()),
+            ),
+            Err(e) => {
+                godot_error!("🦀 [PLOTS] {}", e);
+                None
+            }
+        }
+    }
+
+    /// Result dictionary of the picking API.
+    fn pick_result(hit: Option<PickHit>, error: &str) -> Dictionary {
+        let mut result = Dictionary::new();
+        result.set("success", error.is_empty());
+        result.set("hit", false);
+        result.set("error", error);
+        if let Some(hit) = hit {
+            Self::write_pick_entry(&mut result, &hit.cortical_id, &hit.entry);
+            result.set("distance", hit.distance);
+            result.set("position", hit.position);
+        }
+        result
+    }
+
+    fn write_pick_entry(result: &mut Dictionary, cortical_id: &str, entry: &picking::PickEntry) {
+        result.set("hit", true);
+        result.set("cortical_id", cortical_id);
+        result.set(
+            "voxel",
+            Vector3i::new(entry.x as i32, entry.y as i32, entry.z as i32),
+        );
+        result.set("extent", entry.extent as i32);
+        result.set("potential", entry.potential);
+        result.set("instance_index", entry.instance as i32);
+    }
+
+    /// Instance colors written in delta mode are stale after any color setting changes.
+    fn color_settings_changed(&mut self) {
+        self.delta.clear();
     }
 
-    /// Calculate z-depth color for a single neuron (shared by both versions)
-    #[inline(always)]
-    fn calculate_color(z: u32, z_max: f32) -> [f32; 4] {
-        let z_normalized = (z as f32 / z_max).clamp(0.0, 1.0);
-        let red_intensity = (1.0 - z_normalized).max(0.2); // Front bright, back dark
-        [red_intensity, 0.0, 0.0, 1.0] // Red gradient with full alpha
+    /// LOD variant of the process_neuron_visualization core: areas are aggregated into
+    /// super-voxels, each with a share of `process_count` proportional to its neuron count
+    /// (no share when nothing has to be dropped). The result is still capped at `process_count`;
+    /// neurons of super-voxels cut by the cap are added to `dropped_per_area`.
+    ///
+    /// Returns (transforms, colors) in the same layout as process_neurons_internal
+    fn process_neurons_lod(
+        &self,
+        neuron_data: &CorticalMappedXYZPNeuronVoxels,
+        dimensions: Vector3,
+        total_neurons: usize,
+        process_count: usize,
+        dropped_per_area: &mut Dictionary,
+        dropped_total: &mut usize,
+    ) -> (Vec<f32>, Vec<f32>) {
+        let mut transforms = Vec::new();
+        let mut colors = Vec::new();
+        let mut remaining = process_count;
+        for (cortical_id, neuron_array) in neuron_data.mappings.iter() {
+            let cortical_id_str = cortical_id.as_base_64();
+            if remaining == 0 {
+                dropped_per_area.set(cortical_id_str.as_str(), neuron_array.len() as i32);
+                *dropped_total += neuron_array.len();
+                continue;
+            }
+            let voxels: Vec<VoxelInstance> = neuron_array
+                .iter()
+                .map(|neuron| {
+                    VoxelInstance::fired(
+                        neuron.neuron_voxel_coordinate.x,
+                        neuron.neuron_voxel_coordinate.y,
+                        neuron.neuron_voxel_coordinate.z,
+                        neuron.potential,
+                    )
+                })
+                .collect();
+            let share = if process_count < total_neurons {
+                (process_count * voxels.len() / total_neurons).max(1)
+            } else {
+                0
+            };
+            let (level, instances) = self.lod.apply(&cortical_id_str, voxels, share);
+            let kept = instances.len().min(remaining);
+
+            let area_color = self.color_settings.area_color(&cortical_id_str);
+            let instance = self.instance_builder(dimensions, area_color);
+            for voxel in instances[..kept].iter() {
+                let (transform, color) = instance(voxel);
+                transforms.extend_from_slice(&transform);
+                colors.extend_from_slice(&color);
+            }
+            remaining -= kept;
+
+            if kept < instances.len() {
+                // Count the neurons whose super-voxel did not make it
+                let factor = 1u32 << level;
+                let kept_cells: std::collections::HashSet<(u32, u32, u32)> =
+                    instances[..kept].iter().map(|v| (v.x, v.y, v.z)).collect();
+                let dropped = neuron_array
+                    .iter()
+                    .filter(|neuron| {
+                        let c = &neuron.neuron_voxel_coordinate;
+                        !kept_cells.contains(&(
+                            c.x / factor * factor,
+                            c.y / factor * factor,
+                            c.z / factor * factor,
+                        ))
+                    })
+                    .count();
+                dropped_per_area.set(cortical_id_str.as_str(), dropped as i32);
+                *dropped_total += dropped;
+            }
+        }
+        (transforms, colors)
     }
 
     /// Convert official neuron data structure to Godot Dictionary
@@ -1509,10 +3527,11 @@ impl FeagiDataDeserializer {
     ) -> Dictionary {
         let mut result_dict = Dictionary::new();
         result_dict.set("success", true);
-        result_dict.set("error", "");
+        error::set_error(&mut result_dict, None);
 
         let mut areas_dict = Dictionary::new();
         let mut total_neurons: i32 = 0;
+        let mut area_stats_dict = Dictionary::new();
 
         // Iterate through each cortical area in the neuron data using 'mappings' field
         for (cortical_id, neuron_array) in neuron_data.mappings.iter() {
@@ -1539,11 +3558,26 @@ impl FeagiDataDeserializer {
             let mut p_array = PackedFloat32Array::new();
 
             // Use the iterator to access neurons
+            let mut area_stats = AreaStats::default();
             for neuron in neuron_array.iter() {
                 x_array.push(neuron.neuron_voxel_coordinate.x as i32);
                 y_array.push(neuron.neuron_voxel_coordinate.y as i32);
                 z_array.push(neuron.neuron_voxel_coordinate.z as i32);
                 p_array.push(neuron.potential);
+                if self.stats.enabled {
+                    area_stats.push(
+                        neuron.neuron_voxel_coordinate.x,
+                        neuron.neuron_voxel_coordinate.y,
+                        neuron.neuron_voxel_coordinate.z,
+                        neuron.potential,
+                    );
+                }
+            }
+            if self.stats.enabled {
+                area_stats_dict.set(
+                    cortical_id_str.as_str(),
+                    area_stats.to_dictionary(self.stats.dimensions(&cortical_id_str)),
+                );
             }
 
             area_dict.set("x_array", x_array);
@@ -1556,29 +3590,35 @@ impl FeagiDataDeserializer {
 
         result_dict.set("areas", areas_dict);
         result_dict.set("total_neurons", total_neurons);
+        if self.stats.enabled {
+            result_dict.set("area_stats", area_stats_dict);
+        }
 
         result_dict
     }
 
     /// Create error dictionary
-    fn create_error_dict(&self, error_msg: String) -> Dictionary {
+    fn create_error_dict(&self, error: DecodeError) -> Dictionary {
         let mut error_dict = Dictionary::new();
         error_dict.set("success", false);
-        error_dict.set("error", error_msg);
+        error::set_error(&mut error_dict, Some(&error));
         error_dict.set("areas", Dictionary::new());
         error_dict.set("total_neurons", 0);
+        error_dict.set("structure_count", 0);
+        error_dict.set("neuron_structure_count", 0);
+        error_dict.set("unknown_structures", Array::<Dictionary>::new());
         error_dict
     }
 
     /// Create error dictionary for visualization processing
     fn create_visualization_error_dict(
         &self,
-        error_msg: String,
+        error: DecodeError,
         processing_time_us: i64,
     ) -> Dictionary {
         let mut error_dict = Dictionary::new();
         error_dict.set("success", false);
-        error_dict.set("error", error_msg);
+        error::set_error(&mut error_dict, Some(&error));
         error_dict.set("transforms", PackedFloat32Array::new());
         error_dict.set("colors", PackedFloat32Array::new());
         error_dict.set("neuron_count", 0);
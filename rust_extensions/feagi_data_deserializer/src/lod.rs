@@ -0,0 +1,168 @@
+//! Spatial level of detail for large cortical areas.
+//!
+//! Instead of dropping neurons once a limit is reached, active voxels are aggregated into
+//! super-voxels: level 1 merges 2x2x2 voxels, level 2 merges 4x4x4, and so on. Every
+//! super-voxel is drawn once, centered on its cell, with its neuron count or mean potential
+//! mapped to size and color. The level is chosen per area from an instance budget and an
+//! optional camera distance hint.
+
+use crate::multimesh_buffer::VoxelInstance;
+use std::collections::HashMap;
+
+/// Smallest drawn size of a super-voxel when size follows density
+const MIN_DENSITY_SIZE: f32 = 0.25;
+
+/// What a super-voxel's `potential` (and therefore its color) represents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum LodValue {
+    /// Mean potential of the aggregated neurons
+    MeanPotential,
+    /// Fraction of the cell's voxels that are active, in (0, 1]
+    Density,
+}
+
+impl LodValue {
+    pub fn from_i32(value: i32) -> Option<Self> {
+        match value {
+            0 => Some(LodValue::MeanPotential),
+            1 => Some(LodValue::Density),
+            _ => None,
+        }
+    }
+
+    pub fn as_i32(self) -> i32 {
+        match self {
+            LodValue::MeanPotential => 0,
+            LodValue::Density => 1,
+        }
+    }
+}
+
+/// LOD configuration of a deserializer.
+#[derive(Clone, Debug)]
+pub(crate) struct LodSettings {
+    pub enabled: bool,
+    /// Maximum instances per area (0 = no budget)
+    pub budget_per_area: usize,
+    /// Highest level that may be selected (cell edge 2^max_level)
+    pub max_level: u32,
+    /// Camera distance at which level i+1 starts, ascending
+    pub distance_thresholds: Vec<f32>,
+    /// Latest camera distance hint per cortical ID
+    pub area_distances: HashMap<String, f32>,
+    pub value: LodValue,
+    /// Scale super-voxels by the cube root of their density
+    pub size_by_density: bool,
+}
+
+impl Default for LodSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            budget_per_area: 0,
+            max_level: 3,
+            distance_thresholds: Vec::new(),
+            area_distances: HashMap::new(),
+            value: LodValue::MeanPotential,
+            size_by_density: true,
+        }
+    }
+}
+
+impl LodSettings {
+    /// Level required by the camera distance hint of an area.
+    fn distance_level(&self, cortical_id: &str) -> u32 {
+        match self.area_distances.get(cortical_id) {
+            Some(&distance) => self
+                .distance_thresholds
+                .iter()
+                .take_while(|&&threshold| distance >= threshold)
+                .count() as u32,
+            None => 0,
+        }
+    }
+
+    /// Aggregate an area's voxels at the coarsest level required by the distance hint and the
+    /// budget (`budget` overrides `budget_per_area` when non-zero).
+    ///
+    /// Returns (level, instances); level 0 returns the input unchanged. The result may exceed
+    /// the budget when even `max_level` is not coarse enough.
+    pub fn apply(
+        &self,
+        cortical_id: &str,
+        voxels: Vec<VoxelInstance>,
+        budget: usize,
+    ) -> (u32, Vec<VoxelInstance>) {
+        let budget = if budget > 0 {
+            budget
+        } else {
+            self.budget_per_area
+        };
+        let mut level = self.distance_level(cortical_id).min(self.max_level);
+        let mut instances = if level > 0 {
+            self.aggregate(&voxels, level)
+        } else {
+            voxels.clone()
+        };
+        while budget > 0 && instances.len() > budget && level < self.max_level {
+            level += 1;
+            instances = self.aggregate(&voxels, level);
+        }
+        (level, instances)
+    }
+
+    /// Merge voxels into cells of edge 2^level.
+    pub fn aggregate(&self, voxels: &[VoxelInstance], level: u32) -> Vec<VoxelInstance> {
+        let factor = 1u32 << level.min(31);
+        let cell_volume = (factor as f32).powi(3);
+
+        // cell -> (count, potential sum, max intensity)
+        let mut cells: HashMap<(u32, u32, u32), (u32, f32, f32)> = HashMap::new();
+        for voxel in voxels {
+            let cell = cells
+                .entry((voxel.x / factor, voxel.y / factor, voxel.z / factor))
+                .or_insert((0, 0.0, 0.0));
+            cell.0 += 1;
+            cell.1 += voxel.potential;
+            cell.2 = cell.2.max(voxel.intensity);
+        }
+
+        cells
+            .into_iter()
+            .map(|((cx, cy, cz), (count, potential_sum, intensity))| {
+                let density = (count as f32 / cell_volume).min(1.0);
+                VoxelInstance {
+                    x: cx * factor,
+                    y: cy * factor,
+                    z: cz * factor,
+                    potential: match self.value {
+                        LodValue::MeanPotential => potential_sum / count as f32,
+                        LodValue::Density => density,
+                    },
+                    intensity,
+                    extent: factor,
+                    size: if self.size_by_density {
+                        density.cbrt().max(MIN_DENSITY_SIZE)
+                    } else {
+                        1.0
+                    },
+                }
+            })
+            .collect()
+    }
+}
+
+/// Turn a single-voxel transform (row-major 3x4, basis scale per axis in `scale`) into the
+/// transform of a super-voxel: the origin moves to the center of the cell and the basis grows
+/// to `extent * size` voxels.
+#[inline]
+pub(crate) fn resize_cell(transform: &mut [f32; 12], extent: u32, size: f32, scale: [f32; 3]) {
+    let shift = (extent as f32 - 1.0) / 2.0;
+    let grow = extent as f32 * size;
+    transform[3] += shift * scale[0];
+    transform[7] += shift * scale[1];
+    transform[11] += shift * scale[2];
+    transform[0] *= grow;
+    transform[5] *= grow;
+    transform[10] *= grow;
+}
@@ -0,0 +1,167 @@
+//! Packed MultiMesh instance buffers.
+//!
+//! Calling `set_instance_transform` / `set_instance_color` costs two FFI round trips per
+//! neuron. Building the whole instance buffer in Rust and handing it to Godot with a single
+//! `MultiMesh::set_buffer` call keeps the per-area cost to one copy.
+
+use godot::classes::multi_mesh::TransformFormat;
+use godot::classes::MultiMesh;
+use godot::prelude::*;
+
+// Rayon is only available on native platforms (not WASM)
+#[cfg(not(target_family = "wasm"))]
+use rayon::prelude::*;
+
+/// How instance data is handed to a MultiMesh.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum UploadMode {
+    /// Build the packed buffer in Rust, upload with one `set_buffer` call per area
+    Bulk,
+    /// Legacy path: one `set_instance_transform` + `set_instance_color` call per neuron
+    PerInstance,
+}
+
+impl UploadMode {
+    pub fn from_i32(mode: i32) -> Option<Self> {
+        match mode {
+            0 => Some(UploadMode::Bulk),
+            1 => Some(UploadMode::PerInstance),
+            _ => None,
+        }
+    }
+
+    pub fn as_i32(self) -> i32 {
+        match self {
+            UploadMode::Bulk => 0,
+            UploadMode::PerInstance => 1,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UploadMode::Bulk => "bulk",
+            UploadMode::PerInstance => "per_instance",
+        }
+    }
+}
+
+/// One neuron instance to be drawn into an area's MultiMesh.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct VoxelInstance {
+    pub x: u32,
+    pub y: u32,
+    pub z: u32,
+    pub potential: f32,
+    /// Visibility in [0, 1]; 1.0 for neurons that fired in the current burst
+    pub intensity: f32,
+    /// Voxels covered along each axis (1 for a single voxel, >1 for an LOD super-voxel whose
+    /// min corner is x/y/z)
+    pub extent: u32,
+    /// Drawn size relative to `extent` (1.0 = fills its cell)
+    pub size: f32,
+}
+
+impl VoxelInstance {
+    /// A neuron that fired in the current burst.
+    #[inline]
+    pub fn fired(x: u32, y: u32, z: u32, potential: f32) -> Self {
+        Self {
+            x,
+            y,
+            z,
+            potential,
+            intensity: 1.0,
+            extent: 1,
+            size: 1.0,
+        }
+    }
+}
+
+/// Float layout of one instance inside a MultiMesh buffer.
+///
+/// Godot stores each 3D instance as a row-major 3x4 transform (12 floats), followed by
+/// RGBA color (4 floats) when `use_colors` is set and custom data (4 floats) when
+/// `use_custom_data` is set.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct BufferLayout {
+    pub stride: usize,
+    pub has_colors: bool,
+}
+
+impl BufferLayout {
+    /// Layout for a MultiMesh, or `None` when it does not use 3D transforms.
+    pub fn of(multi_mesh: &Gd<MultiMesh>) -> Option<Self> {
+        if multi_mesh.get_transform_format() != TransformFormat::TRANSFORM_3D {
+            return None;
+        }
+        let has_colors = multi_mesh.is_using_colors();
+        let mut stride = 12;
+        if has_colors {
+            stride += 4;
+        }
+        if multi_mesh.is_using_custom_data() {
+            stride += 4;
+        }
+        Some(Self { stride, has_colors })
+    }
+
+    /// Write one instance into its slot (custom data, if any, is left zeroed).
+    #[inline(always)]
+    fn write(&self, slot: &mut [f32], transform: &[f32; 12], color: &[f32; 4]) {
+        slot[..12].copy_from_slice(transform);
+        if self.has_colors {
+            slot[12..16].copy_from_slice(color);
+        }
+    }
+}
+
+/// Build a packed instance buffer - DESKTOP VERSION with Rayon parallel processing
+#[cfg(not(target_family = "wasm"))]
+pub(crate) fn build_instance_buffer<T, F>(
+    items: &[T],
+    layout: BufferLayout,
+    instance: F,
+) -> Vec<f32>
+where
+    T: Sync,
+    F: Fn(&T) -> ([f32; 12], [f32; 4]) + Sync + Send,
+{
+    let mut buffer = vec![0.0f32; items.len() * layout.stride];
+    buffer
+        .par_chunks_mut(layout.stride)
+        .zip(items.par_iter())
+        .for_each(|(slot, item)| {
+            let (transform, color) = instance(item);
+            layout.write(slot, &transform, &color);
+        });
+    buffer
+}
+
+/// Build a packed instance buffer - WASM VERSION with sequential processing
+#[cfg(target_family = "wasm")]
+pub(crate) fn build_instance_buffer<T, F>(
+    items: &[T],
+    layout: BufferLayout,
+    instance: F,
+) -> Vec<f32>
+where
+    F: Fn(&T) -> ([f32; 12], [f32; 4]),
+{
+    let mut buffer = vec![0.0f32; items.len() * layout.stride];
+    for (slot, item) in buffer.chunks_exact_mut(layout.stride).zip(items.iter()) {
+        let (transform, color) = instance(item);
+        layout.write(slot, &transform, &color);
+    }
+    buffer
+}
+
+/// Convert a row-major 3x4 transform (as produced by `AreaSpace::instance_transform`) to a Godot Transform3D.
+#[inline(always)]
+pub(crate) fn transform_from_rows(t: &[f32; 12]) -> Transform3D {
+    let basis = Basis::from_rows(
+        Vector3::new(t[0], t[1], t[2]),
+        Vector3::new(t[4], t[5], t[6]),
+        Vector3::new(t[8], t[9], t[10]),
+    );
+    Transform3D::new(basis, Vector3::new(t[3], t[7], t[11]))
+}
@@ -0,0 +1,603 @@
+//! Neuron picking for hover and inspection.
+//!
+//! While the MultiMesh apply path draws an area, the drawn voxels are recorded in a sparse
+//! per-area index (voxel cell -> instance). Picks undo the same `AreaSpace` math the instance
+//! transforms are built with, so they stay in sync with what is on screen:
+//!
+//! - a world ray is moved into each area's voxel grid and walked cell by cell (3D DDA) until
+//!   it meets a rendered voxel; the cost depends on the area size, not the neuron count
+//! - a world point looks for the nearest rendered voxel within a radius
+//!
+//! Rays and points are walked in cell space (see `coordinates`): voxel (x, y, z) covers
+//! [x, x+1) x [y, y+1) x [z, z+1) and the area covers [0, dimensions).
+
+use crate::coordinates::AreaSpace;
+use crate::multimesh_buffer::VoxelInstance;
+use godot::prelude::{Transform3D, Vector3};
+use std::collections::HashMap;
+
+type VoxelKey = (u32, u32, u32);
+
+/// One rendered instance.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct PickEntry {
+    /// Voxel coordinate as drawn (clamped to the area; the cell corner for super-voxels)
+    pub x: u32,
+    pub y: u32,
+    pub z: u32,
+    pub potential: f32,
+    /// MultiMesh instance index
+    pub instance: u32,
+    /// Cell edge in voxels (1 unless drawn as an LOD super-voxel)
+    pub extent: u32,
+}
+
+/// Result of a pick.
+pub(crate) struct PickHit {
+    pub cortical_id: String,
+    pub entry: PickEntry,
+    /// World distance from the ray origin to the entry point, or from the query point to the
+    /// voxel center
+    pub distance: f32,
+    /// World position of the hit (ray entry point, or voxel center for point picks)
+    pub position: Vector3,
+}
+
+/// Rendered instances of one area.
+struct AreaPickIndex {
+    dimensions: Vector3,
+    /// Largest cell edge among the entries
+    extent: u32,
+    cells: HashMap<VoxelKey, PickEntry>,
+}
+
+impl AreaPickIndex {
+    /// Entry drawn over voxel `(x, y, z)`.
+    fn at(&self, x: u32, y: u32, z: u32) -> Option<&PickEntry> {
+        if self.extent > 1 {
+            let e = self.extent;
+            if let Some(entry) = self.cells.get(&(x / e * e, y / e * e, z / e * e)) {
+                return Some(entry);
+            }
+        }
+        self.cells.get(&(x, y, z))
+    }
+
+    /// Voxel-space position of an area-local (MultiMesh) position.
+    fn to_voxel(&self, local: Vector3) -> [f32; 3] {
+        AreaSpace::new(self.dimensions).local_to_cell(local)
+    }
+
+    /// Area-local (MultiMesh) position of a voxel-space position.
+    fn to_local(&self, voxel: [f32; 3]) -> Vector3 {
+        AreaSpace::new(self.dimensions).cell_to_local(voxel)
+    }
+
+    /// Voxel-space center of an entry's cell.
+    fn center(entry: &PickEntry) -> [f32; 3] {
+        let half = entry.extent as f32 / 2.0;
+        [
+            entry.x as f32 + half,
+            entry.y as f32 + half,
+            entry.z as f32 + half,
+        ]
+    }
+
+    /// First rendered voxel along `origin + t * direction`, t in [0, max_t].
+    ///
+    /// None for non-finite input or a direction that does not move through the grid (every
+    /// step would be 0, so the walk could not end).
+    fn ray(&self, origin: [f32; 3], direction: [f32; 3], max_t: f32) -> Option<(PickEntry, f32)> {
+        if !origin.iter().chain(&direction).all(|v| v.is_finite())
+            || direction.iter().all(|v| v.abs() <= f32::EPSILON)
+        {
+            return None;
+        }
+        let n = [
+            self.dimensions.x.ceil().max(1.0),
+            self.dimensions.y.ceil().max(1.0),
+            self.dimensions.z.ceil().max(1.0),
+        ];
+
+        // Clip the ray to the area box
+        let mut t_enter = 0.0f32;
+        let mut t_exit = max_t;
+        for a in 0..3 {
+            if direction[a].abs() < f32::EPSILON {
+                if origin[a] < 0.0 || origin[a] >= n[a] {
+                    return None;
+                }
+            } else {
+                let t0 = -origin[a] / direction[a];
+                let t1 = (n[a] - origin[a]) / direction[a];
+                t_enter = t_enter.max(t0.min(t1));
+                t_exit = t_exit.min(t0.max(t1));
+            }
+        }
+        if !(t_enter <= t_exit) {
+            return None;
+        }
+
+        let mut cell = [0i64; 3];
+        let mut step = [0i64; 3];
+        let mut t_next = [f32::INFINITY; 3];
+        let mut t_delta = [f32::INFINITY; 3];
+        for a in 0..3 {
+            let p = origin[a] + direction[a] * t_enter;
+            cell[a] = (p.floor() as i64).clamp(0, n[a] as i64 - 1);
+            if direction[a] > f32::EPSILON {
+                step[a] = 1;
+                t_next[a] = ((cell[a] + 1) as f32 - origin[a]) / direction[a];
+                t_delta[a] = 1.0 / direction[a];
+            } else if direction[a] < -f32::EPSILON {
+                step[a] = -1;
+                t_next[a] = (cell[a] as f32 - origin[a]) / direction[a];
+                t_delta[a] = -1.0 / direction[a];
+            }
+        }
+
+        let mut t = t_enter;
+        loop {
+            if let Some(entry) = self.at(cell[0] as u32, cell[1] as u32, cell[2] as u32) {
+                return Some((*entry, t));
+            }
+            let axis = if t_next[0] <= t_next[1] && t_next[0] <= t_next[2] {
+                0
+            } else if t_next[1] <= t_next[2] {
+                1
+            } else {
+                2
+            };
+            t = t_next[axis];
+            if !t.is_finite() || t > t_exit {
+                return None;
+            }
+            cell[axis] += step[axis];
+            if cell[axis] < 0 || cell[axis] >= n[axis] as i64 {
+                return None;
+            }
+            t_next[axis] += t_delta[axis];
+        }
+    }
+}
+
+/// Picking state of a deserializer: the index of rendered instances and the world transform
+/// of every area's MultiMeshInstance3D.
+#[derive(Default)]
+pub(crate) struct PickIndex {
+    pub enabled: bool,
+    areas: HashMap<String, AreaPickIndex>,
+    /// Global transform of each area's MultiMeshInstance3D
+    transforms: HashMap<String, Transform3D>,
+    /// Transforms derived from area placements, used for areas without a registered transform
+    placed: HashMap<String, Transform3D>,
+}
+
+impl PickIndex {
+    /// Replace an area's entries with the instances just drawn (`drawn[i]` is instance i).
+    ///
+    /// Coordinates are clamped to the area exactly like the instance transforms.
+    pub fn record_area(&mut self, cortical_id: &str, dimensions: Vector3, drawn: &[VoxelInstance]) {
+        let max_x = (dimensions.x as u32).saturating_sub(1);
+        let max_y = (dimensions.y as u32).saturating_sub(1);
+        let max_z = (dimensions.z as u32).saturating_sub(1);
+
+        let mut cells = HashMap::with_capacity(drawn.len());
+        let mut extent = 1;
+        for (instance, voxel) in drawn.iter().enumerate() {
+            let entry = PickEntry {
+                x: voxel.x.min(max_x),
+                y: voxel.y.min(max_y),
+                z: voxel.z.min(max_z),
+                potential: voxel.potential,
+                instance: instance as u32,
+                extent: voxel.extent.max(1),
+            };
+            extent = extent.max(entry.extent);
+            cells.insert((entry.x, entry.y, entry.z), entry);
+        }
+        self.areas.insert(
+            cortical_id.to_string(),
+            AreaPickIndex {
+                dimensions,
+                extent,
+                cells,
+            },
+        );
+    }
+
+    /// Forget all recorded instances.
+    pub fn clear_areas(&mut self) {
+        self.areas.clear();
+    }
+
+    pub fn set_transform(&mut self, cortical_id: &str, transform: Transform3D) {
+        self.transforms.insert(cortical_id.to_string(), transform);
+    }
+
+    pub fn clear_transforms(&mut self) {
+        self.transforms.clear();
+    }
+
+    /// Transform of an area's placement (`AreaSpace::local_to_world`); None removes it.
+    pub fn set_placed_transform(&mut self, cortical_id: &str, transform: Option<Transform3D>) {
+        match transform {
+            Some(t) => self.placed.insert(cortical_id.to_string(), t),
+            None => self.placed.remove(cortical_id),
+        };
+    }
+
+    /// Number of indexed instances over all areas.
+    pub fn instance_count(&self) -> usize {
+        self.areas.values().map(|a| a.cells.len()).sum()
+    }
+
+    pub fn area_count(&self) -> usize {
+        self.areas.len()
+    }
+
+    /// Whether a transform can be inverted (zero, huge or NaN scales cannot)
+    fn invertible(transform: &Transform3D) -> bool {
+        let determinant = transform.basis.determinant();
+        determinant.is_finite() && determinant.abs() > f32::EPSILON && transform.origin.is_finite()
+    }
+
+    fn transform_of(&self, cortical_id: &str) -> Transform3D {
+        self.transforms
+            .get(cortical_id)
+            .or_else(|| self.placed.get(cortical_id))
+            .copied()
+            .unwrap_or(Transform3D::IDENTITY)
+    }
+
+    /// Rendered instance at a voxel of an area.
+    pub fn lookup(&self, cortical_id: &str, x: u32, y: u32, z: u32) -> Option<PickEntry> {
+        self.areas.get(cortical_id)?.at(x, y, z).copied()
+    }
+
+    /// Closest rendered voxel hit by a world-space ray (max_distance <= 0 = unlimited).
+    pub fn pick_ray(
+        &self,
+        origin: Vector3,
+        direction: Vector3,
+        max_distance: f32,
+    ) -> Option<PickHit> {
+        let length = direction.length();
+        if !(length > f32::EPSILON) || !length.is_finite() {
+            return None;
+        }
+        let direction = direction / length;
+        let mut max_t = if max_distance > 0.0 {
+            max_distance
+        } else {
+            f32::INFINITY
+        };
+
+        let mut best: Option<PickHit> = None;
+        for (cortical_id, area) in &self.areas {
+            if area.cells.is_empty() {
+                continue;
+            }
+            let transform = self.transform_of(cortical_id);
+            if !Self::invertible(&transform) {
+                continue;
+            }
+            let inverse = transform.affine_inverse();
+            let local_origin = inverse * origin;
+            let local_direction = inverse.basis * direction;
+            let d = area.dimensions;
+            let voxel_direction = [
+                local_direction.x * d.x,
+                local_direction.y * d.y,
+                -local_direction.z * d.z,
+            ];
+            // The mapping is affine, so t stays the world distance along the normalized ray
+            if let Some((entry, t)) = area.ray(area.to_voxel(local_origin), voxel_direction, max_t)
+            {
+                max_t = t;
+                best = Some(PickHit {
+                    cortical_id: cortical_id.clone(),
+                    entry,
+                    distance: t,
+                    position: origin + direction * t,
+                });
+            }
+        }
+        best
+    }
+
+    /// Rendered voxel whose center is nearest to a world-space point, within max_distance
+    /// (<= 0 = unlimited).
+    pub fn pick_point(&self, point: Vector3, max_distance: f32) -> Option<PickHit> {
+        let mut best_distance = if max_distance > 0.0 {
+            max_distance
+        } else {
+            f32::INFINITY
+        };
+
+        let mut best: Option<PickHit> = None;
+        for (cortical_id, area) in &self.areas {
+            if area.cells.is_empty() {
+                continue;
+            }
+            let transform = self.transform_of(cortical_id);
+            if !Self::invertible(&transform) {
+                continue;
+            }
+            // Search the voxel box around the point when it is smaller than the area's entry
+            // list, otherwise scan the entries.
+            let d = area.dimensions;
+            let voxel_lengths = [
+                (transform.basis * Vector3::new(1.0 / d.x, 0.0, 0.0)).length(),
+                (transform.basis * Vector3::new(0.0, 1.0 / d.y, 0.0)).length(),
+                (transform.basis * Vector3::new(0.0, 0.0, 1.0 / d.z)).length(),
+            ];
+            let center = area.to_voxel(transform.affine_inverse() * point);
+            let mut lo = [0u32; 3];
+            let mut hi = [0u32; 3];
+            let mut box_cells = 1.0f64;
+            let limits = [d.x, d.y, d.z];
+            for a in 0..3 {
+                let reach = best_distance / voxel_lengths[a].max(f32::EPSILON) + area.extent as f32;
+                let from = (center[a] - reach).floor().max(0.0);
+                let to = (center[a] + reach).ceil().min(limits[a] - 1.0);
+                if !(from <= to) {
+                    box_cells = 0.0;
+                    break;
+                }
+                lo[a] = from as u32;
+                hi[a] = to as u32;
+                box_cells *= (to - from + 1.0) as f64;
+            }
+            if box_cells == 0.0 {
+                continue;
+            }
+
+            let mut consider = |entry: &PickEntry| {
+                let world = transform * area.to_local(AreaPickIndex::center(entry));
+                let distance = world.distance_to(point);
+                if distance <= best_distance {
+                    best_distance = distance;
+                    best = Some(PickHit {
+                        cortical_id: cortical_id.clone(),
+                        entry: *entry,
+                        distance,
+                        position: world,
+                    });
+                }
+            };
+            if box_cells <= area.cells.len() as f64 {
+                let e = area.extent;
+                let align = |v: u32| if e > 1 { v / e * e } else { v };
+                let mut x = align(lo[0]);
+                while x <= hi[0] {
+                    let mut y = align(lo[1]);
+                    while y <= hi[1] {
+                        let mut z = align(lo[2]);
+                        while z <= hi[2] {
+                            if let Some(entry) = area.cells.get(&(x, y, z)) {
+                                consider(entry);
+                            }
+                            z += e;
+                        }
+                        y += e;
+                    }
+                    x += e;
+                }
+            } else {
+                area.cells.values().for_each(&mut consider);
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use godot::prelude::Basis;
+
+    fn voxel(x: u32, y: u32, z: u32, extent: u32) -> VoxelInstance {
+        VoxelInstance {
+            x,
+            y,
+            z,
+            potential: 0.5,
+            intensity: 1.0,
+            extent,
+            size: extent as f32,
+        }
+    }
+
+    fn assert_near(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 1e-4,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    fn assert_near_vec(actual: Vector3, expected: Vector3) {
+        assert!(
+            actual.distance_to(expected) < 1e-4,
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    /// 4x4x4 area rotated 90 degrees about Y, scaled by 8 and moved to (10, 0, 0):
+    /// local x -> world -z, local y -> world y, local z -> world x; one voxel is 2 world units.
+    fn transformed_index() -> PickIndex {
+        let mut index = PickIndex::default();
+        let drawn = [voxel(3, 0, 0, 1), voxel(1, 0, 3, 1), voxel(1, 2, 3, 1)];
+        index.record_area("iv00_C", Vector3::new(4.0, 4.0, 4.0), &drawn);
+        let basis = Basis::from_cols(
+            Vector3::new(0.0, 0.0, -8.0),
+            Vector3::new(0.0, 8.0, 0.0),
+            Vector3::new(8.0, 0.0, 0.0),
+        );
+        index.set_transform(
+            "iv00_C",
+            Transform3D::new(basis, Vector3::new(10.0, 0.0, 0.0)),
+        );
+        index
+    }
+
+    /// World center of voxel (1, 2, 3) of `transformed_index`: local (-0.25, 0, -0.25)
+    fn transformed_center() -> Vector3 {
+        Vector3::new(8.0, 0.0, 2.0)
+    }
+
+    fn index_with_one_voxel() -> PickIndex {
+        let mut index = PickIndex::default();
+        let voxel = VoxelInstance {
+            x: 1,
+            y: 1,
+            z: 1,
+            potential: 1.0,
+            intensity: 1.0,
+            extent: 1,
+            size: 1.0,
+        };
+        index.record_area("area", Vector3::new(4.0, 4.0, 4.0), &[voxel]);
+        index
+    }
+
+    #[test]
+    fn ray_without_grid_motion_ends() {
+        let index = index_with_one_voxel();
+        let area = &index.areas["area"];
+        let inside = [1.5, 1.5, 1.5];
+        assert!(area.ray(inside, [0.0; 3], f32::INFINITY).is_none());
+        assert!(area
+            .ray(inside, [1e-9, -1e-9, 0.0], f32::INFINITY)
+            .is_none());
+        assert!(area
+            .ray(inside, [f32::NAN, 1.0, 0.0], f32::INFINITY)
+            .is_none());
+        assert!(area
+            .ray([0.5, 1.5, 1.5], [1.0, 0.0, 0.0], f32::INFINITY)
+            .is_some());
+    }
+
+    #[test]
+    fn non_invertible_transforms_are_skipped() {
+        let mut index = index_with_one_voxel();
+        let origin = Vector3::new(0.0, 0.0, 5.0);
+        let direction = Vector3::new(0.0, 0.0, -1.0);
+        for scale in [0.0, 1e30, f32::NAN] {
+            let transform =
+                Transform3D::new(Basis::from_scale(Vector3::splat(scale)), Vector3::ZERO);
+            index.set_transform("area", transform);
+            assert!(index.pick_ray(origin, direction, 0.0).is_none());
+            assert!(index.pick_point(Vector3::ZERO, 0.0).is_none());
+        }
+    }
+
+    #[test]
+    fn ray_through_transformed_area_hits_the_first_voxel() {
+        let index = transformed_index();
+        // Straight down onto voxel column (1, *, 3); (1, 2, 3) is above (1, 0, 3)
+        let origin = transformed_center() + Vector3::new(0.0, 20.0, 0.0);
+        let direction = Vector3::new(0.0, -3.0, 0.0);
+        let hit = index.pick_ray(origin, direction, 0.0).expect("hit");
+        assert_eq!(hit.cortical_id, "iv00_C");
+        assert_eq!((hit.entry.x, hit.entry.y, hit.entry.z), (1, 2, 3));
+        assert_eq!(hit.entry.instance, 2);
+        // The top face of the voxel is one world unit above its center
+        assert_near(hit.distance, 19.0);
+        assert_near_vec(
+            hit.position,
+            transformed_center() + Vector3::new(0.0, 1.0, 0.0),
+        );
+
+        assert!(index.pick_ray(origin, direction, 18.9).is_none());
+        assert!(index.pick_ray(origin, direction, 19.1).is_some());
+        // One voxel over along world z (local -x) is voxel (0, 2, 3), not rendered
+        let beside = origin + Vector3::new(0.0, 0.0, 2.0);
+        assert!(index.pick_ray(beside, direction, 0.0).is_none());
+    }
+
+    #[test]
+    fn point_in_transformed_area_finds_the_nearest_voxel() {
+        let index = transformed_index();
+        let offset = Vector3::new(0.3, 0.2, -0.1);
+        let hit = index
+            .pick_point(transformed_center() + offset, 1.0)
+            .expect("hit");
+        assert_eq!(hit.cortical_id, "iv00_C");
+        assert_eq!((hit.entry.x, hit.entry.y, hit.entry.z), (1, 2, 3));
+        assert_eq!(hit.entry.instance, 2);
+        assert_near(hit.distance, offset.length());
+        assert_near_vec(hit.position, transformed_center());
+
+        // Voxel (1, 0, 3) is 4 world units below
+        let below = transformed_center() + Vector3::new(0.0, -3.5, 0.0);
+        let hit = index.pick_point(below, 0.0).expect("hit");
+        assert_eq!(hit.entry.instance, 1);
+        assert_near(hit.distance, 0.5);
+        assert!(index.pick_point(below, 0.4).is_none());
+    }
+
+    #[test]
+    fn point_in_dense_area_searches_the_voxel_box() {
+        // A 32x32 sheet of voxels at z = 5: the search box around the point is smaller than
+        // the entry list, so the cells around the point are looked up instead of scanned
+        let mut index = PickIndex::default();
+        let drawn: Vec<VoxelInstance> = (0..32)
+            .flat_map(|x| (0..32).map(move |y| voxel(x, y, 5, 1)))
+            .collect();
+        index.record_area("dense", Vector3::splat(32.0), &drawn);
+
+        // Voxel (10, 20, 5) at local ((10 - 16) / 32, (20 - 16) / 32, -(5 - 16) / 32)
+        let center = Vector3::new(-0.1875, 0.125, 0.34375);
+        let offset = Vector3::new(0.002, -0.003, 0.001);
+        let hit = index.pick_point(center + offset, 0.5 / 32.0).expect("hit");
+        assert_eq!((hit.entry.x, hit.entry.y, hit.entry.z), (10, 20, 5));
+        assert_eq!(hit.entry.instance, 10 * 32 + 20);
+        assert_near(hit.distance, offset.length());
+    }
+
+    #[test]
+    fn lod_super_voxels_cover_their_cells() {
+        let mut index = PickIndex::default();
+        let drawn = [voxel(0, 0, 0, 2), voxel(2, 2, 2, 2)];
+        index.record_area("lod", Vector3::splat(4.0), &drawn);
+
+        let lookup = |x, y, z| {
+            index
+                .lookup("lod", x, y, z)
+                .map(|e| (e.x, e.y, e.z, e.extent, e.instance))
+        };
+        assert_eq!(lookup(0, 0, 0), Some((0, 0, 0, 2, 0)));
+        assert_eq!(lookup(1, 1, 1), Some((0, 0, 0, 2, 0)));
+        assert_eq!(lookup(1, 0, 1), Some((0, 0, 0, 2, 0)));
+        assert_eq!(lookup(3, 2, 3), Some((2, 2, 2, 2, 1)));
+        assert_eq!(lookup(0, 3, 0), None);
+        assert_eq!(lookup(2, 0, 0), None);
+
+        // A ray along +x through cells (*, 1, 1) enters the super-voxel at cell x = 0, local
+        // x = (0 - 0.5 - 2) / 4; cell y = z = 1.5 is local (-0.25, 0.25) in y and z
+        let origin = Vector3::new(-5.0, -0.25, 0.25);
+        let hit = index
+            .pick_ray(origin, Vector3::new(1.0, 0.0, 0.0), 0.0)
+            .expect("hit");
+        assert_eq!(
+            (hit.entry.x, hit.entry.y, hit.entry.z, hit.entry.extent),
+            (0, 0, 0, 2)
+        );
+        assert_eq!(hit.entry.instance, 0);
+        assert_near(hit.distance, 5.0 - 0.625);
+
+        // Point picks measure to the center of the whole super-voxel, cell (1, 1, 1)
+        let center = Vector3::new(-0.375, -0.375, 0.375);
+        let hit = index
+            .pick_point(center + Vector3::new(0.05, 0.0, 0.0), 0.0)
+            .expect("hit");
+        assert_eq!(hit.entry.instance, 0);
+        assert_near(hit.distance, 0.05);
+        assert_near_vec(hit.position, center);
+    }
+}
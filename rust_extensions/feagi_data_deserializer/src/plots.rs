@@ -0,0 +1,283 @@
+//! Raster and heatmap images of selected cortical areas.
+//!
+//! The decode paths feed every burst into a sliding window of fired neuron indices (raster)
+//! and into decaying 2D projections per area (heatmap). Rendering produces RGBA8 pixels that
+//! can be uploaded as an Image directly, so GDScript never loops over voxels.
+//!
+//! Neuron index of voxel (x, y, z) in an area of dimensions (dx, dy, dz) is
+//! `x + y * dx + z * dx * dy`; areas are stacked in selection order.
+
+use crate::color_map::Gradient;
+use feagi_structures::neuron_voxels::xyzp::CorticalMappedXYZPNeuronVoxels;
+use std::collections::{HashMap, VecDeque};
+
+/// Default number of bursts shown by the raster
+const DEFAULT_WINDOW_BURSTS: usize = 200;
+
+/// Largest image edge produced (Godot's Image limit)
+const MAX_IMAGE_EDGE: usize = 16384;
+
+/// 2D projection of an area.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Projection {
+    /// X to the right, Y up
+    XY,
+    /// X to the right, Z down
+    XZ,
+    /// Z to the right, Y up
+    YZ,
+}
+
+impl Projection {
+    pub fn from_i32(projection: i32) -> Option<Self> {
+        match projection {
+            0 => Some(Projection::XY),
+            1 => Some(Projection::XZ),
+            2 => Some(Projection::YZ),
+            _ => None,
+        }
+    }
+
+    /// (width, height) of the projection of an area.
+    fn size(self, d: [u32; 3]) -> (usize, usize) {
+        match self {
+            Projection::XY => (d[0] as usize, d[1] as usize),
+            Projection::XZ => (d[0] as usize, d[2] as usize),
+            Projection::YZ => (d[2] as usize, d[1] as usize),
+        }
+    }
+
+    /// Pixel (column, row) of a voxel.
+    fn pixel(self, d: [u32; 3], x: u32, y: u32, z: u32) -> (usize, usize) {
+        match self {
+            Projection::XY => (x as usize, (d[1] - 1 - y) as usize),
+            Projection::XZ => (x as usize, z as usize),
+            Projection::YZ => (z as usize, (d[1] - 1 - y) as usize),
+        }
+    }
+}
+
+const PROJECTIONS: [Projection; 3] = [Projection::XY, Projection::XZ, Projection::YZ];
+
+/// One selected area.
+struct PlotArea {
+    cortical_id: String,
+    dimensions: [u32; 3],
+    /// Neuron index of the area's first voxel in the raster
+    offset: u64,
+    /// Accumulated activity per projection (row-major, same order as PROJECTIONS)
+    heatmaps: [Vec<f32>; 3],
+}
+
+/// An RGBA8 image.
+pub(crate) struct Rgba8Image {
+    pub width: usize,
+    pub height: usize,
+    pub data: Vec<u8>,
+}
+
+/// Plot state of a deserializer.
+pub(crate) struct ActivityPlots {
+    areas: Vec<PlotArea>,
+    area_index: HashMap<String, usize>,
+    window_bursts: usize,
+    /// Fired neuron indices per burst, oldest first
+    raster: VecDeque<Vec<u64>>,
+    /// Fraction of the heatmap kept per burst (0 = latest burst only)
+    pub heatmap_decay: f32,
+}
+
+impl Default for ActivityPlots {
+    fn default() -> Self {
+        Self {
+            areas: Vec::new(),
+            area_index: HashMap::new(),
+            window_bursts: DEFAULT_WINDOW_BURSTS,
+            raster: VecDeque::new(),
+            heatmap_decay: 0.0,
+        }
+    }
+}
+
+impl ActivityPlots {
+    /// Select the areas to plot (cortical ID and integer dimensions) and the raster window.
+    /// Clears all accumulated data.
+    pub fn configure(&mut self, areas: Vec<(String, [u32; 3])>, window_bursts: usize) {
+        self.areas.clear();
+        self.area_index.clear();
+        self.raster.clear();
+        self.window_bursts = window_bursts.max(1);
+
+        let mut offset = 0u64;
+        for (cortical_id, dimensions) in areas {
+            if self.area_index.contains_key(&cortical_id) {
+                continue;
+            }
+            let heatmaps = PROJECTIONS.map(|p| {
+                let (w, h) = p.size(dimensions);
+                vec![0.0f32; w * h]
+            });
+            self.area_index
+                .insert(cortical_id.clone(), self.areas.len());
+            self.areas.push(PlotArea {
+                cortical_id,
+                dimensions,
+                offset,
+                heatmaps,
+            });
+            offset += dimensions.iter().map(|&d| d as u64).product::<u64>();
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.areas.is_empty()
+    }
+
+    pub fn window_bursts(&self) -> usize {
+        self.window_bursts
+    }
+
+    pub fn burst_count(&self) -> usize {
+        self.raster.len()
+    }
+
+    /// Total neurons of the selected areas (raster rows before downsampling).
+    pub fn neuron_count(&self) -> u64 {
+        self.areas
+            .last()
+            .map(|a| a.offset + a.dimensions.iter().map(|&d| d as u64).product::<u64>())
+            .unwrap_or(0)
+    }
+
+    /// Add one burst.
+    pub fn record(&mut self, neuron_data: &CorticalMappedXYZPNeuronVoxels) {
+        let keep = self.heatmap_decay.clamp(0.0, 1.0);
+        for area in &mut self.areas {
+            for heatmap in &mut area.heatmaps {
+                heatmap.iter_mut().for_each(|v| *v *= keep);
+            }
+        }
+
+        let mut fired: Vec<u64> = Vec::new();
+        for (cortical_id, neuron_array) in neuron_data.mappings.iter() {
+            let area = match self.area_index.get(&cortical_id.as_base_64()) {
+                Some(&i) => &mut self.areas[i],
+                None => continue,
+            };
+            let d = area.dimensions;
+            for neuron in neuron_array.iter() {
+                let c = &neuron.neuron_voxel_coordinate;
+                if c.x >= d[0] || c.y >= d[1] || c.z >= d[2] {
+                    continue;
+                }
+                fired.push(
+                    area.offset
+                        + c.x as u64
+                        + c.y as u64 * d[0] as u64
+                        + c.z as u64 * d[0] as u64 * d[1] as u64,
+                );
+                for (p, heatmap) in PROJECTIONS.iter().zip(area.heatmaps.iter_mut()) {
+                    let (w, _) = p.size(d);
+                    let (col, row) = p.pixel(d, c.x, c.y, c.z);
+                    heatmap[row * w + col] += 1.0;
+                }
+            }
+        }
+
+        self.raster.push_back(fired);
+        while self.raster.len() > self.window_bursts {
+            self.raster.pop_front();
+        }
+    }
+
+    /// Raster image: one column per burst of the window (newest on the right), one row per
+    /// neuron or, when `max_rows` is smaller than the neuron count, per group of neurons.
+    ///
+    /// Spikes are drawn in the color of their area; the background is transparent.
+    pub fn render_raster<F>(&self, max_rows: usize, area_color: F) -> Result<Rgba8Image, String>
+    where
+        F: Fn(&str) -> [f32; 4],
+    {
+        let neurons = self.neuron_count();
+        if neurons == 0 {
+            return Err("No plot areas selected".to_string());
+        }
+        let limit = if max_rows > 0 {
+            max_rows.min(MAX_IMAGE_EDGE)
+        } else {
+            MAX_IMAGE_EDGE
+        };
+        let height = (neurons.min(limit as u64)) as usize;
+        let width = self.window_bursts.min(MAX_IMAGE_EDGE);
+        let colors: Vec<[u8; 4]> = self
+            .areas
+            .iter()
+            .map(|a| to_rgba8(area_color(&a.cortical_id)))
+            .collect();
+
+        let mut data = vec![0u8; width * height * 4];
+        let first_column = width - self.raster.len().min(width);
+        let skipped = self.raster.len().saturating_sub(width);
+        for (column, fired) in self.raster.iter().skip(skipped).enumerate() {
+            let x = first_column + column;
+            for &index in fired {
+                let row = (index as u128 * height as u128 / neurons as u128) as usize;
+                let area = self.areas.partition_point(|a| a.offset <= index) - 1;
+                let at = (row * width + x) * 4;
+                data[at..at + 4].copy_from_slice(&colors[area]);
+            }
+        }
+        Ok(Rgba8Image {
+            width,
+            height,
+            data,
+        })
+    }
+
+    /// Heatmap image of one area: activity summed along the projected axis (with decay over
+    /// bursts), normalized to the maximum and colored with `gradient`. Pixels without
+    /// activity are transparent.
+    pub fn render_heatmap(
+        &self,
+        cortical_id: &str,
+        projection: Projection,
+        gradient: &Gradient,
+    ) -> Result<Rgba8Image, String> {
+        let area = match self.area_index.get(cortical_id) {
+            Some(&i) => &self.areas[i],
+            None => return Err(format!("Area {} is not selected for plots", cortical_id)),
+        };
+        let (width, height) = projection.size(area.dimensions);
+        if width > MAX_IMAGE_EDGE || height > MAX_IMAGE_EDGE {
+            return Err(format!(
+                "Projection {}x{} exceeds the {} pixel image limit",
+                width, height, MAX_IMAGE_EDGE
+            ));
+        }
+        let heatmap = &area.heatmaps[PROJECTIONS
+            .iter()
+            .position(|&p| p == projection)
+            .unwrap_or(0)];
+        let max = heatmap.iter().copied().fold(0.0f32, f32::max);
+
+        let mut data = vec![0u8; width * height * 4];
+        if max > 0.0 {
+            for (pixel, &value) in data.chunks_exact_mut(4).zip(heatmap.iter()) {
+                if value > 0.0 {
+                    let mut rgba = to_rgba8(gradient.sample(value / max));
+                    rgba[3] = 255;
+                    pixel.copy_from_slice(&rgba);
+                }
+            }
+        }
+        Ok(Rgba8Image {
+            width,
+            height,
+            data,
+        })
+    }
+}
+
+fn to_rgba8(color: [f32; 4]) -> [u8; 4] {
+    color.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
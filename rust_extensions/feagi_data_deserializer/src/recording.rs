@@ -0,0 +1,859 @@
+//! Recording and replay of visualization streams.
+//!
+//! Raw visualization packets (FeagiByteContainer or Type 11 bytes, exactly as received)
+//! are appended to an indexed file so a run can be inspected later or shared as a
+//! reproducible clip. Replayed frames go through the normal decode / MultiMesh paths.
+//!
+//! File layout (all integers little-endian):
+//!
+//! ```text
+//! header   : magic "FEAGIBVR" | version u32 (=1) | reserved u32 | start_unix_ms u64
+//! frame    : timestamp_us u64 | burst u64 | payload_len u32 | payload bytes
+//! ...
+//! index    : entry * frame_count, entry = offset u64 | timestamp_us u64 | burst u64 | payload_len u32
+//! trailer  : frame_count u64 | index_offset u64 | magic "BVRINDEX"
+//! ```
+//!
+//! `timestamp_us` is relative to the start of the recording. The index is written when the
+//! recording is stopped; files without it (e.g. after a crash) are recovered by scanning
+//! the frames, ignoring a truncated last frame. Files whose index points outside the frame
+//! data, or with frames above the payload ceiling, are rejected on open.
+
+use crate::ingest::IngestLimits;
+use crate::FeagiDataDeserializer;
+use godot::prelude::*;
+use std::fs::File;
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+const FILE_MAGIC: &[u8; 8] = b"FEAGIBVR";
+const INDEX_MAGIC: &[u8; 8] = b"BVRINDEX";
+const FORMAT_VERSION: u32 = 1;
+const HEADER_BYTES: u64 = 24;
+const FRAME_HEADER_BYTES: u64 = 20;
+const INDEX_ENTRY_BYTES: u64 = 28;
+const TRAILER_BYTES: u64 = 24;
+
+/// Location and metadata of one recorded frame.
+#[derive(Clone, Copy, Debug)]
+struct FrameEntry {
+    offset: u64,
+    timestamp_us: u64,
+    burst: u64,
+    payload_len: u32,
+}
+
+impl FrameEntry {
+    fn to_bytes(self) -> [u8; INDEX_ENTRY_BYTES as usize] {
+        let mut out = [0u8; INDEX_ENTRY_BYTES as usize];
+        out[0..8].copy_from_slice(&self.offset.to_le_bytes());
+        out[8..16].copy_from_slice(&self.timestamp_us.to_le_bytes());
+        out[16..24].copy_from_slice(&self.burst.to_le_bytes());
+        out[24..28].copy_from_slice(&self.payload_len.to_le_bytes());
+        out
+    }
+
+    fn from_bytes(b: &[u8]) -> Self {
+        Self {
+            offset: read_u64(&b[0..8]),
+            timestamp_us: read_u64(&b[8..16]),
+            burst: read_u64(&b[16..24]),
+            payload_len: read_u32(&b[24..28]),
+        }
+    }
+}
+
+fn read_u64(b: &[u8]) -> u64 {
+    let mut a = [0u8; 8];
+    a.copy_from_slice(&b[..8]);
+    u64::from_le_bytes(a)
+}
+
+fn read_u32(b: &[u8]) -> u32 {
+    let mut a = [0u8; 4];
+    a.copy_from_slice(&b[..4]);
+    u32::from_le_bytes(a)
+}
+
+/// Append-only writer for recording files.
+struct RecordingWriter {
+    file: BufWriter<File>,
+    started: Instant,
+    position: u64,
+    entries: Vec<FrameEntry>,
+}
+
+impl RecordingWriter {
+    fn create(path: &str) -> std::io::Result<Self> {
+        let mut file = BufWriter::new(File::create(path)?);
+        let start_unix_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        file.write_all(FILE_MAGIC)?;
+        file.write_all(&FORMAT_VERSION.to_le_bytes())?;
+        file.write_all(&0u32.to_le_bytes())?;
+        file.write_all(&start_unix_ms.to_le_bytes())?;
+        Ok(Self {
+            file,
+            started: Instant::now(),
+            position: HEADER_BYTES,
+            entries: Vec::new(),
+        })
+    }
+
+    fn append(&mut self, payload: &[u8], burst: u64) -> std::io::Result<()> {
+        let payload_len = u32::try_from(payload.len()).map_err(|_| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "packet larger than 4 GiB")
+        })?;
+        let entry = FrameEntry {
+            offset: self.position,
+            timestamp_us: self.started.elapsed().as_micros() as u64,
+            burst,
+            payload_len,
+        };
+        self.file.write_all(&entry.timestamp_us.to_le_bytes())?;
+        self.file.write_all(&burst.to_le_bytes())?;
+        self.file.write_all(&payload_len.to_le_bytes())?;
+        self.file.write_all(payload)?;
+        self.position += FRAME_HEADER_BYTES + payload.len() as u64;
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Write the index and trailer and flush everything to disk.
+    fn finish(mut self) -> std::io::Result<()> {
+        let index_offset = self.position;
+        for entry in &self.entries {
+            self.file.write_all(&entry.to_bytes())?;
+        }
+        self.file
+            .write_all(&(self.entries.len() as u64).to_le_bytes())?;
+        self.file.write_all(&index_offset.to_le_bytes())?;
+        self.file.write_all(INDEX_MAGIC)?;
+        self.file.flush()?;
+        self.file.get_ref().sync_all()
+    }
+}
+
+/// Random-access reader for recording files.
+struct RecordingReader {
+    file: File,
+    entries: Vec<FrameEntry>,
+}
+
+impl RecordingReader {
+    /// Open a recording; frames larger than `max_payload_bytes` are rejected.
+    fn open(path: &str, max_payload_bytes: usize) -> Result<Self, String> {
+        let mut file = File::open(path).map_err(|e| format!("{}: {}", path, e))?;
+        let file_len = file.metadata().map_err(|e| e.to_string())?.len();
+
+        let mut header = [0u8; HEADER_BYTES as usize];
+        file.read_exact(&mut header)
+            .map_err(|_| "File too small for recording header".to_string())?;
+        if &header[0..8] != FILE_MAGIC {
+            return Err("Not a visualization recording (bad magic)".to_string());
+        }
+        let version = read_u32(&header[8..12]);
+        if version != FORMAT_VERSION {
+            return Err(format!("Unsupported recording version: {}", version));
+        }
+
+        let entries = match Self::read_index(&mut file, file_len, max_payload_bytes)? {
+            Some(entries) => entries,
+            None => Self::scan_frames(&mut file, file_len, max_payload_bytes)?,
+        };
+        Ok(Self { file, entries })
+    }
+
+    /// Index written by `RecordingWriter::finish`, if present (None without a trailer).
+    ///
+    /// Every entry must lie between the header and the index and fit the payload ceiling.
+    fn read_index(
+        file: &mut File,
+        file_len: u64,
+        max_payload_bytes: usize,
+    ) -> Result<Option<Vec<FrameEntry>>, String> {
+        if file_len < HEADER_BYTES + TRAILER_BYTES {
+            return Ok(None);
+        }
+        let mut trailer = [0u8; TRAILER_BYTES as usize];
+        file.seek(SeekFrom::Start(file_len - TRAILER_BYTES))
+            .and_then(|_| file.read_exact(&mut trailer))
+            .map_err(|e| e.to_string())?;
+        if &trailer[16..24] != INDEX_MAGIC {
+            return Ok(None);
+        }
+        let frame_count = read_u64(&trailer[0..8]);
+        let index_offset = read_u64(&trailer[8..16]);
+        let index_len = frame_count.checked_mul(INDEX_ENTRY_BYTES);
+        match index_len.and_then(|len| index_offset.checked_add(len)) {
+            Some(end) if end == file_len - TRAILER_BYTES => {}
+            _ => return Ok(None),
+        }
+
+        let mut raw = vec![0u8; (frame_count * INDEX_ENTRY_BYTES) as usize];
+        file.seek(SeekFrom::Start(index_offset))
+            .and_then(|_| file.read_exact(&mut raw))
+            .map_err(|e| e.to_string())?;
+        let entries: Vec<FrameEntry> = raw
+            .chunks_exact(INDEX_ENTRY_BYTES as usize)
+            .map(FrameEntry::from_bytes)
+            .collect();
+        for (i, entry) in entries.iter().enumerate() {
+            let end = entry
+                .offset
+                .checked_add(FRAME_HEADER_BYTES + entry.payload_len as u64);
+            if entry.offset < HEADER_BYTES || !matches!(end, Some(end) if end <= index_offset) {
+                return Err(format!(
+                    "Corrupt recording index: frame {} ({} bytes at offset {}) lies outside the frame data",
+                    i, entry.payload_len, entry.offset
+                ));
+            }
+            check_payload_len(i, entry.payload_len, max_payload_bytes)?;
+        }
+        Ok(Some(entries))
+    }
+
+    /// Rebuild the index by walking frame headers (recording was not stopped cleanly).
+    fn scan_frames(
+        file: &mut File,
+        file_len: u64,
+        max_payload_bytes: usize,
+    ) -> Result<Vec<FrameEntry>, String> {
+        let mut entries = Vec::new();
+        let mut offset = HEADER_BYTES;
+        let mut frame_header = [0u8; FRAME_HEADER_BYTES as usize];
+        while offset + FRAME_HEADER_BYTES <= file_len {
+            file.seek(SeekFrom::Start(offset))
+                .and_then(|_| file.read_exact(&mut frame_header))
+                .map_err(|e| e.to_string())?;
+            let payload_len = read_u32(&frame_header[16..20]);
+            let end = offset + FRAME_HEADER_BYTES + payload_len as u64;
+            if end > file_len {
+                break; // truncated last frame
+            }
+            check_payload_len(entries.len(), payload_len, max_payload_bytes)?;
+            entries.push(FrameEntry {
+                offset,
+                timestamp_us: read_u64(&frame_header[0..8]),
+                burst: read_u64(&frame_header[8..16]),
+                payload_len,
+            });
+            offset = end;
+        }
+        Ok(entries)
+    }
+
+    fn read_payload(&mut self, index: usize) -> Result<Vec<u8>, String> {
+        let entry = self.entries[index];
+        let mut payload = vec![0u8; entry.payload_len as usize];
+        self.file
+            .seek(SeekFrom::Start(entry.offset + FRAME_HEADER_BYTES))
+            .and_then(|_| self.file.read_exact(&mut payload))
+            .map_err(|e| e.to_string())?;
+        Ok(payload)
+    }
+}
+
+fn check_payload_len(
+    frame: usize,
+    payload_len: u32,
+    max_payload_bytes: usize,
+) -> Result<(), String> {
+    if payload_len as usize > max_payload_bytes {
+        return Err(format!(
+            "Frame {} is {} bytes, above the {} byte ceiling",
+            frame, payload_len, max_payload_bytes
+        ));
+    }
+    Ok(())
+}
+
+/// Records raw visualization packets to an indexed file.
+///
+/// Example (GDScript):
+///   var recorder = FeagiVisualizationRecorder.new()
+///   recorder.start("user://session.bvrec")
+///   # for every packet received from FEAGI:
+///   recorder.record_packet(packet, -1)
+///   recorder.stop()
+#[derive(GodotClass)]
+#[class(base=RefCounted)]
+pub struct FeagiVisualizationRecorder {
+    #[base]
+    base: Base<RefCounted>,
+    writer: Option<RecordingWriter>,
+    next_burst: u64,
+    bytes_written: u64,
+}
+
+#[godot_api]
+impl IRefCounted for FeagiVisualizationRecorder {
+    fn init(base: Base<RefCounted>) -> Self {
+        Self {
+            base,
+            writer: None,
+            next_burst: 0,
+            bytes_written: 0,
+        }
+    }
+}
+
+#[godot_api]
+impl FeagiVisualizationRecorder {
+    /// Start a new recording (an active recording is stopped first).
+    ///
+    /// Args:
+    ///   - path: file system path (use ProjectSettings.globalize_path for user:// paths)
+    ///
+    /// Returns: true if the file was created
+    #[func]
+    pub fn start(&mut self, path: GString) -> bool {
+        self.stop();
+        match RecordingWriter::create(&path.to_string()) {
+            Ok(writer) => {
+                self.writer = Some(writer);
+                self.next_burst = 0;
+                self.bytes_written = HEADER_BYTES;
+                true
+            }
+            Err(e) => {
+                godot_error!("🦀 [REC] Cannot create recording {}: {}", path, e);
+                false
+            }
+        }
+    }
+
+    /// Append one raw packet as received from FEAGI.
+    ///
+    /// Args:
+    ///   - buffer: FeagiByteContainer / Type 11 bytes (not modified)
+    ///   - burst: FEAGI burst number, or -1 to number frames sequentially
+    ///
+    /// Returns: true if the frame was written
+    #[func]
+    pub fn record_packet(&mut self, buffer: PackedByteArray, burst: i64) -> bool {
+        let writer = match self.writer.as_mut() {
+            Some(w) => w,
+            None => return false,
+        };
+        let burst = if burst >= 0 {
+            burst as u64
+        } else {
+            self.next_burst
+        };
+        let payload = buffer.as_slice();
+        match writer.append(payload, burst) {
+            Ok(()) => {
+                self.next_burst = burst + 1;
+                self.bytes_written += FRAME_HEADER_BYTES + payload.len() as u64;
+                true
+            }
+            Err(e) => {
+                godot_error!("🦀 [REC] Write failed, recording stopped: {}", e);
+                self.writer = None;
+                false
+            }
+        }
+    }
+
+    /// Finish the recording (writes the frame index). Safe to call when not recording.
+    ///
+    /// Returns: true if a recording was finalized successfully
+    #[func]
+    pub fn stop(&mut self) -> bool {
+        match self.writer.take() {
+            Some(writer) => match writer.finish() {
+                Ok(()) => true,
+                Err(e) => {
+                    godot_error!("🦀 [REC] Failed to finalize recording: {}", e);
+                    false
+                }
+            },
+            None => false,
+        }
+    }
+
+    #[func]
+    pub fn is_recording(&self) -> bool {
+        self.writer.is_some()
+    }
+
+    /// Frames written to the current recording
+    #[func]
+    pub fn get_frame_count(&self) -> i64 {
+        self.writer.as_ref().map_or(0, |w| w.entries.len() as i64)
+    }
+
+    /// Bytes written to the current recording (without the index)
+    #[func]
+    pub fn get_bytes_written(&self) -> i64 {
+        self.bytes_written as i64
+    }
+}
+
+impl Drop for FeagiVisualizationRecorder {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Plays back files written by FeagiVisualizationRecorder.
+///
+/// Call advance(delta) every frame while playing; it returns the newest frame that became
+/// due (or an empty array). Frames can be applied through apply_current_frame or passed to
+/// any FeagiDataDeserializer method that takes a packet.
+///
+/// Example (GDScript):
+///   player.open(path)
+///   player.play()
+///   func _process(delta):
+///       if not player.advance(delta).is_empty():
+///           player.apply_current_frame(deserializer, multimeshes, dimensions, true)
+#[derive(GodotClass)]
+#[class(base=RefCounted)]
+pub struct FeagiVisualizationPlayer {
+    #[base]
+    base: Base<RefCounted>,
+    reader: Option<RecordingReader>,
+    /// Index of the frame currently shown (-1 before the first frame)
+    current: i64,
+    playing: bool,
+    speed: f64,
+    /// Playback clock in microseconds of recording time
+    clock_us: f64,
+    /// Largest frame accepted by open()
+    max_frame_bytes: usize,
+}
+
+#[godot_api]
+impl IRefCounted for FeagiVisualizationPlayer {
+    fn init(base: Base<RefCounted>) -> Self {
+        Self {
+            base,
+            reader: None,
+            current: -1,
+            playing: false,
+            speed: 1.0,
+            clock_us: 0.0,
+            max_frame_bytes: IngestLimits::default().max_decompressed_bytes,
+        }
+    }
+}
+
+#[godot_api]
+impl FeagiVisualizationPlayer {
+    /// Emitted when playback reaches the last frame
+    #[signal]
+    fn playback_finished();
+
+    /// Open a recording. Playback starts paused before the first frame.
+    ///
+    /// Returns: true if the file is a valid recording
+    #[func]
+    pub fn open(&mut self, path: GString) -> bool {
+        self.close();
+        match RecordingReader::open(&path.to_string(), self.max_frame_bytes) {
+            Ok(reader) => {
+                self.reader = Some(reader);
+                true
+            }
+            Err(e) => {
+                godot_error!("🦀 [REPLAY] Cannot open recording: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Largest frame accepted by open(), in bytes (default 256 MiB, the deserializer's
+    /// default decompression ceiling; pass FeagiDataDeserializer.get_max_decompressed_bytes()
+    /// to match a deserializer). Recordings with a larger frame are rejected.
+    ///
+    /// Args:
+    ///   - max_bytes: ceiling in bytes (values below 1 are ignored)
+    #[func]
+    pub fn set_max_frame_bytes(&mut self, max_bytes: i64) {
+        if max_bytes < 1 {
+            godot_error!(
+                "🦀 [REPLAY] Ignoring non-positive frame size ceiling: {}",
+                max_bytes
+            );
+            return;
+        }
+        self.max_frame_bytes = max_bytes as usize;
+    }
+
+    #[func]
+    pub fn get_max_frame_bytes(&self) -> i64 {
+        self.max_frame_bytes as i64
+    }
+
+    #[func]
+    pub fn close(&mut self) {
+        self.reader = None;
+        self.current = -1;
+        self.playing = false;
+        self.clock_us = 0.0;
+    }
+
+    #[func]
+    pub fn get_frame_count(&self) -> i64 {
+        self.reader.as_ref().map_or(0, |r| r.entries.len() as i64)
+    }
+
+    /// Recording length in seconds (timestamp of the last frame)
+    #[func]
+    pub fn get_duration_seconds(&self) -> f64 {
+        self.reader
+            .as_ref()
+            .and_then(|r| r.entries.last())
+            .map_or(0.0, |e| e.timestamp_us as f64 / 1_000_000.0)
+    }
+
+    #[func]
+    pub fn play(&mut self) {
+        if self.reader.is_some() {
+            self.playing = true;
+        }
+    }
+
+    #[func]
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    #[func]
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Playback speed multiplier (1.0 = real time, 0.5 = half speed). Must be > 0.
+    #[func]
+    pub fn set_speed(&mut self, speed: f64) {
+        if speed.is_finite() && speed > 0.0 {
+            self.speed = speed;
+        } else {
+            godot_error!("🦀 [REPLAY] Playback speed must be > 0 (got {})", speed);
+        }
+    }
+
+    #[func]
+    pub fn get_speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// Jump to a frame (keeps the play/pause state).
+    ///
+    /// Returns: the frame payload, or an empty array if the index is out of range
+    #[func]
+    pub fn seek(&mut self, frame_index: i64) -> PackedByteArray {
+        if frame_index < 0 || frame_index >= self.get_frame_count() {
+            return PackedByteArray::new();
+        }
+        self.current = frame_index;
+        self.clock_us = self
+            .entry(frame_index)
+            .map_or(0.0, |e| e.timestamp_us as f64);
+        self.get_current_frame()
+    }
+
+    /// Jump to the last frame at or before a time (seconds from the start of the recording).
+    #[func]
+    pub fn seek_time(&mut self, seconds: f64) -> PackedByteArray {
+        let target_us = (seconds.max(0.0) * 1_000_000.0) as u64;
+        let index = match self.reader.as_ref() {
+            Some(r) => r
+                .entries
+                .partition_point(|e| e.timestamp_us <= target_us)
+                .saturating_sub(1),
+            None => return PackedByteArray::new(),
+        };
+        self.seek(index as i64)
+    }
+
+    /// Pause and show the next frame.
+    #[func]
+    pub fn step_forward(&mut self) -> PackedByteArray {
+        self.playing = false;
+        self.seek(self.current + 1)
+    }
+
+    /// Pause and show the previous frame.
+    #[func]
+    pub fn step_backward(&mut self) -> PackedByteArray {
+        self.playing = false;
+        self.seek(self.current - 1)
+    }
+
+    /// Advance the playback clock by `delta` seconds (scaled by the speed).
+    ///
+    /// Returns: payload of the newest frame that became due, or an empty array if the
+    /// shown frame did not change. Intermediate frames are skipped, like the live stream.
+    #[func]
+    pub fn advance(&mut self, delta: f64) -> PackedByteArray {
+        if !self.playing || self.reader.is_none() {
+            return PackedByteArray::new();
+        }
+        self.clock_us += delta.max(0.0) * self.speed * 1_000_000.0;
+
+        let clock_us = self.clock_us as u64;
+        let due = match self.reader.as_ref() {
+            Some(r) => r.entries.partition_point(|e| e.timestamp_us <= clock_us) as i64 - 1,
+            None => -1,
+        };
+        let last = self.get_frame_count() - 1;
+        if due >= last {
+            self.playing = false;
+            self.base_mut().emit_signal("playback_finished", &[]);
+        }
+        if due <= self.current {
+            return PackedByteArray::new();
+        }
+        self.current = due;
+        self.get_current_frame()
+    }
+
+    /// Index of the frame currently shown (-1 before the first frame)
+    #[func]
+    pub fn get_current_frame_index(&self) -> i64 {
+        self.current
+    }
+
+    /// Payload of the frame currently shown (empty before the first frame)
+    #[func]
+    pub fn get_current_frame(&mut self) -> PackedByteArray {
+        let index = self.current;
+        if index < 0 {
+            return PackedByteArray::new();
+        }
+        let reader = match self.reader.as_mut() {
+            Some(r) => r,
+            None => return PackedByteArray::new(),
+        };
+        match reader.read_payload(index as usize) {
+            Ok(payload) => PackedByteArray::from(payload.as_slice()),
+            Err(e) => {
+                godot_error!("🦀 [REPLAY] Failed to read frame {}: {}", index, e);
+                PackedByteArray::new()
+            }
+        }
+    }
+
+    /// Metadata of the frame currently shown.
+    ///
+    /// Returns: Dictionary {index: int, burst: int, timestamp_us: int, size: int} (index -1 if none)
+    #[func]
+    pub fn get_current_frame_info(&self) -> Dictionary {
+        let mut info = Dictionary::new();
+        info.set("index", self.current);
+        match self.entry(self.current) {
+            Some(e) => {
+                info.set("burst", e.burst as i64);
+                info.set("timestamp_us", e.timestamp_us as i64);
+                info.set("size", e.payload_len as i64);
+            }
+            None => {
+                info.set("burst", -1);
+                info.set("timestamp_us", 0);
+                info.set("size", 0);
+            }
+        }
+        info
+    }
+
+    /// Decode the current frame with a deserializer (same result as decode_type_11_data).
+    #[func]
+    pub fn decode_current_frame(
+        &mut self,
+        mut deserializer: Gd<FeagiDataDeserializer>,
+    ) -> Dictionary {
+        let frame = self.get_current_frame();
+        deserializer.bind_mut().decode_type_11_data(frame)
+    }
+
+    /// Apply the current frame to MultiMeshes (same result as apply_type11_packet_to_multimeshes).
+    #[func]
+    pub fn apply_current_frame(
+        &mut self,
+        mut deserializer: Gd<FeagiDataDeserializer>,
+        multimeshes_by_id: Dictionary,
+        dimensions_by_id: Dictionary,
+        clear_all_before_apply: bool,
+    ) -> Dictionary {
+        let frame = self.get_current_frame();
+        deserializer.bind_mut().apply_type11_packet_to_multimeshes(
+            frame,
+            multimeshes_by_id,
+            dimensions_by_id,
+            clear_all_before_apply,
+        )
+    }
+}
+
+impl FeagiVisualizationPlayer {
+    fn entry(&self, index: i64) -> Option<FrameEntry> {
+        if index < 0 {
+            return None;
+        }
+        self.reader
+            .as_ref()
+            .and_then(|r| r.entries.get(index as usize).copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CEILING: usize = 1024;
+
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("feagi_recording_{}_{}", std::process::id(), name))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn frames() -> Vec<(Vec<u8>, u64)> {
+        vec![
+            (vec![2, 0, 1, 2, 3], 7),
+            (Vec::new(), 8),
+            (vec![11; 300], 20),
+        ]
+    }
+
+    fn write_recording(path: &str, finish: bool) {
+        let mut writer = RecordingWriter::create(path).unwrap();
+        for (payload, burst) in frames() {
+            writer.append(&payload, burst).unwrap();
+        }
+        if finish {
+            writer.finish().unwrap();
+        } else {
+            writer.file.flush().unwrap();
+        }
+    }
+
+    fn assert_frames(reader: &mut RecordingReader) {
+        let expected = frames();
+        assert_eq!(reader.entries.len(), expected.len());
+        for (i, (payload, burst)) in expected.into_iter().enumerate() {
+            assert_eq!(reader.entries[i].burst, burst);
+            assert_eq!(reader.read_payload(i).unwrap(), payload);
+        }
+        let timestamps: Vec<u64> = reader.entries.iter().map(|e| e.timestamp_us).collect();
+        assert!(timestamps.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    /// Overwrite the index entry of `frame` with `entry`
+    fn patch_index_entry(path: &str, frame: u64, entry: FrameEntry) {
+        let mut bytes = std::fs::read(path).unwrap();
+        let trailer = bytes.len() - TRAILER_BYTES as usize;
+        let index_offset = read_u64(&bytes[trailer + 8..trailer + 16]);
+        let at = (index_offset + frame * INDEX_ENTRY_BYTES) as usize;
+        bytes[at..at + INDEX_ENTRY_BYTES as usize].copy_from_slice(&entry.to_bytes());
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    #[test]
+    fn recording_round_trips_through_the_index() {
+        let path = temp_path("round_trip.bvrec");
+        write_recording(&path, true);
+        let mut reader = RecordingReader::open(&path, CEILING).unwrap();
+        assert_frames(&mut reader);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn unfinished_recording_is_recovered_by_scanning() {
+        let path = temp_path("unfinished.bvrec");
+        write_recording(&path, false);
+        // Crash in the middle of a fourth frame
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        file.write_all(&[0u8; 16]).unwrap();
+        file.write_all(&100u32.to_le_bytes()).unwrap();
+        file.write_all(&[2, 0]).unwrap();
+        drop(file);
+
+        let mut reader = RecordingReader::open(&path, CEILING).unwrap();
+        assert_frames(&mut reader);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn index_entries_outside_the_frame_data_are_rejected() {
+        let path = temp_path("corrupt_index.bvrec");
+        write_recording(&path, true);
+        let good = RecordingReader::open(&path, CEILING).unwrap().entries;
+        let index_offset = good
+            .last()
+            .map(|e| e.offset + FRAME_HEADER_BYTES + e.payload_len as u64)
+            .unwrap();
+
+        let corruptions = [
+            // Payload runs into the index
+            FrameEntry {
+                payload_len: good[2].payload_len + 1,
+                ..good[2]
+            },
+            // Huge length that would otherwise be allocated by read_payload
+            FrameEntry {
+                payload_len: u32::MAX,
+                ..good[0]
+            },
+            // Offset inside the header
+            FrameEntry {
+                offset: 4,
+                ..good[0]
+            },
+            // Offset past the index, and one that overflows
+            FrameEntry {
+                offset: index_offset,
+                ..good[1]
+            },
+            FrameEntry {
+                offset: u64::MAX - 4,
+                ..good[1]
+            },
+        ];
+        for (i, corrupt) in corruptions.into_iter().enumerate() {
+            write_recording(&path, true);
+            patch_index_entry(&path, i.min(2) as u64, corrupt);
+            let error = RecordingReader::open(&path, usize::MAX)
+                .err()
+                .unwrap_or_else(|| panic!("corruption {} accepted", i));
+            assert!(error.contains("Corrupt recording index"), "{}", error);
+        }
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn frames_above_the_ceiling_are_rejected() {
+        let path = temp_path("ceiling.bvrec");
+        write_recording(&path, true);
+        let error = RecordingReader::open(&path, 299).err().unwrap();
+        assert_eq!(error, "Frame 2 is 300 bytes, above the 299 byte ceiling");
+        assert!(RecordingReader::open(&path, 300).is_ok());
+
+        write_recording(&path, false);
+        let error = RecordingReader::open(&path, 299).err().unwrap();
+        assert_eq!(error, "Frame 2 is 300 bytes, above the 299 byte ceiling");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn files_that_are_not_recordings_are_rejected() {
+        let path = temp_path("not_a_recording.bvrec");
+        std::fs::write(&path, b"FEAGIBVH: a history export, not a recording").unwrap();
+        let error = RecordingReader::open(&path, CEILING).err().unwrap();
+        assert_eq!(error, "Not a visualization recording (bad magic)");
+        std::fs::write(&path, b"FEAGIBVR").unwrap();
+        let error = RecordingReader::open(&path, CEILING).err().unwrap();
+        assert_eq!(error, "File too small for recording header");
+        std::fs::remove_file(path).unwrap();
+    }
+}
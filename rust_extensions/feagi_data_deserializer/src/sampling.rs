@@ -0,0 +1,425 @@
+//! Budget-aware neuron sampling.
+//!
+//! When more neurons fired than may be drawn, the legacy path kept whatever came first from
+//! the HashMap iteration, so the drawn subset changed from frame to frame. The strategies here
+//! only depend on the cortical IDs, voxel coordinates, potentials and a seed, so the same
+//! activity always produces the same subset.
+
+use crate::multimesh_buffer::VoxelInstance;
+
+/// How neurons are selected when the budget is exceeded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SamplingStrategy {
+    /// Keep neurons in packet order until the budget is used up (legacy behaviour)
+    FirstN,
+    /// Every area keeps a share of the budget proportional to its neuron count
+    Proportional,
+    /// Keep the neurons with the largest |potential| across all areas
+    Strongest,
+    /// Keep the neurons with the lowest seeded hash of (area, x, y, z) across all areas
+    SeededReservoir,
+}
+
+impl SamplingStrategy {
+    pub fn from_i32(strategy: i32) -> Option<Self> {
+        match strategy {
+            0 => Some(SamplingStrategy::FirstN),
+            1 => Some(SamplingStrategy::Proportional),
+            2 => Some(SamplingStrategy::Strongest),
+            3 => Some(SamplingStrategy::SeededReservoir),
+            _ => None,
+        }
+    }
+
+    pub fn as_i32(self) -> i32 {
+        match self {
+            SamplingStrategy::FirstN => 0,
+            SamplingStrategy::Proportional => 1,
+            SamplingStrategy::Strongest => 2,
+            SamplingStrategy::SeededReservoir => 3,
+        }
+    }
+}
+
+/// Sampling configuration of a deserializer.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct SamplingSettings {
+    pub strategy: SamplingStrategy,
+    pub seed: u64,
+}
+
+impl Default for SamplingSettings {
+    fn default() -> Self {
+        Self {
+            strategy: SamplingStrategy::Proportional,
+            seed: 0,
+        }
+    }
+}
+
+/// Neurons of one area that survived sampling.
+pub(crate) struct AreaSample {
+    pub cortical_id: String,
+    pub voxels: Vec<VoxelInstance>,
+    pub dropped: usize,
+}
+
+/// SplitMix64 finalizer.
+#[inline]
+pub(crate) fn mix64(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Stable 64-bit key of a cortical ID (FNV-1a).
+pub(crate) fn area_key(cortical_id: &str) -> u64 {
+    cortical_id.bytes().fold(0xCBF2_9CE4_8422_2325u64, |h, b| {
+        (h ^ b as u64).wrapping_mul(0x0100_0000_01B3)
+    })
+}
+
+/// Seeded hash of a voxel coordinate within an area.
+#[inline]
+pub(crate) fn voxel_hash(seed: u64, area_key: u64, x: u32, y: u32, z: u32) -> u64 {
+    let mut h = mix64(seed ^ area_key);
+    h = mix64(h ^ x as u64);
+    h = mix64(h ^ y as u64);
+    mix64(h ^ z as u64)
+}
+
+/// Reduce `areas` to at most `budget` neurons.
+///
+/// Areas come back in input order for FirstN and sorted by cortical ID otherwise; the relative
+/// order of kept neurons inside an area is preserved.
+pub(crate) fn sample(
+    settings: SamplingSettings,
+    mut areas: Vec<(String, Vec<VoxelInstance>)>,
+    budget: usize,
+) -> Vec<AreaSample> {
+    let total: usize = areas.iter().map(|(_, v)| v.len()).sum();
+    if total <= budget {
+        return areas
+            .into_iter()
+            .map(|(cortical_id, voxels)| AreaSample {
+                cortical_id,
+                voxels,
+                dropped: 0,
+            })
+            .collect();
+    }
+
+    if settings.strategy == SamplingStrategy::FirstN {
+        let mut remaining = budget;
+        return areas
+            .into_iter()
+            .map(|(cortical_id, mut voxels)| {
+                let keep = voxels.len().min(remaining);
+                remaining -= keep;
+                let dropped = voxels.len() - keep;
+                voxels.truncate(keep);
+                AreaSample {
+                    cortical_id,
+                    voxels,
+                    dropped,
+                }
+            })
+            .collect();
+    }
+
+    areas.sort_by(|a, b| a.0.cmp(&b.0));
+    let keep_masks: Vec<Vec<bool>> = match settings.strategy {
+        SamplingStrategy::Proportional => {
+            let quotas = proportional_quotas(&areas, total, budget);
+            areas
+                .iter()
+                .zip(quotas)
+                .map(|((cortical_id, voxels), quota)| {
+                    let key = area_key(cortical_id);
+                    let ranked: Vec<(f32, u64)> = voxels
+                        .iter()
+                        .map(|v| (0.0, voxel_hash(settings.seed, key, v.x, v.y, v.z)))
+                        .collect();
+                    keep_best(&ranked, quota)
+                })
+                .collect()
+        }
+        SamplingStrategy::Strongest => keep_best_global(&areas, budget, settings.seed, |v| {
+            // Larger magnitude sorts first
+            -v.potential.abs()
+        }),
+        SamplingStrategy::SeededReservoir => {
+            keep_best_global(&areas, budget, settings.seed, |_| 0.0)
+        }
+        SamplingStrategy::FirstN => unreachable!("handled above"),
+    };
+
+    areas
+        .into_iter()
+        .zip(keep_masks)
+        .map(|((cortical_id, voxels), mask)| {
+            let before = voxels.len();
+            let voxels: Vec<VoxelInstance> = voxels
+                .into_iter()
+                .zip(mask)
+                .filter_map(|(v, keep)| keep.then_some(v))
+                .collect();
+            AreaSample {
+                dropped: before - voxels.len(),
+                cortical_id,
+                voxels,
+            }
+        })
+        .collect()
+}
+
+/// Largest-remainder split of `budget` over the areas by neuron count (ties go to the
+/// earlier area, i.e. the lower cortical ID).
+fn proportional_quotas(
+    areas: &[(String, Vec<VoxelInstance>)],
+    total: usize,
+    budget: usize,
+) -> Vec<usize> {
+    let mut quotas: Vec<usize> = Vec::with_capacity(areas.len());
+    let mut remainders: Vec<(usize, usize)> = Vec::with_capacity(areas.len());
+    for (i, (_, voxels)) in areas.iter().enumerate() {
+        let exact = voxels.len() as u128 * budget as u128;
+        quotas.push((exact / total as u128) as usize);
+        remainders.push(((exact % total as u128) as usize, i));
+    }
+    let mut leftover = budget - quotas.iter().sum::<usize>();
+    remainders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    for (_, i) in remainders {
+        if leftover == 0 {
+            break;
+        }
+        if quotas[i] < areas[i].1.len() {
+            quotas[i] += 1;
+            leftover -= 1;
+        }
+    }
+    quotas
+}
+
+/// Mask of the `keep` entries with the lowest (score, hash).
+fn keep_best(ranked: &[(f32, u64)], keep: usize) -> Vec<bool> {
+    let mut mask = vec![false; ranked.len()];
+    if keep >= ranked.len() {
+        mask.iter_mut().for_each(|m| *m = true);
+        return mask;
+    }
+    if keep == 0 {
+        return mask;
+    }
+    let mut order: Vec<usize> = (0..ranked.len()).collect();
+    order.select_nth_unstable_by(keep - 1, |&a, &b| {
+        ranked[a]
+            .0
+            .total_cmp(&ranked[b].0)
+            .then(ranked[a].1.cmp(&ranked[b].1))
+    });
+    for &i in &order[..keep] {
+        mask[i] = true;
+    }
+    mask
+}
+
+/// `keep_best` across all areas at once; returns one mask per area.
+fn keep_best_global<F>(
+    areas: &[(String, Vec<VoxelInstance>)],
+    keep: usize,
+    seed: u64,
+    score: F,
+) -> Vec<Vec<bool>>
+where
+    F: Fn(&VoxelInstance) -> f32,
+{
+    let mut ranked: Vec<(f32, u64)> = Vec::new();
+    for (cortical_id, voxels) in areas {
+        let key = area_key(cortical_id);
+        ranked.extend(
+            voxels
+                .iter()
+                .map(|v| (score(v), voxel_hash(seed, key, v.x, v.y, v.z))),
+        );
+    }
+    let flat = keep_best(&ranked, keep);
+
+    let mut masks = Vec::with_capacity(areas.len());
+    let mut start = 0;
+    for (_, voxels) in areas {
+        masks.push(flat[start..start + voxels.len()].to_vec());
+        start += voxels.len();
+    }
+    masks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STRATEGIES: [SamplingStrategy; 4] = [
+        SamplingStrategy::FirstN,
+        SamplingStrategy::Proportional,
+        SamplingStrategy::Strongest,
+        SamplingStrategy::SeededReservoir,
+    ];
+
+    fn voxel(x: u32, y: u32, z: u32, potential: f32) -> VoxelInstance {
+        VoxelInstance {
+            x,
+            y,
+            z,
+            potential,
+            intensity: 1.0,
+            extent: 1,
+            size: 1.0,
+        }
+    }
+
+    /// A `side` x `side` plane with potentials spread over [-1, 1].
+    fn plane(side: u32) -> Vec<VoxelInstance> {
+        (0..side * side)
+            .map(|i| {
+                let potential = (i % 21) as f32 / 10.0 - 1.0;
+                voxel(i % side, i / side, 0, potential)
+            })
+            .collect()
+    }
+
+    fn activity() -> Vec<(String, Vec<VoxelInstance>)> {
+        vec![
+            ("o__mot".to_string(), plane(10)),
+            ("i__inf".to_string(), plane(20)),
+            ("iv00_C".to_string(), plane(5)),
+        ]
+    }
+
+    fn settings(strategy: SamplingStrategy, seed: u64) -> SamplingSettings {
+        SamplingSettings { strategy, seed }
+    }
+
+    /// Kept (area, x, y, z) in output order.
+    fn kept(samples: &[AreaSample]) -> Vec<(String, u32, u32, u32)> {
+        samples
+            .iter()
+            .flat_map(|s| {
+                s.voxels
+                    .iter()
+                    .map(|v| (s.cortical_id.clone(), v.x, v.y, v.z))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn every_strategy_stays_within_the_budget() {
+        let total: usize = activity().iter().map(|(_, v)| v.len()).sum();
+        for strategy in STRATEGIES {
+            for budget in [0, 1, 7, 100, 333, total - 1, total, total + 10] {
+                let samples = sample(settings(strategy, 3), activity(), budget);
+                let drawn: usize = samples.iter().map(|s| s.voxels.len()).sum();
+                let dropped: usize = samples.iter().map(|s| s.dropped).sum();
+                assert_eq!(drawn, budget.min(total), "{:?} {}", strategy, budget);
+                assert_eq!(drawn + dropped, total);
+                assert_eq!(samples.len(), 3);
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_and_input_select_the_same_neurons() {
+        for strategy in STRATEGIES {
+            let first = kept(&sample(settings(strategy, 42), activity(), 120));
+            let second = kept(&sample(settings(strategy, 42), activity(), 120));
+            assert_eq!(first, second, "{:?}", strategy);
+        }
+        // Hash-based strategies do not depend on the order areas arrive in
+        let mut reversed = activity();
+        reversed.reverse();
+        for strategy in [
+            SamplingStrategy::Proportional,
+            SamplingStrategy::SeededReservoir,
+        ] {
+            assert_eq!(
+                kept(&sample(settings(strategy, 42), activity(), 120)),
+                kept(&sample(settings(strategy, 42), reversed.clone(), 120))
+            );
+        }
+        // ...but do depend on the seed
+        assert_ne!(
+            kept(&sample(
+                settings(SamplingStrategy::SeededReservoir, 1),
+                activity(),
+                120
+            )),
+            kept(&sample(
+                settings(SamplingStrategy::SeededReservoir, 2),
+                activity(),
+                120
+            ))
+        );
+    }
+
+    #[test]
+    fn seeded_reservoir_keeps_voxels_that_stay_active() {
+        let strategy = settings(SamplingStrategy::SeededReservoir, 7);
+        let frame = plane(20);
+        let first = sample(strategy, vec![("area".to_string(), frame.clone())], 50);
+        let selected: Vec<(u32, u32, u32)> =
+            first[0].voxels.iter().map(|v| (v.x, v.y, v.z)).collect();
+        assert_eq!(selected.len(), 50);
+
+        // Next burst: every other neuron went quiet and the potentials changed
+        let next: Vec<VoxelInstance> = frame
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| i % 2 == 0)
+            .map(|(_, v)| voxel(v.x, v.y, v.z, -v.potential))
+            .collect();
+        let second = sample(strategy, vec![("area".to_string(), next.clone())], 50);
+        let still_selected: Vec<(u32, u32, u32)> =
+            second[0].voxels.iter().map(|v| (v.x, v.y, v.z)).collect();
+        let stayed_active = selected
+            .iter()
+            .filter(|key| next.iter().any(|v| (v.x, v.y, v.z) == **key));
+        let mut checked = 0;
+        for key in stayed_active {
+            assert!(still_selected.contains(key), "{:?} was dropped", key);
+            checked += 1;
+        }
+        assert!(checked > 0);
+    }
+
+    #[test]
+    fn strategies_pick_the_expected_neurons() {
+        let areas = || vec![("b".to_string(), plane(10)), ("a".to_string(), plane(20))];
+
+        // FirstN: packet order, first area first
+        let samples = sample(settings(SamplingStrategy::FirstN, 0), areas(), 120);
+        assert_eq!(samples[0].cortical_id, "b");
+        assert_eq!(samples[0].voxels.len(), 100);
+        assert_eq!((samples[1].voxels.len(), samples[1].dropped), (20, 380));
+        assert_eq!(samples[1].voxels[..], plane(20)[..20]);
+
+        // Proportional: 400 and 100 neurons share 100 as 80 and 20, sorted by cortical ID
+        let samples = sample(settings(SamplingStrategy::Proportional, 0), areas(), 100);
+        assert_eq!(samples[0].cortical_id, "a");
+        assert_eq!(samples[0].voxels.len(), 80);
+        assert_eq!(samples[1].voxels.len(), 20);
+
+        // Strongest: no kept neuron is weaker than a dropped one
+        let samples = sample(settings(SamplingStrategy::Strongest, 0), areas(), 100);
+        let weakest_kept = samples
+            .iter()
+            .flat_map(|s| &s.voxels)
+            .map(|v| v.potential.abs())
+            .fold(f32::INFINITY, f32::min);
+        let stronger_than_weakest_kept = areas()
+            .into_iter()
+            .flat_map(|(_, v)| v)
+            .filter(|v| v.potential.abs() > weakest_kept)
+            .count();
+        assert!(stronger_than_weakest_kept <= 100);
+    }
+}
@@ -0,0 +1,241 @@
+//! Per-area activity statistics.
+//!
+//! Statistics are accumulated while the decode and apply paths already walk every neuron, so
+//! the brain monitor does not have to loop over `p_array` in GDScript. Firing rates are rolling
+//! averages over the last N recorded bursts.
+
+use godot::prelude::*;
+use std::collections::{HashMap, VecDeque};
+use std::time::Instant;
+
+/// Default number of bursts in the firing-rate window
+const DEFAULT_WINDOW_BURSTS: usize = 20;
+
+/// Activity of one area in one burst.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct AreaStats {
+    pub count: usize,
+    min_potential: f32,
+    max_potential: f32,
+    potential_sum: f64,
+    coordinate_sum: [f64; 3],
+    bbox_min: [u32; 3],
+    bbox_max: [u32; 3],
+}
+
+impl Default for AreaStats {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            min_potential: f32::INFINITY,
+            max_potential: f32::NEG_INFINITY,
+            potential_sum: 0.0,
+            coordinate_sum: [0.0; 3],
+            bbox_min: [u32::MAX; 3],
+            bbox_max: [0; 3],
+        }
+    }
+}
+
+impl AreaStats {
+    /// Add one active neuron.
+    #[inline]
+    pub fn push(&mut self, x: u32, y: u32, z: u32, potential: f32) {
+        self.count += 1;
+        self.min_potential = self.min_potential.min(potential);
+        self.max_potential = self.max_potential.max(potential);
+        self.potential_sum += potential as f64;
+        for (a, v) in [x, y, z].into_iter().enumerate() {
+            self.coordinate_sum[a] += v as f64;
+            self.bbox_min[a] = self.bbox_min[a].min(v);
+            self.bbox_max[a] = self.bbox_max[a].max(v);
+        }
+    }
+
+    /// Statistics as a Godot Dictionary.
+    ///
+    /// Keys: firing_count, min_potential, max_potential, mean_potential, centroid (Vector3),
+    /// bbox_min / bbox_max (Vector3i, inclusive) and active_fraction (count / area volume, or
+    /// -1.0 when the area dimensions are unknown).
+    pub fn to_dictionary(&self, dimensions: Option<Vector3>) -> Dictionary {
+        let mut dict = Dictionary::new();
+        dict.set("firing_count", self.count as i32);
+        if self.count == 0 {
+            dict.set("min_potential", 0.0f32);
+            dict.set("max_potential", 0.0f32);
+            dict.set("mean_potential", 0.0f32);
+            dict.set("centroid", Vector3::ZERO);
+            dict.set("bbox_min", Vector3i::ZERO);
+            dict.set("bbox_max", Vector3i::ZERO);
+        } else {
+            let n = self.count as f64;
+            dict.set("min_potential", self.min_potential);
+            dict.set("max_potential", self.max_potential);
+            dict.set("mean_potential", (self.potential_sum / n) as f32);
+            dict.set(
+                "centroid",
+                Vector3::new(
+                    (self.coordinate_sum[0] / n) as f32,
+                    (self.coordinate_sum[1] / n) as f32,
+                    (self.coordinate_sum[2] / n) as f32,
+                ),
+            );
+            dict.set("bbox_min", to_vector3i(self.bbox_min));
+            dict.set("bbox_max", to_vector3i(self.bbox_max));
+        }
+        let volume = dimensions.map(|d| d.x as f64 * d.y as f64 * d.z as f64);
+        let active_fraction = match volume {
+            Some(v) if v > 0.0 => (self.count as f64 / v).min(1.0) as f32,
+            _ => -1.0,
+        };
+        dict.set("active_fraction", active_fraction);
+        dict
+    }
+}
+
+fn to_vector3i(v: [u32; 3]) -> Vector3i {
+    Vector3i::new(v[0] as i32, v[1] as i32, v[2] as i32)
+}
+
+/// One recorded burst.
+struct BurstRecord {
+    at: Instant,
+    counts: HashMap<String, usize>,
+}
+
+/// Statistics state of a deserializer: settings, area dimensions, the latest burst and the
+/// firing-rate window.
+pub(crate) struct ActivityStats {
+    pub enabled: bool,
+    window_bursts: usize,
+    /// Dimensions per cortical ID, for active_fraction
+    dimensions: HashMap<String, Vector3>,
+    latest: Vec<(String, AreaStats)>,
+    window: VecDeque<BurstRecord>,
+}
+
+impl Default for ActivityStats {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window_bursts: DEFAULT_WINDOW_BURSTS,
+            dimensions: HashMap::new(),
+            latest: Vec::new(),
+            window: VecDeque::new(),
+        }
+    }
+}
+
+impl ActivityStats {
+    pub fn window_bursts(&self) -> usize {
+        self.window_bursts
+    }
+
+    pub fn set_window_bursts(&mut self, bursts: usize) {
+        self.window_bursts = bursts.max(1);
+        while self.window.len() > self.window_bursts {
+            self.window.pop_front();
+        }
+    }
+
+    pub fn set_dimensions(&mut self, cortical_id: &str, dimensions: Vector3) {
+        self.dimensions.insert(cortical_id.to_string(), dimensions);
+    }
+
+    pub fn dimensions(&self, cortical_id: &str) -> Option<Vector3> {
+        self.dimensions.get(cortical_id).copied()
+    }
+
+    /// Forget the latest burst and the firing-rate window (dimensions are kept).
+    pub fn reset(&mut self) {
+        self.latest.clear();
+        self.window.clear();
+    }
+
+    /// Record one burst's per-area statistics into the firing-rate window.
+    pub fn record_burst(&mut self, areas: Vec<(String, AreaStats)>) {
+        let counts = areas
+            .iter()
+            .map(|(cortical_id, stats)| (cortical_id.clone(), stats.count))
+            .collect();
+        self.window.push_back(BurstRecord {
+            at: Instant::now(),
+            counts,
+        });
+        while self.window.len() > self.window_bursts {
+            self.window.pop_front();
+        }
+        self.latest = areas;
+    }
+
+    /// Per-area statistics of some burst as a Dictionary[cortical_id -> Dictionary].
+    pub fn areas_to_dictionary(&self, areas: &[(String, AreaStats)]) -> Dictionary {
+        let mut dict = Dictionary::new();
+        for (cortical_id, stats) in areas {
+            dict.set(
+                cortical_id.as_str(),
+                stats.to_dictionary(self.dimensions(cortical_id)),
+            );
+        }
+        dict
+    }
+
+    /// Latest recorded burst plus rolling rates for every area seen in the window.
+    ///
+    /// Each area Dictionary holds the fields of `AreaStats::to_dictionary` (zeros for areas
+    /// silent in the latest burst) and:
+    ///   - rate_per_burst: mean firing count per burst over the window
+    ///   - rate_hz: firings per second over the window (0 until two bursts were recorded)
+    ///   - neuron_rate_per_burst: rate_per_burst / area volume (-1.0 if dimensions unknown)
+    pub fn to_dictionary(&self) -> Dictionary {
+        let mut totals: HashMap<&str, usize> = HashMap::new();
+        for burst in &self.window {
+            for (cortical_id, count) in &burst.counts {
+                *totals.entry(cortical_id.as_str()).or_insert(0) += count;
+            }
+        }
+        let bursts = self.window.len().max(1) as f64;
+        let burst_interval_s = match (self.window.front(), self.window.back()) {
+            (Some(first), Some(last)) if self.window.len() > 1 => {
+                last.at.duration_since(first.at).as_secs_f64() / (self.window.len() - 1) as f64
+            }
+            _ => 0.0,
+        };
+
+        let mut areas = self.areas_to_dictionary(&self.latest);
+        for (cortical_id, total) in totals {
+            let mut area = match areas.get(cortical_id) {
+                Some(existing) => existing.to::<Dictionary>(),
+                None => AreaStats::default().to_dictionary(self.dimensions(cortical_id)),
+            };
+            let per_burst = total as f64 / bursts;
+            area.set("rate_per_burst", per_burst as f32);
+            let rate_hz = if burst_interval_s > 0.0 {
+                per_burst / burst_interval_s
+            } else {
+                0.0
+            };
+            area.set("rate_hz", rate_hz as f32);
+            let neuron_rate = match self.dimensions(cortical_id) {
+                Some(d) if d.x * d.y * d.z > 0.0 => {
+                    (per_burst / (d.x as f64 * d.y as f64 * d.z as f64)) as f32
+                }
+                _ => -1.0,
+            };
+            area.set("neuron_rate_per_burst", neuron_rate);
+            areas.set(cortical_id, area);
+        }
+
+        let mut dict = Dictionary::new();
+        dict.set("areas", areas);
+        dict.set("window_bursts", self.window_bursts as i32);
+        dict.set("bursts_recorded", self.window.len() as i32);
+        let burst_hz = if burst_interval_s > 0.0 {
+            1.0 / burst_interval_s
+        } else {
+            0.0
+        };
+        dict.set("burst_hz", burst_hz as f32);
+        dict
+    }
+}
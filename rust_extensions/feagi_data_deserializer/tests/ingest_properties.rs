@@ -0,0 +1,126 @@
+//! Property tests of the packet ingest path shared by every GDExtension decode method.
+//!
+//! Run with `cargo test --features fuzzing`.
+
+use feagi_data_deserializer::fuzzing::{encode_packet, ingest_packet, split_bulk_columns, Limits};
+use proptest::prelude::*;
+
+/// ERROR_OVERSIZED_PAYLOAD
+const ERROR_OVERSIZED_PAYLOAD: i32 = 5;
+
+/// Core cortical IDs: base64 of "___power", "___death" and "___fatig"
+const CORTICAL_IDS: [&str; 3] = ["X19fcG93ZXI=", "X19fZGVhdGg=", "X19fZmF0aWc="];
+
+type Areas = Vec<(String, Vec<(u32, u32, u32, f32)>)>;
+
+/// Areas with distinct coordinates, so no neurons are merged by the encoder.
+fn areas_strategy() -> impl Strategy<Value = Areas> {
+    prop::sample::subsequence(CORTICAL_IDS.to_vec(), 1..=CORTICAL_IDS.len()).prop_flat_map(|ids| {
+        ids.into_iter()
+            .map(|id| {
+                prop::collection::btree_map((0u32..64, 0u32..64, 0u32..64), 0.0f32..1.0, 1..48)
+                    .prop_map(move |neurons| {
+                        let neurons = neurons
+                            .into_iter()
+                            .map(|((x, y, z), p)| (x, y, z, p))
+                            .collect();
+                        (id.to_string(), neurons)
+                    })
+            })
+            .collect::<Vec<_>>()
+    })
+}
+
+fn expected_summary(areas: &Areas) -> Vec<(String, usize)> {
+    let mut expected: Vec<(String, usize)> =
+        areas.iter().map(|(id, n)| (id.clone(), n.len())).collect();
+    expected.sort();
+    expected
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(128))]
+
+    #[test]
+    fn arbitrary_bytes_never_panic(bytes in prop::collection::vec(any::<u8>(), 0..1024)) {
+        let _ = ingest_packet(&bytes, &Limits::default());
+    }
+
+    #[test]
+    fn arbitrary_bytes_behind_format_bytes_never_panic(
+        first in prop::sample::select(vec![2u8, 3, 11, 0x04]),
+        rest in prop::collection::vec(any::<u8>(), 0..1024),
+    ) {
+        let mut bytes = vec![first];
+        if first == 0x04 {
+            bytes.extend_from_slice(&[0x22, 0x4D, 0x18]);
+        }
+        bytes.extend_from_slice(&rest);
+        let _ = ingest_packet(&bytes, &Limits::default());
+    }
+
+    #[test]
+    fn encoded_packets_round_trip(
+        areas in areas_strategy(),
+        container in any::<bool>(),
+        compression in 0i32..3,
+    ) {
+        let packet = encode_packet(&areas, container, compression).unwrap();
+        let decoded = ingest_packet(&packet, &Limits::default()).unwrap();
+        prop_assert_eq!(decoded.areas, expected_summary(&areas));
+    }
+
+    #[test]
+    fn truncated_and_mutated_packets_never_panic(
+        areas in areas_strategy(),
+        container in any::<bool>(),
+        compression in 0i32..3,
+        cut in any::<prop::sample::Index>(),
+        flips in prop::collection::vec((any::<prop::sample::Index>(), any::<u8>()), 1..8),
+    ) {
+        let mut packet = encode_packet(&areas, container, compression).unwrap();
+        for (index, value) in flips {
+            let i = index.index(packet.len());
+            packet[i] ^= value;
+        }
+        let _ = ingest_packet(&packet, &Limits::default());
+        packet.truncate(cut.index(packet.len()));
+        let _ = ingest_packet(&packet, &Limits::default());
+    }
+
+    #[test]
+    fn frame_limits_are_enforced(areas in areas_strategy(), container in any::<bool>()) {
+        let packet = encode_packet(&areas, container, 0).unwrap();
+        let most_neurons = areas.iter().map(|(_, n)| n.len()).max().unwrap();
+
+        let area_limit = Limits { max_areas_per_frame: areas.len() - 1, ..Limits::default() };
+        prop_assert_eq!(ingest_packet(&packet, &area_limit).unwrap_err().0, ERROR_OVERSIZED_PAYLOAD);
+
+        let neuron_limit = Limits { max_neurons_per_area: most_neurons - 1, ..Limits::default() };
+        prop_assert_eq!(ingest_packet(&packet, &neuron_limit).unwrap_err().0, ERROR_OVERSIZED_PAYLOAD);
+
+        let exact = Limits { max_areas_per_frame: areas.len(), max_neurons_per_area: most_neurons, ..Limits::default() };
+        prop_assert!(ingest_packet(&packet, &exact).is_ok());
+    }
+
+    #[test]
+    fn decompression_ceiling_is_enforced(areas in areas_strategy(), compression in 1i32..3) {
+        let packet = encode_packet(&areas, true, compression).unwrap();
+        let payload_bytes = ingest_packet(&packet, &Limits::default()).unwrap().payload_bytes;
+        let ceiling = Limits { max_decompressed_bytes: payload_bytes - 1, ..Limits::default() };
+        prop_assert!(ingest_packet(&packet, &ceiling).is_err());
+    }
+
+    #[test]
+    fn bulk_offsets_never_panic(
+        neurons in 0usize..16,
+        offsets in prop::collection::vec(any::<i64>(), 0..6),
+        area_count in 0usize..6,
+    ) {
+        let column = vec![0u8; neurons * 4];
+        if let Ok(counts) = split_bulk_columns(&column, &column, &column, &column, area_count, &offsets) {
+            prop_assert_eq!(counts.len(), area_count);
+            prop_assert_eq!(counts.iter().sum::<usize>(), if area_count == 0 { 0 } else { neurons });
+        }
+    }
+}
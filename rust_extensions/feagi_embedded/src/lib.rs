@@ -20,22 +20,52 @@
 //! feagi.start()
 //!
 //! func _process(delta):
+//!     feagi.poll_visualization()  # Emits visualization_data with the latest burst
 //!     var neurons = feagi.get_neuron_count()
 //!     var running = feagi.is_running()
 //! ```
 
+mod lifecycle;
+mod npu_io;
+mod settings;
+mod visualization;
+
 use godot::prelude::*;
-use godot::classes::{RefCounted, IRefCounted};
+use godot::classes::{RefCounted, IRefCounted, ProjectSettings};
 use feagi::{FeagiInstance, FeagiConfig};
 use std::sync::{Arc, Mutex, OnceLock};
 use std::io::Write;
 use crossbeam_channel::{unbounded, Sender, Receiver};
+use visualization::{FrameExchange, VisualizationFrame};
+use settings::FeagiEmbeddedSettings;
+use lifecycle::{ConfigSource, FeagiState, Lifecycle, StateEvent};
+use std::time::Duration;
+use feagi_structures::neuron_voxels::xyzp::CorticalMappedXYZPNeuronVoxels;
 
 struct FeagiEmbeddedLib;
 
 #[gdextension]
 unsafe impl ExtensionLibrary for FeagiEmbeddedLib {}
 
+/// Deserializer method that writes a visualization packet into MultiMeshes
+const APPLY_TO_MULTIMESHES: &str = "apply_type11_packet_to_multimeshes";
+
+/// MultiMeshes that poll_visualization() writes into instead of emitting the signal
+struct VisualizationTarget {
+    /// FeagiDataDeserializer (or any object with apply_type11_packet_to_multimeshes)
+    deserializer: Gd<Object>,
+    multimeshes_by_id: Dictionary,
+    dimensions_by_id: Dictionary,
+}
+
+/// Outcome of a genome swap
+struct GenomeSwap {
+    /// Neuron and synapse counts of the new genome, or why it was not loaded
+    loaded: Result<(i64, i64), String>,
+    /// Why a burst engine that was running before the swap is stopped now
+    restart_error: Option<String>,
+}
+
 /// Global log channel for thread-safe logging
 /// Worker threads send logs here, main thread polls via poll_logs()
 static LOG_CHANNEL: OnceLock<(Sender<String>, Receiver<String>)> = OnceLock::new();
@@ -57,6 +87,31 @@ pub struct FeagiEmbedded {
     
     /// FEAGI instance (wrapped in Arc<Mutex> for thread-safe access)
     instance: Arc<Mutex<Option<FeagiInstance>>>,
+    
+    /// Latest visualization packet, filled by the burst engine thread
+    visualization: Arc<FrameExchange>,
+    
+    /// Reused decode buffers for the visualization_data signal
+    visualization_frame: VisualizationFrame,
+    
+    /// Registered MultiMeshes (None = emit visualization_data)
+    visualization_target: Option<VisualizationTarget>,
+    
+    /// Last visualization error, logged once until it changes
+    visualization_error: Option<String>,
+    visualization_errors: u64,
+    
+    /// Initialization / shutdown worker
+    lifecycle: Lifecycle,
+    
+    /// Current lifecycle state (main-thread view, updated by poll_state())
+    state: FeagiState,
+    
+    /// Error text of the last Failed state
+    last_error: String,
+    
+    /// How long shutdown may take before it is reported as failed
+    shutdown_timeout_ms: i64,
 }
 
 #[godot_api]
@@ -74,6 +129,15 @@ impl IRefCounted for FeagiEmbedded {
         Self {
             base,
             instance: Arc::new(Mutex::new(None)),
+            visualization: Arc::new(FrameExchange::new()),
+            visualization_frame: VisualizationFrame::default(),
+            visualization_target: None,
+            visualization_error: None,
+            visualization_errors: 0,
+            lifecycle: Lifecycle::new(),
+            state: FeagiState::Uninitialized,
+            last_error: String::new(),
+            shutdown_timeout_ms: 10_000,
         }
     }
 }
@@ -84,11 +148,15 @@ impl FeagiEmbedded {
     // ============ SIGNALS ============
     //
     
-    /// Emitted when visualization data is available (every burst cycle)
+    /// Emitted by `poll_visualization()` with the latest burst's fired neurons
+    /// 
+    /// Neurons of `cortical_ids[i]` are the next `neuron_counts[i]` entries of
+    /// `x`, `y`, `z` and `powers`.
     /// 
     /// # Arguments
     /// 
-    /// * `cortical_ids` - Array of cortical area IDs (e.g., ["iic100", "ogaz00"])
+    /// * `cortical_ids` - Cortical area IDs (base64), one per area with activity
+    /// * `neuron_counts` - Number of fired neurons of each area
     /// * `x` - X coordinates of fired neurons
     /// * `y` - Y coordinates of fired neurons
     /// * `z` - Z coordinates of fired neurons
@@ -96,21 +164,74 @@ impl FeagiEmbedded {
     /// 
     /// # Note
     /// 
-    /// Currently, this signal is not connected as PNS callback support is pending.
-    /// Visualization data is published via WebSocket (port 9050).
+    /// Not emitted while a visualization target is registered
+    /// (see `set_visualization_target()`).
     #[signal]
     fn visualization_data(
         cortical_ids: PackedStringArray,
+        neuron_counts: PackedInt32Array,
         x: PackedInt32Array,
         y: PackedInt32Array,
         z: PackedInt32Array,
         powers: PackedFloat32Array,
     );
     
+    /// Emitted by `poll_state()` on every lifecycle state change
+    /// 
+    /// Also emitted with `state == previous` when the progress detail of a
+    /// running initialization or shutdown changes.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `state` - New state (one of the STATE_* constants)
+    /// * `previous` - Previous state
+    /// * `detail` - Progress detail (e.g. "Starting services (HTTP API, WebSocket transports)")
+    /// * `error` - Error text when `state` is STATE_FAILED (names the subsystem for shutdown)
+    #[signal]
+    fn state_changed(state: i32, previous: i32, detail: GString, error: GString);
+    
+    /// Emitted after every genome load attempt
+    /// 
+    /// # Arguments
+    /// 
+    /// * `success` - Whether the genome was loaded
+    /// * `load_ms` - Time spent loading, including neuroembryogenesis
+    /// * `neuron_count` - Neurons after the load (0 on failure)
+    /// * `synapse_count` - Synapses after the load (0 on failure)
+    /// * `error` - Error text; also set on success if the burst engine could not be
+    ///   restarted after the swap (the state is then STATE_READY)
+    #[signal]
+    fn genome_loaded(
+        success: bool,
+        load_ms: f64,
+        neuron_count: i64,
+        synapse_count: i64,
+        error: GString,
+    );
+    
     //
     // ============ LIFECYCLE ============
     //
     
+    /// No FEAGI instance
+    #[constant]
+    const STATE_UNINITIALIZED: i32 = 0;
+    /// Initialization running on the worker thread
+    #[constant]
+    const STATE_INITIALIZING: i32 = 1;
+    /// Initialized, burst engine stopped
+    #[constant]
+    const STATE_READY: i32 = 2;
+    /// Burst engine running
+    #[constant]
+    const STATE_RUNNING: i32 = 3;
+    /// Shutdown running on the worker thread
+    #[constant]
+    const STATE_STOPPING: i32 = 4;
+    /// Initialization or shutdown failed (see `get_last_error()`)
+    #[constant]
+    const STATE_FAILED: i32 = 5;
+    
     /// Initialize FEAGI with default embedded configuration
     /// 
     /// Uses sensible defaults for desktop mode:
@@ -120,6 +241,8 @@ impl FeagiEmbedded {
     /// - GPU: Auto-detect
     /// - Debug logging: ENABLED
     /// 
+    /// Blocks until FEAGI is ready; use `initialize_default_async()` to keep the UI responsive.
+    /// 
     /// # Returns
     /// 
     /// `true` if initialization succeeded, `false` otherwise
@@ -132,41 +255,70 @@ impl FeagiEmbedded {
         godot_print!("📝 Initializing FEAGI with embedded defaults...");
         godot_print!("   Note: Logging is initialized by FeagiInstance::new() automatically");
         
-        let config = Self::create_embedded_config();
-        
-        eprintln!("[GDX-INIT] Calling FeagiInstance::new()...");
-        match FeagiInstance::new(config) {
-            Ok(mut feagi) => {
-                eprintln!("[GDX-INIT] ✅ FeagiInstance created, calling initialize()...");
-                match feagi.initialize() {
-                    Ok(_) => {
-                        eprintln!("[GDX-INIT] ✅ initialize() returned Ok");
-
-                        godot_print!("✅ FEAGI initialized successfully");
-                        godot_print!("   HTTP API: {}", feagi.get_api_url());
-                        godot_print!("   Use HTTP API for: genome loading, analytics, settings");
-                        godot_print!("   Use FFI methods for: start/stop, stats, real-time control");
-                        
-                        *self.instance.lock().unwrap() = Some(feagi);
-                        true
-                    }
-                    Err(e) => {
-                        eprintln!("[GDX-INIT] ❌ initialize() failed: {}", e);
-                        godot_error!("❌ FEAGI initialization failed: {}", e);
-                        false
-                    }
-                }
-            }
-            Err(e) => {
-                eprintln!("[GDX-INIT] ❌ FeagiInstance::new() failed: {}", e);
-                godot_error!("❌ Failed to create FEAGI instance: {}", e);
-                false
-            }
+        self.initialize_blocking(ConfigSource::Config(Self::create_embedded_config()))
+    }
+    
+    /// Start initializing FEAGI with default embedded configuration on a worker thread
+    /// 
+    /// Same defaults as `initialize_default()`. Progress is reported through
+    /// `state_changed` (call `poll_state()` from `_process`), ending in STATE_READY
+    /// or STATE_FAILED.
+    /// 
+    /// # Returns
+    /// 
+    /// `true` if initialization was started, `false` if FEAGI is already initialized
+    /// or busy
+    #[func]
+    fn initialize_default_async(&mut self) -> bool {
+        godot_print!("📝 Initializing FEAGI with embedded defaults (background)...");
+        self.begin_initialize(ConfigSource::Config(Self::create_embedded_config()))
+    }
+    
+    /// Initialize FEAGI from a settings resource
+    /// 
+    /// Picks free ports first if `auto_assign_ports` is set (the chosen ports are
+    /// written back to `settings`), then validates the settings. Nothing is started
+    /// if validation fails. Blocks until FEAGI is ready.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `settings` - FeagiEmbeddedSettings (defaults match `initialize_default()`)
+    /// 
+    /// # Returns
+    /// 
+    /// `true` if initialization succeeded, `false` otherwise
+    #[func]
+    fn initialize_with_settings(&mut self, settings: Gd<FeagiEmbeddedSettings>) -> bool {
+        godot_print!("📝 Initializing FEAGI from settings...");
+        match Self::prepare_settings(settings) {
+            Some(config) => self.initialize_blocking(ConfigSource::Config(config)),
+            None => false,
+        }
+    }
+    
+    /// Start initializing FEAGI from a settings resource on a worker thread
+    /// 
+    /// Settings are validated before the worker starts; see `initialize_with_settings()`
+    /// and `initialize_default_async()`.
+    /// 
+    /// # Returns
+    /// 
+    /// `true` if initialization was started, `false` on invalid settings or if FEAGI
+    /// is already initialized or busy
+    #[func]
+    fn initialize_with_settings_async(&mut self, settings: Gd<FeagiEmbeddedSettings>) -> bool {
+        godot_print!("📝 Initializing FEAGI from settings (background)...");
+        match Self::prepare_settings(settings) {
+            Some(config) => self.begin_initialize(ConfigSource::Config(config)),
+            None => false,
         }
     }
     
     /// Initialize FEAGI from a configuration file
     /// 
+    /// Blocks until FEAGI is ready; use `initialize_from_config_async()` to keep the
+    /// UI responsive.
+    /// 
     /// # Arguments
     /// 
     /// * `config_path` - Path to feagi_configuration.toml
@@ -178,36 +330,158 @@ impl FeagiEmbedded {
     fn initialize_from_config(&mut self, config_path: GString) -> bool {
         let path = config_path.to_string();
         godot_print!("📝 Loading FEAGI configuration from: {}", path);
-        
-        match Self::initialize_internal(&path) {
-            Ok(feagi) => {
-                godot_print!("✅ FEAGI initialized from config");
-                godot_print!("   HTTP API: {}", feagi.get_api_url());
-                *self.instance.lock().unwrap() = Some(feagi);
-                true
-            }
-            Err(e) => {
-                godot_error!("❌ FEAGI initialization failed: {}", e);
-                false
-            }
-        }
+        self.initialize_blocking(ConfigSource::File(path))
+    }
+    
+    /// Start initializing FEAGI from a configuration file on a worker thread
+    /// 
+    /// The file is read on the worker; see `initialize_default_async()`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `config_path` - Path to feagi_configuration.toml
+    /// 
+    /// # Returns
+    /// 
+    /// `true` if initialization was started, `false` if FEAGI is already initialized
+    /// or busy
+    #[func]
+    fn initialize_from_config_async(&mut self, config_path: GString) -> bool {
+        let path = config_path.to_string();
+        godot_print!("📝 Loading FEAGI configuration from: {} (background)", path);
+        self.begin_initialize(ConfigSource::File(path))
     }
     
     /// Shutdown FEAGI gracefully
     /// 
     /// Stops burst engine, closes streams, and releases resources.
-    /// Call this before exiting the application.
+    /// Call this before exiting the application. Blocks for at most the shutdown
+    /// timeout (see `set_shutdown_timeout_ms()`), including the time spent waiting
+    /// for a running initialization to finish first. If initialization does not
+    /// finish in time it is cancelled: the state becomes STATE_FAILED now, and the
+    /// initialization worker shuts its instance down itself once it is created
+    /// (reported through `poll_state()` as STATE_STOPPING, then STATE_UNINITIALIZED).
+    /// 
+    /// # Returns
+    /// 
+    /// `true` if FEAGI shut down cleanly (or was not initialized), `false` if
+    /// initialization or a subsystem did not stop in time or failed to stop
+    /// (see `get_last_error()`)
     #[func]
-    fn shutdown(&self) {
+    fn shutdown(&mut self) -> bool {
         godot_print!("🛑 Shutting down FEAGI...");
-        let instance = self.instance.lock().unwrap();
-        if let Some(ref feagi) = *instance {
-            if let Err(e) = feagi.shutdown() {
-                godot_error!("❌ FEAGI shutdown error: {}", e);
-            } else {
-                godot_print!("✅ FEAGI shutdown complete");
+        let timeout = self.shutdown_timeout();
+        let started = std::time::Instant::now();
+        
+        // An instance being created must be shut down too
+        let initializing = self.lifecycle.is_working() && self.state == FeagiState::Initializing;
+        let finished = self.lifecycle.wait(Some(timeout));
+        if !finished && initializing {
+            // The worker shuts the instance down itself once it is created, unless it
+            // was published between the wait and the cancel
+            if !self.lifecycle.cancel_initialize(&self.instance) {
+                self.poll_state();
+                self.set_state(
+                    FeagiState::Failed,
+                    "Shutdown timed out in initialization",
+                    &format!(
+                        "initialization did not finish within {}ms; it will shut its instance down when it does",
+                        self.shutdown_timeout_ms
+                    ),
+                );
+                return false;
             }
+        } else if !finished {
+            self.poll_state();
+            return false;
+        }
+        self.poll_state();
+        
+        let remaining = timeout.saturating_sub(started.elapsed());
+        if !self.begin_shutdown(remaining) {
+            return self.state != FeagiState::Failed;
+        }
+        self.lifecycle.wait(Some(remaining));
+        self.poll_state();
+        self.state == FeagiState::Uninitialized
+    }
+    
+    /// Start shutting FEAGI down on a worker thread
+    /// 
+    /// Progress is reported through `state_changed`, one detail per subsystem,
+    /// ending in STATE_UNINITIALIZED, or STATE_FAILED naming the subsystem that failed
+    /// to stop or was still stopping when the shutdown timeout passed.
+    /// 
+    /// # Returns
+    /// 
+    /// `true` if shutdown was started, `false` if FEAGI is not initialized or busy
+    #[func]
+    fn shutdown_async(&mut self) -> bool {
+        if self.lifecycle.is_working() {
+            godot_error!("❌ FEAGI is {}; wait for it to finish", self.state.as_str());
+            return false;
+        }
+        godot_print!("🛑 Shutting down FEAGI (background)...");
+        self.begin_shutdown(self.shutdown_timeout())
+    }
+    
+    /// How long shutdown may take before it is reported as failed
+    /// 
+    /// # Arguments
+    /// 
+    /// * `timeout_ms` - Milliseconds (at least 1, default 10000)
+    #[func]
+    fn set_shutdown_timeout_ms(&mut self, timeout_ms: i64) {
+        self.shutdown_timeout_ms = timeout_ms.max(1);
+    }
+    
+    /// Shutdown timeout in milliseconds
+    #[func]
+    fn get_shutdown_timeout_ms(&self) -> i64 {
+        self.shutdown_timeout_ms
+    }
+    
+    /// Emit `state_changed` for everything the worker reported since the last call
+    /// 
+    /// **Call this from `_process(delta)`** while an async initialization or shutdown
+    /// is running (it is cheap when nothing happened). Also reports a shutdown that
+    /// passed its timeout.
+    /// 
+    /// # Returns
+    /// 
+    /// Current state (one of the STATE_* constants)
+    #[func]
+    fn poll_state(&mut self) -> i32 {
+        while let Some(event) = self.lifecycle.try_next() {
+            self.apply_state_event(event);
+        }
+        if let Some(event) = self.lifecycle.poll_timeout() {
+            self.apply_state_event(event);
         }
+        self.state.as_i32()
+    }
+    
+    /// Current lifecycle state as of the last `poll_state()`
+    /// 
+    /// # Returns
+    /// 
+    /// One of the STATE_* constants
+    #[func]
+    fn get_state(&self) -> i32 {
+        self.state.as_i32()
+    }
+    
+    /// Current lifecycle state name ("uninitialized", "initializing", "ready",
+    /// "running", "stopping" or "failed")
+    #[func]
+    fn get_state_name(&self) -> GString {
+        GString::from(self.state.as_str())
+    }
+    
+    /// Error text of the last failed initialization or shutdown (empty if none)
+    #[func]
+    fn get_last_error(&self) -> GString {
+        GString::from(self.last_error.as_str())
     }
     
     /// Poll and drain log messages from worker threads
@@ -243,6 +517,247 @@ impl FeagiEmbedded {
         }
     }
     
+    //
+    // ============ VISUALIZATION (Hot Path - In-Process) ============
+    //
+    
+    /// Hand the latest burst's visualization to Godot
+    /// 
+    /// Call this from `_process(delta)`. Takes the newest packet the burst engine
+    /// published since the last call (older unread bursts are dropped) and either
+    /// writes it into the registered MultiMeshes or emits `visualization_data`.
+    /// No WebSocket is involved. Builds without the `visualization-callback` feature
+    /// never receive a burst (see `get_capabilities()`).
+    /// 
+    /// # Returns
+    /// 
+    /// `true` if a new burst was applied or emitted, `false` if there was none
+    /// or it could not be decoded
+    /// 
+    /// # Example
+    /// 
+    /// ```gdscript
+    /// func _process(delta):
+    ///     feagi_embedded.poll_visualization()
+    /// ```
+    #[func]
+    fn poll_visualization(&mut self) -> bool {
+        let exchange = Arc::clone(&self.visualization);
+        let result = exchange.take(|packet| match self.visualization_target.as_mut() {
+            Some(target) => Self::apply_to_target(target, packet),
+            None => self.emit_visualization(packet),
+        });
+        match result {
+            None => false,
+            Some(Ok(())) => {
+                self.visualization_error = None;
+                true
+            }
+            Some(Err(e)) => {
+                self.visualization_errors += 1;
+                if self.visualization_error.as_deref() != Some(e.as_str()) {
+                    godot_error!("❌ Visualization error: {}", e);
+                    self.visualization_error = Some(e);
+                }
+                false
+            }
+        }
+    }
+    
+    /// Write visualization straight into MultiMeshes instead of emitting the signal
+    /// 
+    /// `poll_visualization()` passes each burst to the deserializer's
+    /// `apply_type11_packet_to_multimeshes()` with `clear_all_before_apply = true`,
+    /// so placement, coloring and LOD settings of that deserializer apply.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `deserializer` - FeagiDataDeserializer instance
+    /// * `multimeshes_by_id` - Cortical ID -> MultiMesh
+    /// * `dimensions_by_id` - Cortical ID -> Vector3i dimensions
+    /// 
+    /// # Returns
+    /// 
+    /// `true` if the target was registered, `false` if `deserializer` cannot apply packets
+    #[func]
+    fn set_visualization_target(
+        &mut self,
+        deserializer: Gd<Object>,
+        multimeshes_by_id: Dictionary,
+        dimensions_by_id: Dictionary,
+    ) -> bool {
+        if !deserializer.has_method(APPLY_TO_MULTIMESHES) {
+            godot_error!(
+                "❌ Visualization target {} has no {}() method",
+                deserializer.get_class(),
+                APPLY_TO_MULTIMESHES
+            );
+            return false;
+        }
+        self.visualization_target = Some(VisualizationTarget {
+            deserializer,
+            multimeshes_by_id,
+            dimensions_by_id,
+        });
+        true
+    }
+    
+    /// Stop writing into MultiMeshes; `poll_visualization()` emits `visualization_data` again
+    #[func]
+    fn clear_visualization_target(&mut self) {
+        self.visualization_target = None;
+    }
+    
+    /// Visualization hand-off counters
+    /// 
+    /// # Returns
+    /// 
+    /// Dictionary with `published` (bursts from the engine), `consumed` (bursts
+    /// taken by `poll_visualization()`), `dropped` (bursts replaced before a poll),
+    /// `errors` (bursts that failed to decode or apply) and `target_registered`
+    #[func]
+    fn get_visualization_stats(&self) -> Dictionary {
+        let mut stats = Dictionary::new();
+        stats.set("published", self.visualization.published() as i64);
+        stats.set("consumed", self.visualization.consumed() as i64);
+        stats.set("dropped", self.visualization.overwritten() as i64);
+        stats.set("errors", self.visualization_errors as i64);
+        stats.set("target_registered", self.visualization_target.is_some());
+        stats
+    }
+    
+    //
+    // ============ SENSORY / MOTOR (Hot Path - FFI) ============
+    //
+    
+    /// Push sensory voxels of one cortical area into the next burst
+    /// 
+    /// Goes straight to the burst engine; nothing is sent over the sensory WebSocket.
+    /// Needs the `sensorimotor-ffi` feature (see `get_capabilities()`).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `cortical_id` - IPU cortical area ID (base64 or legacy, e.g. "iic100")
+    /// * `x` / `y` / `z` - Voxel coordinates (same length, non-negative)
+    /// * `p` - Potential of each voxel
+    /// 
+    /// # Returns
+    /// 
+    /// `true` if the voxels were queued, `false` on invalid input, if FEAGI is not initialized
+    /// or if built without `sensorimotor-ffi`
+    #[func]
+    fn push_sensory_data(
+        &self,
+        cortical_id: GString,
+        x: PackedInt32Array,
+        y: PackedInt32Array,
+        z: PackedInt32Array,
+        p: PackedFloat32Array,
+    ) -> bool {
+        let mut frame = CorticalMappedXYZPNeuronVoxels::new();
+        if let Err(e) = npu_io::stage_area(
+            &mut frame,
+            &cortical_id.to_string(),
+            x.as_slice(),
+            y.as_slice(),
+            z.as_slice(),
+            p.as_slice(),
+        ) {
+            godot_error!("❌ {}", e);
+            return false;
+        }
+        self.inject_sensory_frame(&frame).is_some()
+    }
+    
+    /// Push sensory voxels of several cortical areas into the next burst at once
+    /// 
+    /// # Arguments
+    /// 
+    /// * `areas` - Cortical ID -> `{"x": PackedInt32Array, "y": ..., "z": ..., "p": PackedFloat32Array}`
+    /// 
+    /// # Returns
+    /// 
+    /// Number of neurons the voxels were mapped to, or -1 on invalid input, if FEAGI
+    /// is not initialized or if built without `sensorimotor-ffi` (nothing is queued then)
+    #[func]
+    fn push_sensory_areas(&self, areas: Dictionary) -> i64 {
+        let mut frame = CorticalMappedXYZPNeuronVoxels::new();
+        for (key, value) in areas.iter_shared() {
+            let cortical_id = key.to_string();
+            let staged = value
+                .try_to::<Dictionary>()
+                .map_err(|_| format!("Area {}: expected a Dictionary with x, y, z and p", cortical_id))
+                .and_then(|area| {
+                    let x = Self::dict_array::<PackedInt32Array>(&area, "x", &cortical_id)?;
+                    let y = Self::dict_array::<PackedInt32Array>(&area, "y", &cortical_id)?;
+                    let z = Self::dict_array::<PackedInt32Array>(&area, "z", &cortical_id)?;
+                    let p = Self::dict_array::<PackedFloat32Array>(&area, "p", &cortical_id)?;
+                    npu_io::stage_area(
+                        &mut frame,
+                        &cortical_id,
+                        x.as_slice(),
+                        y.as_slice(),
+                        z.as_slice(),
+                        p.as_slice(),
+                    )
+                });
+            if let Err(e) = staged {
+                godot_error!("❌ {}", e);
+                return -1;
+            }
+        }
+        self.inject_sensory_frame(&frame).map_or(-1, |neurons| neurons as i64)
+    }
+    
+    /// Read the motor (OPU) activations of the latest burst
+    /// 
+    /// Reads the burst engine's last output directly; nothing is received over the
+    /// motor WebSocket. Poll this once per frame (or per physics tick) and compare
+    /// `burst` to skip bursts that were already handled. Builds without the
+    /// `sensorimotor-ffi` feature always report `burst == -1`.
+    /// 
+    /// # Returns
+    /// 
+    /// Dictionary with `burst` (int, -1 if there is no motor output yet) and `areas`
+    /// (cortical ID -> `{"x": PackedInt32Array, "y": ..., "z": ..., "p": PackedFloat32Array}`)
+    #[func]
+    fn get_motor_data(&self) -> Dictionary {
+        let mut result = Dictionary::new();
+        let mut areas = Dictionary::new();
+        let snapshot = {
+            let instance = self.instance.lock().unwrap();
+            instance.as_ref().and_then(npu_io::latest_motor)
+        };
+        match snapshot {
+            Some(snapshot) => {
+                for (cortical_id, neurons) in snapshot.neuron_data.mappings.iter() {
+                    let n = neurons.len();
+                    let mut x = Vec::with_capacity(n);
+                    let mut y = Vec::with_capacity(n);
+                    let mut z = Vec::with_capacity(n);
+                    let mut p = Vec::with_capacity(n);
+                    for neuron in neurons.iter() {
+                        let coordinate = neuron.neuron_voxel_coordinate;
+                        x.push(coordinate.x as i32);
+                        y.push(coordinate.y as i32);
+                        z.push(coordinate.z as i32);
+                        p.push(neuron.potential);
+                    }
+                    let mut area = Dictionary::new();
+                    area.set("x", PackedInt32Array::from(x.as_slice()));
+                    area.set("y", PackedInt32Array::from(y.as_slice()));
+                    area.set("z", PackedInt32Array::from(z.as_slice()));
+                    area.set("p", PackedFloat32Array::from(p.as_slice()));
+                    areas.set(cortical_id.as_base_64(), area);
+                }
+                result.set("burst", snapshot.burst as i64);
+            }
+            None => result.set("burst", -1),
+        }
+        result.set("areas", areas);
+        result
+    }
+    
     //
     // ============ BURST ENGINE CONTROL (Hot Path - FFI) ============
     //
@@ -255,12 +770,14 @@ impl FeagiEmbedded {
     /// 
     /// `true` if burst engine started successfully, `false` otherwise
     #[func]
-    fn start(&self) -> bool {
+    fn start(&mut self) -> bool {
         let instance = self.instance.lock().unwrap();
         if let Some(ref feagi) = *instance {
             match feagi.start() {
                 Ok(_) => {
                     godot_print!("▶️  FEAGI burst engine started");
+                    drop(instance);
+                    self.set_state(FeagiState::Running, "Burst engine started", "");
                     true
                 }
                 Err(e) => {
@@ -282,12 +799,14 @@ impl FeagiEmbedded {
     /// 
     /// `true` if burst engine stopped successfully, `false` otherwise
     #[func]
-    fn stop(&self) -> bool {
+    fn stop(&mut self) -> bool {
         let instance = self.instance.lock().unwrap();
         if let Some(ref feagi) = *instance {
             match feagi.stop() {
                 Ok(_) => {
                     godot_print!("⏸️  FEAGI burst engine stopped");
+                    drop(instance);
+                    self.set_state(FeagiState::Ready, "Burst engine stopped", "");
                     true
                 }
                 Err(e) => {
@@ -330,6 +849,82 @@ impl FeagiEmbedded {
         }
     }
     
+    //
+    // ============ GENOME (FFI) ============
+    //
+    
+    /// Load a genome from JSON text, replacing the current one
+    /// 
+    /// If the burst engine is running it is stopped for the swap and restarted
+    /// afterwards. Emits `genome_loaded` with timing and counts. Needs the `genome-ffi`
+    /// feature (see `get_capabilities()`).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `genome_json` - Genome JSON (e.g. the contents of `circuits/*/genome.json`)
+    /// 
+    /// # Returns
+    /// 
+    /// `true` if the genome was loaded, `false` otherwise
+    #[func]
+    fn load_genome_from_json(&mut self, genome_json: GString) -> bool {
+        self.load_genome("JSON text", &genome_json.to_string())
+    }
+    
+    /// Load a genome from a JSON file, replacing the current one
+    /// 
+    /// Same as `load_genome_from_json()` with the file contents.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - Genome file (absolute, `res://` or `user://`)
+    /// 
+    /// # Returns
+    /// 
+    /// `true` if the genome was loaded, `false` otherwise
+    #[func]
+    fn load_genome_from_file(&mut self, path: GString) -> bool {
+        let path = ProjectSettings::singleton().globalize_path(&path).to_string();
+        match std::fs::read_to_string(&path) {
+            Ok(genome_json) => self.load_genome(&path, &genome_json),
+            Err(e) => {
+                let error = format!("Failed to read genome {}: {}", path, e);
+                godot_error!("❌ {}", error);
+                self.emit_genome_loaded(false, 0.0, 0, 0, &error);
+                false
+            }
+        }
+    }
+    
+    /// Export the running genome as JSON
+    /// 
+    /// # Returns
+    /// 
+    /// Genome JSON, or an empty string if no genome is loaded, export failed or the
+    /// build lacks the `genome-ffi` feature
+    #[func]
+    fn export_genome(&self) -> GString {
+        let instance = self.instance.lock().unwrap();
+        let Some(ref feagi) = *instance else {
+            godot_error!("❌ FEAGI not initialized. Call initialize() first.");
+            return GString::new();
+        };
+        #[cfg(feature = "genome-ffi")]
+        match feagi.export_genome_json() {
+            Ok(genome_json) => GString::from(genome_json.as_str()),
+            Err(e) => {
+                godot_error!("❌ Genome export failed: {}", e);
+                GString::new()
+            }
+        }
+        #[cfg(not(feature = "genome-ffi"))]
+        {
+            let _ = feagi;
+            godot_error!("❌ Genome export unavailable (built without the genome-ffi feature)");
+            GString::new()
+        }
+    }
+    
     //
     // ============ REAL-TIME STATS (Hot Path - FFI) ============
     //
@@ -422,11 +1017,36 @@ impl FeagiEmbedded {
         }
     }
     
+    //
+    // ============ BUILD INFO ============
+    //
+    
+    /// Optional FFI paths compiled into this build
+    /// 
+    /// Each needs an upstream feagi-rs API that is behind a cargo feature until
+    /// feagi-rs is pinned to a revision providing it (see the GDExtension README).
+    /// 
+    /// # Returns
+    /// 
+    /// Dictionary of bools: `visualization_callback` (the burst engine publishes to
+    /// `poll_visualization()`), `sensorimotor_ffi` (sensory injection and motor readout)
+    /// and `genome_ffi` (genome load and export)
+    #[func]
+    fn get_capabilities(&self) -> Dictionary {
+        let mut capabilities = Dictionary::new();
+        capabilities.set("visualization_callback", cfg!(feature = "visualization-callback"));
+        capabilities.set("sensorimotor_ffi", cfg!(feature = "sensorimotor-ffi"));
+        capabilities.set("genome_ffi", cfg!(feature = "genome-ffi"));
+        capabilities
+    }
+    
     //
     // ============ INTERNAL HELPERS ============
     //
     
     /// Create embedded configuration with sensible defaults
+    /// 
+    /// Also the defaults of `FeagiEmbeddedSettings`.
     fn create_embedded_config() -> FeagiConfig {
         use feagi_config::*;
         
@@ -451,16 +1071,268 @@ impl FeagiEmbedded {
         config
     }
     
-    /// Initialize FEAGI from config file
-    fn initialize_internal(config_path: &str) -> anyhow::Result<FeagiInstance> {
-        // Load config from file
-        let config = feagi::load_config(Some(std::path::Path::new(config_path)), None)?;
+    /// Load genome JSON and emit `genome_loaded`
+    /// 
+    /// If the burst engine was running and could not be restarted after the swap, the
+    /// state drops to STATE_READY and the restart error is reported with the load result.
+    fn load_genome(&mut self, source: &str, genome_json: &str) -> bool {
+        godot_print!("🧬 Loading genome from {}...", source);
+        let start = std::time::Instant::now();
+        let swap = {
+            let instance = self.instance.lock().unwrap();
+            match *instance {
+                Some(ref feagi) => Self::swap_genome(feagi, genome_json),
+                None => GenomeSwap {
+                    loaded: Err("FEAGI not initialized. Call initialize() first.".to_string()),
+                    restart_error: None,
+                },
+            }
+        };
+        let load_ms = start.elapsed().as_secs_f64() * 1000.0;
         
-        // Create and initialize FEAGI instance
-        let mut feagi = FeagiInstance::new(config)?;
-        feagi.initialize()?;
+        if let Some(ref restart_error) = swap.restart_error {
+            godot_error!("❌ Burst engine did not restart after the genome load: {}", restart_error);
+            self.set_state(
+                FeagiState::Ready,
+                &format!("Burst engine not restarted after genome load ({})", restart_error),
+                "",
+            );
+        }
+        let restart_note = swap
+            .restart_error
+            .as_ref()
+            .map(|e| format!("burst engine did not restart: {}", e));
         
-        Ok(feagi)
+        match swap.loaded {
+            Ok((neurons, synapses)) => {
+                godot_print!(
+                    "✅ Genome loaded in {:.1}ms ({} neurons, {} synapses)",
+                    load_ms, neurons, synapses
+                );
+                let error = restart_note.unwrap_or_default();
+                self.emit_genome_loaded(true, load_ms, neurons, synapses, &error);
+                true
+            }
+            Err(e) => {
+                let mut error = format!("Genome load failed: {}", e);
+                if let Some(note) = restart_note {
+                    error = format!("{}; {}", error, note);
+                }
+                godot_error!("❌ {}", error);
+                self.emit_genome_loaded(false, load_ms, 0, 0, &error);
+                false
+            }
+        }
+    }
+    
+    /// Replace the genome, pausing the burst engine around the swap
+    /// 
+    /// A burst engine that was running is restarted even after a failed load, so the
+    /// previous genome keeps running.
+    #[cfg(feature = "genome-ffi")]
+    fn swap_genome(feagi: &FeagiInstance, genome_json: &str) -> GenomeSwap {
+        let was_running = feagi.is_running();
+        if was_running {
+            if let Err(e) = feagi.stop() {
+                return GenomeSwap {
+                    loaded: Err(format!("could not stop the burst engine for the swap: {}", e)),
+                    restart_error: None,
+                };
+            }
+        }
+        let loaded = feagi.load_genome_from_json(genome_json).map_err(|e| e.to_string());
+        let restart_error = if was_running {
+            feagi.start().err().map(|e| e.to_string())
+        } else {
+            None
+        };
+        GenomeSwap {
+            loaded: loaded.map(|_| {
+                (
+                    feagi.get_neuron_count().unwrap_or(0) as i64,
+                    feagi.get_synapse_count().unwrap_or(0) as i64,
+                )
+            }),
+            restart_error,
+        }
+    }
+    
+    /// Built without `genome-ffi`: the genome is left as it is
+    #[cfg(not(feature = "genome-ffi"))]
+    fn swap_genome(_feagi: &FeagiInstance, _genome_json: &str) -> GenomeSwap {
+        GenomeSwap {
+            loaded: Err("genome loading unavailable (built without the genome-ffi feature)".to_string()),
+            restart_error: None,
+        }
+    }
+    
+    fn emit_genome_loaded(&mut self, success: bool, load_ms: f64, neurons: i64, synapses: i64, error: &str) {
+        let args = [
+            success.to_variant(),
+            load_ms.to_variant(),
+            neurons.to_variant(),
+            synapses.to_variant(),
+            GString::from(error).to_variant(),
+        ];
+        self.base_mut().emit_signal("genome_loaded", &args);
+    }
+    
+    /// Start the initialization worker
+    /// 
+    /// With the `visualization-callback` feature the worker also registers the burst
+    /// engine callback that fills the visualization exchange (it only copies each
+    /// packet into the back buffer).
+    fn begin_initialize(&mut self, source: ConfigSource) -> bool {
+        if self.lifecycle.is_working() {
+            godot_error!("❌ FEAGI is {}; wait for it to finish", self.state.as_str());
+            return false;
+        }
+        if self.instance.lock().unwrap().is_some() {
+            godot_error!("❌ FEAGI already initialized. Call shutdown() first.");
+            return false;
+        }
+        self.set_state(FeagiState::Initializing, "Starting initialization worker", "");
+        let exchange = Arc::clone(&self.visualization);
+        self.lifecycle.spawn_initialize(
+            move |progress| lifecycle::initialize(source, progress, &exchange),
+            Arc::clone(&self.instance),
+        );
+        true
+    }
+    
+    /// Run the initialization worker and wait for it
+    fn initialize_blocking(&mut self, source: ConfigSource) -> bool {
+        if !self.begin_initialize(source) {
+            return false;
+        }
+        self.lifecycle.wait(None);
+        self.poll_state();
+        self.state == FeagiState::Ready
+    }
+    
+    /// Take the instance out of the shared slot and start the shutdown worker
+    /// 
+    /// # Returns
+    /// 
+    /// `true` if a shutdown was started, `false` if there was no instance
+    fn begin_shutdown(&mut self, timeout: Duration) -> bool {
+        let Some(feagi) = self.instance.lock().unwrap().take() else {
+            return false;
+        };
+        self.set_state(FeagiState::Stopping, "Starting shutdown worker", "");
+        self.lifecycle.spawn_shutdown(feagi, timeout);
+        true
+    }
+    
+    fn shutdown_timeout(&self) -> Duration {
+        Duration::from_millis(self.shutdown_timeout_ms as u64)
+    }
+    
+    /// Pick free ports if requested and validate settings (errors are logged)
+    fn prepare_settings(mut settings: Gd<FeagiEmbeddedSettings>) -> Option<FeagiConfig> {
+        let prepared = settings.bind_mut().prepare();
+        match prepared {
+            Ok(config) => Some(config),
+            Err(problems) => {
+                for problem in &problems {
+                    godot_error!("❌ Invalid FEAGI setting: {}", problem);
+                }
+                None
+            }
+        }
+    }
+    
+    fn apply_state_event(&mut self, event: StateEvent) {
+        self.set_state(event.state, &event.detail, &event.error);
+    }
+    
+    /// Change state, log it and emit `state_changed` (main thread only)
+    fn set_state(&mut self, state: FeagiState, detail: &str, error: &str) {
+        let previous = self.state;
+        self.state = state;
+        if state == FeagiState::Failed {
+            self.last_error = error.to_string();
+            godot_error!("❌ FEAGI {}: {}", detail, error);
+        } else {
+            godot_print!("🔄 FEAGI {}: {}", state.as_str(), detail);
+        }
+        let args = [
+            state.as_i32().to_variant(),
+            previous.as_i32().to_variant(),
+            GString::from(detail).to_variant(),
+            GString::from(error).to_variant(),
+        ];
+        self.base_mut().emit_signal("state_changed", &args);
+    }
+    
+    /// Queue a staged sensory frame in the burst engine
+    /// 
+    /// # Returns
+    /// 
+    /// Number of neurons injected, or None if FEAGI is not initialized or injection failed
+    fn inject_sensory_frame(&self, frame: &CorticalMappedXYZPNeuronVoxels) -> Option<usize> {
+        let instance = self.instance.lock().unwrap();
+        let Some(ref feagi) = *instance else {
+            godot_error!("❌ FEAGI not initialized. Call initialize() first.");
+            return None;
+        };
+        match npu_io::inject_sensory(feagi, frame) {
+            Ok(neurons) => Some(neurons),
+            Err(e) => {
+                godot_error!("❌ {}", e);
+                None
+            }
+        }
+    }
+    
+    /// Typed array stored under `key` of a sensory area Dictionary
+    fn dict_array<T: FromGodot>(area: &Dictionary, key: &str, cortical_id: &str) -> Result<T, String> {
+        area.get(key)
+            .ok_or_else(|| format!("Area {}: missing \"{}\"", cortical_id, key))?
+            .try_to::<T>()
+            .map_err(|_| format!("Area {}: \"{}\" has the wrong array type", cortical_id, key))
+    }
+    
+    /// Decode a packet and emit `visualization_data` (skipped while nothing is connected)
+    fn emit_visualization(&mut self, packet: &[u8]) -> Result<(), String> {
+        if self.base().get_signal_connection_list("visualization_data").is_empty() {
+            return Ok(());
+        }
+        self.visualization_frame.decode(packet)?;
+        let frame = &self.visualization_frame;
+        let cortical_ids: PackedStringArray =
+            frame.cortical_ids.iter().map(|id| GString::from(id.as_str())).collect();
+        let args = [
+            cortical_ids.to_variant(),
+            PackedInt32Array::from(frame.neuron_counts.as_slice()).to_variant(),
+            PackedInt32Array::from(frame.x.as_slice()).to_variant(),
+            PackedInt32Array::from(frame.y.as_slice()).to_variant(),
+            PackedInt32Array::from(frame.z.as_slice()).to_variant(),
+            PackedFloat32Array::from(frame.powers.as_slice()).to_variant(),
+        ];
+        self.base_mut().emit_signal("visualization_data", &args);
+        Ok(())
+    }
+    
+    /// Pass a packet to the registered deserializer's MultiMesh apply
+    fn apply_to_target(target: &mut VisualizationTarget, packet: &[u8]) -> Result<(), String> {
+        let args = [
+            PackedByteArray::from(packet).to_variant(),
+            target.multimeshes_by_id.to_variant(),
+            target.dimensions_by_id.to_variant(),
+            true.to_variant(),
+        ];
+        let result = target
+            .deserializer
+            .call(APPLY_TO_MULTIMESHES, &args)
+            .try_to::<Dictionary>()
+            .map_err(|_| format!("{}() did not return a Dictionary", APPLY_TO_MULTIMESHES))?;
+        if result.get("success").is_some_and(|ok| ok.booleanize()) {
+            Ok(())
+        } else {
+            let error = result.get("error").map(|e| e.to_string()).unwrap_or_default();
+            Err(format!("{}() failed: {}", APPLY_TO_MULTIMESHES, error))
+        }
     }
     
     
@@ -535,7 +1407,20 @@ impl FeagiEmbedded {
 // Implement Drop to ensure cleanup
 impl Drop for FeagiEmbedded {
     fn drop(&mut self) {
-        self.shutdown();
+        // No signals here: the object is going away. A worker that is still busy
+        // (slow initialization, hung shutdown) is given the shutdown timeout; an
+        // initialization that is still running then shuts its own instance down.
+        if !self.lifecycle.wait(Some(self.shutdown_timeout())) {
+            self.lifecycle.cancel_initialize(&self.instance);
+        }
+        let feagi = self.instance.lock().unwrap().take();
+        if let Some(feagi) = feagi {
+            let timeout = self.shutdown_timeout();
+            self.lifecycle.spawn_shutdown(feagi, timeout);
+            if !self.lifecycle.wait(Some(timeout)) {
+                eprintln!("[FEAGI] ⚠️  Shutdown did not finish within {}ms", self.shutdown_timeout_ms);
+            }
+        }
     }
 }
 
@@ -0,0 +1,652 @@
+//! Initialization and shutdown on a worker thread
+//!
+//! Creating FEAGI (HTTP server, transports, connectome state) and tearing it down take
+//! long enough to freeze the Godot UI, so both run on a worker thread. The worker
+//! reports progress as `StateEvent`s over a channel; `FeagiEmbedded.poll_state()`
+//! drains them on the main thread and emits `state_changed`. The worker never calls
+//! into Godot.
+//!
+//! The FEAGI instance is only placed into the shared slot once it is ready, and is
+//! taken out of it before shutdown starts, so main-thread calls never wait on the
+//! worker. A shutdown that gives up waiting for initialization cancels it; the
+//! initialization worker then shuts its instance down instead of publishing it.
+
+use crate::visualization::FrameExchange;
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use feagi::{FeagiConfig, FeagiInstance};
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Lifecycle state of the embedded instance
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeagiState {
+    Uninitialized,
+    Initializing,
+    /// Initialized, burst engine stopped
+    Ready,
+    /// Burst engine running
+    Running,
+    Stopping,
+    /// Initialization or shutdown failed (see the error text)
+    Failed,
+}
+
+impl FeagiState {
+    pub fn as_i32(self) -> i32 {
+        match self {
+            FeagiState::Uninitialized => 0,
+            FeagiState::Initializing => 1,
+            FeagiState::Ready => 2,
+            FeagiState::Running => 3,
+            FeagiState::Stopping => 4,
+            FeagiState::Failed => 5,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FeagiState::Uninitialized => "uninitialized",
+            FeagiState::Initializing => "initializing",
+            FeagiState::Ready => "ready",
+            FeagiState::Running => "running",
+            FeagiState::Stopping => "stopping",
+            FeagiState::Failed => "failed",
+        }
+    }
+}
+
+/// State reported by a worker
+pub struct StateEvent {
+    pub state: FeagiState,
+    /// Progress detail, e.g. "Starting services (HTTP API, WebSocket)"
+    pub detail: String,
+    /// Error text (empty unless the state is Failed)
+    pub error: String,
+}
+
+/// Where the configuration of a new instance comes from
+pub enum ConfigSource {
+    Config(FeagiConfig),
+    /// feagi_configuration.toml, loaded on the worker
+    File(String),
+}
+
+/// Subsystems stopped by shutdown, in order
+const SHUTDOWN_STEPS: [&str; 3] = ["burst_engine", "services", "http_server"];
+
+/// What the shutdown worker needs from an instance
+///
+/// Implemented by `FeagiInstance`; the tests drive the workers with a stand-in.
+pub trait ManagedInstance: Send + 'static {
+    fn is_running(&self) -> bool;
+    /// Stop the burst engine
+    fn stop(&self) -> Result<(), String>;
+    /// Stop services (WebSocket transports, state)
+    fn shutdown(&self) -> Result<(), String>;
+    fn is_http_server_running(&self) -> bool;
+}
+
+impl ManagedInstance for FeagiInstance {
+    fn is_running(&self) -> bool {
+        FeagiInstance::is_running(self)
+    }
+
+    fn stop(&self) -> Result<(), String> {
+        FeagiInstance::stop(self).map_err(|e| e.to_string())
+    }
+
+    fn shutdown(&self) -> Result<(), String> {
+        FeagiInstance::shutdown(self).map_err(|e| e.to_string())
+    }
+
+    fn is_http_server_running(&self) -> bool {
+        FeagiInstance::is_http_server_running(self)
+    }
+}
+
+/// Worker thread and the channel it reports through
+pub struct Lifecycle<T: ManagedInstance = FeagiInstance> {
+    sender: Sender<StateEvent>,
+    receiver: Receiver<StateEvent>,
+    worker: Option<JoinHandle<()>>,
+    /// Subsystem the shutdown worker is stopping (index into SHUTDOWN_STEPS)
+    shutdown_step: Arc<Mutex<usize>>,
+    /// Deadline of the running shutdown
+    shutdown_deadline: Option<(Instant, Duration)>,
+    /// Set when the running initialization must not publish its instance
+    cancel_initialize: Arc<AtomicBool>,
+    instance: PhantomData<fn(T)>,
+}
+
+impl<T: ManagedInstance> Lifecycle<T> {
+    pub fn new() -> Self {
+        let (sender, receiver) = unbounded();
+        Self {
+            sender,
+            receiver,
+            worker: None,
+            shutdown_step: Arc::new(Mutex::new(0)),
+            shutdown_deadline: None,
+            cancel_initialize: Arc::new(AtomicBool::new(false)),
+            instance: PhantomData,
+        }
+    }
+
+    /// Create and initialize an instance on a worker thread
+    ///
+    /// `create` builds the instance and reports progress through the callback it is
+    /// given (see `initialize()`). Reports Initializing (with progress detail), then
+    /// Ready or Failed. The instance is stored in `slot` when ready, unless
+    /// `cancel_initialize()` was called first: then the worker shuts the instance down
+    /// itself and reports Stopping, then Uninitialized or Failed.
+    pub fn spawn_initialize<F>(&mut self, create: F, slot: Arc<Mutex<Option<T>>>)
+    where
+        F: FnOnce(&dyn Fn(&str)) -> anyhow::Result<(T, String)> + Send + 'static,
+    {
+        let sender = self.sender.clone();
+        let step = Arc::clone(&self.shutdown_step);
+        let cancel = Arc::clone(&self.cancel_initialize);
+        cancel.store(false, Ordering::Release);
+        self.worker = Some(std::thread::spawn(move || {
+            let progress = |detail: &str| {
+                let _ = sender.send(StateEvent {
+                    state: FeagiState::Initializing,
+                    detail: detail.to_string(),
+                    error: String::new(),
+                });
+            };
+            let instance = match create(&progress) {
+                Ok((instance, detail)) => {
+                    // Flag and slot change together under the slot lock, so an instance is
+                    // either published (and Ready is queued) or handed back here
+                    let mut slot = slot.lock().unwrap();
+                    if !cancel.load(Ordering::Acquire) {
+                        *slot = Some(instance);
+                        let _ = sender.send(StateEvent {
+                            state: FeagiState::Ready,
+                            detail,
+                            error: String::new(),
+                        });
+                        return;
+                    }
+                    instance
+                }
+                Err(e) => {
+                    eprintln!("[GDX-INIT] ❌ initialization failed: {}", e);
+                    let _ = sender.send(StateEvent {
+                        state: FeagiState::Failed,
+                        detail: "Initialization failed".to_string(),
+                        error: e.to_string(),
+                    });
+                    return;
+                }
+            };
+
+            // Shutdown gave up waiting for this initialization; nobody else will stop it
+            *step.lock().unwrap() = 0;
+            let _ = sender.send(shutdown_event(shutdown(instance, &sender, &step)));
+        }));
+    }
+
+    /// Tell a running initialization not to publish its instance
+    ///
+    /// The worker shuts the instance down itself once it is created.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the instance had already been placed into `slot` (its Ready event is
+    /// queued and the caller has to shut it down)
+    pub fn cancel_initialize(&self, slot: &Mutex<Option<T>>) -> bool {
+        let slot = slot.lock().unwrap();
+        self.cancel_initialize.store(true, Ordering::Release);
+        slot.is_some()
+    }
+
+    /// Shut an instance down on a worker thread
+    ///
+    /// Reports Stopping (one detail per subsystem), then Uninitialized or Failed
+    /// naming the subsystem that failed to stop. `poll_timeout()` reports Failed
+    /// if the worker is still busy after `timeout`.
+    pub fn spawn_shutdown(&mut self, instance: T, timeout: Duration) {
+        let sender = self.sender.clone();
+        let step = Arc::clone(&self.shutdown_step);
+        *step.lock().unwrap() = 0;
+        self.shutdown_deadline = Some((Instant::now() + timeout, timeout));
+        self.worker = Some(std::thread::spawn(move || {
+            let _ = sender.send(shutdown_event(shutdown(instance, &sender, &step)));
+        }));
+    }
+
+    /// Next event reported since the last call
+    pub fn try_next(&self) -> Option<StateEvent> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Whether a worker thread is still running
+    pub fn is_working(&self) -> bool {
+        self.worker.as_ref().is_some_and(|w| !w.is_finished())
+    }
+
+    /// Failed event if the running shutdown passed its deadline (reported once)
+    ///
+    /// The worker keeps running; if it finishes later, its final state follows.
+    pub fn poll_timeout(&mut self) -> Option<StateEvent> {
+        let (deadline, timeout) = self.shutdown_deadline?;
+        if !self.is_working() {
+            self.shutdown_deadline = None;
+            return None;
+        }
+        if Instant::now() < deadline {
+            return None;
+        }
+        self.shutdown_deadline = None;
+        let subsystem = SHUTDOWN_STEPS[*self.shutdown_step.lock().unwrap()];
+        Some(StateEvent {
+            state: FeagiState::Failed,
+            detail: format!("Shutdown timed out in {}", subsystem),
+            error: format!(
+                "{} did not stop within {}ms",
+                subsystem,
+                timeout.as_millis()
+            ),
+        })
+    }
+
+    /// Block until the worker finishes, or until `timeout` passes (None = no limit)
+    ///
+    /// # Returns
+    ///
+    /// `true` if no worker is running anymore
+    pub fn wait(&self, timeout: Option<Duration>) -> bool {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        while self.is_working() {
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return false;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        true
+    }
+}
+
+impl<T: ManagedInstance> Default for Lifecycle<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Worker side of initialization (the `create` step of `spawn_initialize()`)
+///
+/// # Returns
+///
+/// The ready instance and the detail text of the Ready event
+pub fn initialize(
+    source: ConfigSource,
+    progress: &dyn Fn(&str),
+    exchange: &Arc<FrameExchange>,
+) -> anyhow::Result<(FeagiInstance, String)> {
+    let config = match source {
+        ConfigSource::Config(config) => config,
+        ConfigSource::File(path) => {
+            progress(&format!("Loading configuration {}", path));
+            feagi::load_config(Some(std::path::Path::new(&path)), None)?
+        }
+    };
+
+    progress("Creating FEAGI instance");
+    eprintln!("[GDX-INIT] Calling FeagiInstance::new()...");
+    let mut feagi = FeagiInstance::new(config)?;
+
+    progress("Starting services (HTTP API, WebSocket transports)");
+    eprintln!("[GDX-INIT] ✅ FeagiInstance created, calling initialize()...");
+    feagi.initialize()?;
+
+    progress("Connecting in-process visualization");
+    let visualization = connect_visualization(&mut feagi, exchange);
+
+    let detail = format!("HTTP API: {}, {}", feagi.get_api_url(), visualization);
+    Ok((feagi, detail))
+}
+
+/// Point the burst engine's visualization callback at `exchange`
+///
+/// # Returns
+///
+/// Detail text for the Ready event
+#[cfg(feature = "visualization-callback")]
+fn connect_visualization(feagi: &mut FeagiInstance, exchange: &Arc<FrameExchange>) -> String {
+    let exchange = Arc::clone(exchange);
+    match feagi.set_visualization_callback(move |packet: &[u8]| exchange.publish(packet)) {
+        Ok(_) => "in-process visualization connected".to_string(),
+        Err(e) => format!("in-process visualization unavailable ({})", e),
+    }
+}
+
+/// Built without `visualization-callback`: nothing is published to the exchange
+#[cfg(not(feature = "visualization-callback"))]
+fn connect_visualization(_feagi: &mut FeagiInstance, _exchange: &Arc<FrameExchange>) -> String {
+    "in-process visualization unavailable (built without the visualization-callback feature)"
+        .to_string()
+}
+
+/// Final event of a shutdown worker
+fn shutdown_event(result: Result<(), (&'static str, String)>) -> StateEvent {
+    match result {
+        Ok(()) => StateEvent {
+            state: FeagiState::Uninitialized,
+            detail: "Shutdown complete".to_string(),
+            error: String::new(),
+        },
+        Err((subsystem, e)) => StateEvent {
+            state: FeagiState::Failed,
+            detail: format!("Shutdown failed in {}", subsystem),
+            error: format!("{} failed to stop: {}", subsystem, e),
+        },
+    }
+}
+
+/// Worker side of shutdown: stop each subsystem in SHUTDOWN_STEPS order
+///
+/// # Returns
+///
+/// The failing subsystem and its error
+fn shutdown<T: ManagedInstance>(
+    instance: T,
+    sender: &Sender<StateEvent>,
+    step: &Mutex<usize>,
+) -> Result<(), (&'static str, String)> {
+    let enter = |index: usize, detail: &str| {
+        *step.lock().unwrap() = index;
+        let _ = sender.send(StateEvent {
+            state: FeagiState::Stopping,
+            detail: detail.to_string(),
+            error: String::new(),
+        });
+    };
+
+    enter(0, "Stopping burst engine");
+    if instance.is_running() {
+        instance.stop().map_err(|e| (SHUTDOWN_STEPS[0], e))?;
+    }
+
+    enter(1, "Stopping services (WebSocket transports, state)");
+    instance.shutdown().map_err(|e| (SHUTDOWN_STEPS[1], e))?;
+
+    enter(2, "Waiting for the HTTP API to close");
+    if instance.is_http_server_running() {
+        return Err((
+            SHUTDOWN_STEPS[2],
+            "still listening after shutdown".to_string(),
+        ));
+    }
+    drop(instance);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    /// Stand-in instance: records shutdown calls, fails or hangs on request
+    #[derive(Default)]
+    struct FakeInstance {
+        running: bool,
+        stop_error: Option<String>,
+        shutdown_error: Option<String>,
+        /// Shutdown blocks until this receives (or its sender is dropped)
+        shutdown_gate: Option<Mutex<mpsc::Receiver<()>>>,
+        http_still_running: bool,
+        shut_down: Arc<AtomicBool>,
+    }
+
+    impl ManagedInstance for FakeInstance {
+        fn is_running(&self) -> bool {
+            self.running
+        }
+
+        fn stop(&self) -> Result<(), String> {
+            self.stop_error.clone().map_or(Ok(()), Err)
+        }
+
+        fn shutdown(&self) -> Result<(), String> {
+            if let Some(gate) = &self.shutdown_gate {
+                let _ = gate.lock().unwrap().recv();
+            }
+            self.shut_down.store(true, Ordering::SeqCst);
+            self.shutdown_error.clone().map_or(Ok(()), Err)
+        }
+
+        fn is_http_server_running(&self) -> bool {
+            self.http_still_running
+        }
+    }
+
+    const WAIT: Option<Duration> = Some(Duration::from_secs(5));
+
+    fn drain(lifecycle: &Lifecycle<FakeInstance>) -> Vec<(FeagiState, String, String)> {
+        std::iter::from_fn(|| lifecycle.try_next())
+            .map(|event| (event.state, event.detail, event.error))
+            .collect()
+    }
+
+    fn states(events: &[(FeagiState, String, String)]) -> Vec<FeagiState> {
+        events.iter().map(|(state, _, _)| *state).collect()
+    }
+
+    #[test]
+    fn initialization_reports_progress_then_publishes_instance() {
+        let mut lifecycle = Lifecycle::new();
+        let slot = Arc::new(Mutex::new(None));
+        lifecycle.spawn_initialize(
+            |progress| {
+                progress("Creating FEAGI instance");
+                Ok((FakeInstance::default(), "HTTP API: test".to_string()))
+            },
+            Arc::clone(&slot),
+        );
+        assert!(lifecycle.wait(WAIT));
+
+        let events = drain(&lifecycle);
+        assert_eq!(
+            events,
+            [
+                (
+                    FeagiState::Initializing,
+                    "Creating FEAGI instance".into(),
+                    String::new()
+                ),
+                (FeagiState::Ready, "HTTP API: test".into(), String::new()),
+            ]
+        );
+        assert!(slot.lock().unwrap().is_some());
+    }
+
+    #[test]
+    fn failed_initialization_reports_error_and_leaves_slot_empty() {
+        let mut lifecycle = Lifecycle::<FakeInstance>::new();
+        let slot = Arc::new(Mutex::new(None));
+        lifecycle.spawn_initialize(|_| Err(anyhow::anyhow!("port in use")), Arc::clone(&slot));
+        assert!(lifecycle.wait(WAIT));
+
+        let events = drain(&lifecycle);
+        assert_eq!(
+            events,
+            [(
+                FeagiState::Failed,
+                "Initialization failed".into(),
+                "port in use".into()
+            )]
+        );
+        assert!(slot.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn shutdown_steps_through_each_subsystem() {
+        let shut_down = Arc::new(AtomicBool::new(false));
+        let instance = FakeInstance {
+            running: true,
+            shut_down: Arc::clone(&shut_down),
+            ..Default::default()
+        };
+        let mut lifecycle = Lifecycle::new();
+        lifecycle.spawn_shutdown(instance, Duration::from_secs(5));
+        assert!(lifecycle.wait(WAIT));
+
+        let events = drain(&lifecycle);
+        assert_eq!(
+            states(&events),
+            [
+                FeagiState::Stopping,
+                FeagiState::Stopping,
+                FeagiState::Stopping,
+                FeagiState::Uninitialized
+            ]
+        );
+        assert!(shut_down.load(Ordering::SeqCst));
+        assert!(lifecycle.poll_timeout().is_none());
+    }
+
+    #[test]
+    fn shutdown_failure_names_the_subsystem() {
+        let cases = [
+            (
+                FakeInstance {
+                    running: true,
+                    stop_error: Some("stuck".into()),
+                    ..Default::default()
+                },
+                "burst_engine",
+            ),
+            (
+                FakeInstance {
+                    shutdown_error: Some("transport busy".into()),
+                    ..Default::default()
+                },
+                "services",
+            ),
+            (
+                FakeInstance {
+                    http_still_running: true,
+                    ..Default::default()
+                },
+                "http_server",
+            ),
+        ];
+        for (instance, subsystem) in cases {
+            let mut lifecycle = Lifecycle::new();
+            lifecycle.spawn_shutdown(instance, Duration::from_secs(5));
+            assert!(lifecycle.wait(WAIT));
+
+            let events = drain(&lifecycle);
+            let (state, detail, error) = events.last().unwrap();
+            assert_eq!(*state, FeagiState::Failed);
+            assert_eq!(*detail, format!("Shutdown failed in {}", subsystem));
+            assert!(error.starts_with(subsystem), "{}", error);
+        }
+    }
+
+    #[test]
+    fn stopped_burst_engine_is_not_stopped_again() {
+        let instance = FakeInstance {
+            running: false,
+            stop_error: Some("not running".into()),
+            ..Default::default()
+        };
+        let mut lifecycle = Lifecycle::new();
+        lifecycle.spawn_shutdown(instance, Duration::from_secs(5));
+        assert!(lifecycle.wait(WAIT));
+        assert_eq!(
+            drain(&lifecycle).last().unwrap().0,
+            FeagiState::Uninitialized
+        );
+    }
+
+    #[test]
+    fn hung_shutdown_times_out_once_then_reports_final_state() {
+        let (release, gate) = mpsc::channel();
+        let instance = FakeInstance {
+            shutdown_gate: Some(Mutex::new(gate)),
+            ..Default::default()
+        };
+        let mut lifecycle = Lifecycle::new();
+        lifecycle.spawn_shutdown(instance, Duration::from_millis(20));
+
+        assert!(!lifecycle.wait(Some(Duration::from_millis(50))));
+        let timeout = lifecycle.poll_timeout().expect("deadline passed");
+        assert_eq!(timeout.state, FeagiState::Failed);
+        assert_eq!(timeout.detail, "Shutdown timed out in services");
+        assert_eq!(timeout.error, "services did not stop within 20ms");
+        assert!(lifecycle.poll_timeout().is_none());
+
+        release.send(()).unwrap();
+        assert!(lifecycle.wait(WAIT));
+        assert_eq!(
+            drain(&lifecycle).last().unwrap().0,
+            FeagiState::Uninitialized
+        );
+    }
+
+    #[test]
+    fn cancelled_initialization_shuts_its_instance_down() {
+        let (release, gate) = mpsc::channel::<()>();
+        let shut_down = Arc::new(AtomicBool::new(false));
+        let instance = FakeInstance {
+            running: true,
+            shut_down: Arc::clone(&shut_down),
+            ..Default::default()
+        };
+        let mut lifecycle = Lifecycle::new();
+        let slot = Arc::new(Mutex::new(None));
+        lifecycle.spawn_initialize(
+            move |_| {
+                let _ = gate.recv();
+                Ok((instance, "HTTP API: test".to_string()))
+            },
+            Arc::clone(&slot),
+        );
+
+        // Shutdown gives up waiting, as FeagiEmbedded::shutdown() does on timeout
+        assert!(!lifecycle.wait(Some(Duration::from_millis(20))));
+        assert!(!lifecycle.cancel_initialize(&slot));
+
+        release.send(()).unwrap();
+        assert!(lifecycle.wait(WAIT));
+        let events = drain(&lifecycle);
+        assert!(!states(&events).contains(&FeagiState::Ready));
+        assert_eq!(events.last().unwrap().0, FeagiState::Uninitialized);
+        assert!(slot.lock().unwrap().is_none());
+        assert!(shut_down.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn cancel_after_publishing_leaves_the_instance_to_the_caller() {
+        let mut lifecycle = Lifecycle::new();
+        let slot = Arc::new(Mutex::new(None));
+        lifecycle.spawn_initialize(
+            |_| Ok((FakeInstance::default(), String::new())),
+            Arc::clone(&slot),
+        );
+        assert!(lifecycle.wait(WAIT));
+
+        assert!(lifecycle.cancel_initialize(&slot));
+        assert_eq!(states(&drain(&lifecycle)), [FeagiState::Ready]);
+        assert!(slot.lock().unwrap().is_some());
+    }
+
+    #[test]
+    fn next_initialization_is_not_cancelled() {
+        let mut lifecycle = Lifecycle::new();
+        let slot = Arc::new(Mutex::new(None));
+        lifecycle.cancel_initialize(&slot);
+        lifecycle.spawn_initialize(
+            |_| Ok((FakeInstance::default(), String::new())),
+            Arc::clone(&slot),
+        );
+        assert!(lifecycle.wait(WAIT));
+        assert_eq!(states(&drain(&lifecycle)), [FeagiState::Ready]);
+    }
+}
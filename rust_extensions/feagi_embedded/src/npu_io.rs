@@ -0,0 +1,227 @@
+//! Sensory injection and motor readout through the burst engine
+//!
+//! Godot arrays are converted to `NeuronVoxelXYZPArrays` and handed to the instance's
+//! `BurstLoopRunner`, which applies staged sensory voxels at the start of its next
+//! burst and keeps the motor (OPU) voxels that fired in its last burst. Nothing is
+//! serialized or sent over the sensory/motor WebSocket ports (9051/9052).
+//!
+//! The burst runner APIs are not in a pinned feagi-rs revision yet, so injection and
+//! readout need the `sensorimotor-ffi` feature; without it they report that they are
+//! unavailable. Staging Godot arrays into a frame does not depend on it.
+
+use feagi::FeagiInstance;
+use feagi_structures::genomic::cortical_area::CorticalID;
+use feagi_structures::neuron_voxels::xyzp::{
+    CorticalMappedXYZPNeuronVoxels, NeuronVoxelXYZP, NeuronVoxelXYZPArrays,
+};
+
+/// Motor voxels of one burst
+#[cfg_attr(not(feature = "sensorimotor-ffi"), allow(dead_code))]
+pub struct MotorSnapshot {
+    /// Burst that produced the activations
+    pub burst: u64,
+    pub neuron_data: CorticalMappedXYZPNeuronVoxels,
+}
+
+/// Parse a cortical ID given as base64, or as a legacy 6-character ASCII ID
+pub fn parse_cortical_id(id: &str) -> Result<CorticalID, String> {
+    CorticalID::try_from_base_64(id)
+        .or_else(|_| CorticalID::try_from_legacy_ascii(id))
+        .map_err(|e| format!("Invalid cortical ID {}: {:?}", id, e))
+}
+
+/// Add one area's voxels to a sensory frame (areas given twice are merged)
+///
+/// # Returns
+///
+/// Number of voxels added, or an error if the arrays differ in length or hold a
+/// negative coordinate
+pub fn stage_area(
+    frame: &mut CorticalMappedXYZPNeuronVoxels,
+    cortical_id: &str,
+    x: &[i32],
+    y: &[i32],
+    z: &[i32],
+    p: &[f32],
+) -> Result<usize, String> {
+    let n = x.len();
+    if y.len() != n || z.len() != n || p.len() != n {
+        return Err(format!(
+            "Area {}: array lengths differ (x={}, y={}, z={}, p={})",
+            cortical_id,
+            n,
+            y.len(),
+            z.len(),
+            p.len()
+        ));
+    }
+    let id = parse_cortical_id(cortical_id)?;
+    let coordinate = |v: i32| {
+        u32::try_from(v).map_err(|_| format!("Area {}: negative coordinate {}", cortical_id, v))
+    };
+
+    let mut neurons = Vec::with_capacity(n);
+    for (((&x, &y), &z), &p) in x.iter().zip(y).zip(z).zip(p) {
+        neurons.push(NeuronVoxelXYZP::new(
+            coordinate(x)?,
+            coordinate(y)?,
+            coordinate(z)?,
+            p,
+        ));
+    }
+    match frame.mappings.get_mut(&id) {
+        Some(existing) => neurons.iter().for_each(|neuron| existing.push(neuron)),
+        None => {
+            let mut area = NeuronVoxelXYZPArrays::new();
+            neurons.iter().for_each(|neuron| area.push(neuron));
+            frame.mappings.insert(id, area);
+        }
+    }
+    Ok(n)
+}
+
+/// Queue a sensory frame for the next burst
+///
+/// # Returns
+///
+/// Number of neurons the burst engine mapped the voxels to
+#[cfg(feature = "sensorimotor-ffi")]
+pub fn inject_sensory(
+    feagi: &FeagiInstance,
+    frame: &CorticalMappedXYZPNeuronVoxels,
+) -> Result<usize, String> {
+    let runner = feagi.burst_runner();
+    let runner = runner.read();
+    runner
+        .inject_sensory_xyzp(frame)
+        .map_err(|e| format!("Sensory injection failed: {}", e))
+}
+
+/// Motor voxels of the most recent burst (None before the first burst with motor output)
+#[cfg(feature = "sensorimotor-ffi")]
+pub fn latest_motor(feagi: &FeagiInstance) -> Option<MotorSnapshot> {
+    let runner = feagi.burst_runner();
+    let runner = runner.read();
+    runner
+        .latest_motor_output()
+        .map(|(burst, neuron_data)| MotorSnapshot { burst, neuron_data })
+}
+
+/// Built without `sensorimotor-ffi`: nothing can be injected
+#[cfg(not(feature = "sensorimotor-ffi"))]
+pub fn inject_sensory(
+    _feagi: &FeagiInstance,
+    _frame: &CorticalMappedXYZPNeuronVoxels,
+) -> Result<usize, String> {
+    Err("Sensory injection unavailable (built without the sensorimotor-ffi feature)".to_string())
+}
+
+/// Built without `sensorimotor-ffi`: there is never motor output
+#[cfg(not(feature = "sensorimotor-ffi"))]
+pub fn latest_motor(_feagi: &FeagiInstance) -> Option<MotorSnapshot> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn voxels(frame: &CorticalMappedXYZPNeuronVoxels, cortical_id: &str) -> Vec<(u32, u32, u32)> {
+        let id = parse_cortical_id(cortical_id).unwrap();
+        frame.mappings.get(&id).map_or_else(Vec::new, |neurons| {
+            neurons
+                .iter()
+                .map(|neuron| {
+                    let coordinate = neuron.neuron_voxel_coordinate;
+                    (coordinate.x, coordinate.y, coordinate.z)
+                })
+                .collect()
+        })
+    }
+
+    #[test]
+    fn stage_area_adds_voxels_and_merges_repeated_areas() {
+        let mut frame = CorticalMappedXYZPNeuronVoxels::new();
+        let added = stage_area(&mut frame, "iic100", &[0, 1], &[2, 3], &[0, 0], &[1.0, 0.5]);
+        assert_eq!(added, Ok(2));
+        let added = stage_area(&mut frame, "iic100", &[4], &[5], &[1], &[0.25]);
+        assert_eq!(added, Ok(1));
+        assert_eq!(frame.mappings.len(), 1);
+        assert_eq!(
+            voxels(&frame, "iic100"),
+            vec![(0, 2, 0), (1, 3, 0), (4, 5, 1)]
+        );
+    }
+
+    #[test]
+    fn stage_area_rejects_bad_input_without_staging() {
+        let mut frame = CorticalMappedXYZPNeuronVoxels::new();
+        let mismatched = stage_area(&mut frame, "iic100", &[0, 1], &[0], &[0, 0], &[1.0, 1.0]);
+        assert!(mismatched.unwrap_err().contains("array lengths differ"));
+        let negative = stage_area(
+            &mut frame,
+            "iic100",
+            &[0, -1],
+            &[0, 0],
+            &[0, 0],
+            &[1.0, 1.0],
+        );
+        assert!(negative.unwrap_err().contains("negative coordinate -1"));
+        let unknown = stage_area(&mut frame, "not an id", &[0], &[0], &[0], &[1.0]);
+        assert!(unknown.unwrap_err().contains("Invalid cortical ID"));
+        assert!(frame.mappings.is_empty());
+    }
+
+    /// Builds the essential genome in a real instance and drives iic700 (vision_TM)
+    /// through c__for and cRSMot into omot00
+    #[cfg(all(feature = "sensorimotor-ffi", feature = "genome-ffi"))]
+    #[test]
+    fn sensory_voxels_reach_motor_output() {
+        use std::time::{Duration, Instant};
+
+        const ESSENTIAL_GENOME: &str =
+            include_str!("../../../godot_source/Resources/genomes/essential_genome.json");
+
+        let mut config = crate::FeagiEmbedded::create_embedded_config();
+        config.websocket.enabled = false;
+        config.resources.use_gpu = false;
+        config.api.port = std::net::TcpListener::bind(("127.0.0.1", 0))
+            .and_then(|listener| listener.local_addr())
+            .unwrap()
+            .port()
+            .into();
+        let mut feagi = FeagiInstance::new(config).unwrap();
+        feagi.initialize().unwrap();
+        feagi.load_genome_from_json(ESSENTIAL_GENOME).unwrap();
+        feagi.start().unwrap();
+
+        // Every voxel of the 16x16x3 vision area
+        let (mut x, mut y, mut z) = (Vec::new(), Vec::new(), Vec::new());
+        for vx in 0..16 {
+            for vy in 0..16 {
+                for vz in 0..3 {
+                    x.push(vx);
+                    y.push(vy);
+                    z.push(vz);
+                }
+            }
+        }
+        let mut frame = CorticalMappedXYZPNeuronVoxels::new();
+        stage_area(&mut frame, "iic700", &x, &y, &z, &vec![1.0; x.len()]).unwrap();
+
+        let motor = parse_cortical_id("omot00").unwrap();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let mut snapshot = None;
+        while snapshot.is_none() && Instant::now() < deadline {
+            assert!(inject_sensory(&feagi, &frame).unwrap() > 0);
+            std::thread::sleep(Duration::from_millis(20));
+            snapshot = latest_motor(&feagi)
+                .filter(|snapshot| snapshot.neuron_data.mappings.get(&motor).is_some());
+        }
+
+        feagi.stop().unwrap();
+        feagi.shutdown().unwrap();
+        let snapshot = snapshot.expect("omot00 never fired");
+        assert!(voxels(&snapshot.neuron_data, "omot00").contains(&(0, 0, 0)));
+    }
+}
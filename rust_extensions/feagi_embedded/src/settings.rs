@@ -0,0 +1,649 @@
+//! Embedded FEAGI settings as a Godot Resource
+//!
+//! `FeagiEmbeddedSettings` exposes the parts of `FeagiConfig` an embedding application
+//! usually changes (hosts, ports, burst timestep, GPU use, connectome capacity). It can
+//! be edited in the inspector, saved as `.tres`, or saved to and loaded from a
+//! feagi_configuration.toml. Anything not covered here keeps FEAGI's defaults.
+//!
+//! Validation, port assignment and the TOML round trip work on `SettingsValues`, a copy
+//! of the fields without Godot types, so they can be tested without the engine.
+
+use feagi::FeagiConfig;
+use godot::classes::{IResource, ProjectSettings, Resource};
+use godot::prelude::*;
+use std::net::TcpListener;
+use std::path::Path;
+
+/// Settings for `FeagiEmbedded.initialize_with_settings()`
+///
+/// Defaults match `initialize_default()`.
+///
+/// # GDScript Usage
+///
+/// ```gdscript
+/// var settings := FeagiEmbeddedSettings.new()
+/// settings.auto_assign_ports = true   # Run next to another BV instance
+/// settings.use_gpu = false
+/// feagi.initialize_with_settings(settings)
+/// ```
+#[derive(GodotClass)]
+#[class(base=Resource)]
+pub struct FeagiEmbeddedSettings {
+    #[base]
+    base: Base<Resource>,
+
+    /// Address the HTTP API binds to
+    #[export]
+    api_bind_host: GString,
+    /// Address advertised to clients for the HTTP API
+    #[export]
+    api_advertised_host: GString,
+    #[export]
+    api_port: i32,
+
+    /// Start the WebSocket transports (external tools; BV itself uses FFI)
+    #[export]
+    websocket_enabled: bool,
+    #[export]
+    websocket_bind_host: GString,
+    #[export]
+    websocket_advertised_host: GString,
+    #[export]
+    visualization_port: i32,
+    #[export]
+    sensory_port: i32,
+    #[export]
+    motor_port: i32,
+    #[export]
+    registration_port: i32,
+
+    /// Pick free ports for the API and WebSocket transports at initialization
+    #[export]
+    auto_assign_ports: bool,
+
+    /// Seconds per burst (0.01 = 100Hz)
+    #[export]
+    burst_timestep: f64,
+
+    #[export]
+    use_gpu: bool,
+    /// Share of GPU memory FEAGI may use, in (0, 1]
+    #[export]
+    gpu_memory_fraction: f64,
+
+    /// Neuron capacity of the connectome
+    #[export]
+    neuron_space: i64,
+    /// Synapse capacity of the connectome
+    #[export]
+    synapse_space: i64,
+}
+
+#[godot_api]
+impl IResource for FeagiEmbeddedSettings {
+    fn init(base: Base<Resource>) -> Self {
+        let mut settings = Self {
+            base,
+            api_bind_host: GString::new(),
+            api_advertised_host: GString::new(),
+            api_port: 0,
+            websocket_enabled: false,
+            websocket_bind_host: GString::new(),
+            websocket_advertised_host: GString::new(),
+            visualization_port: 0,
+            sensory_port: 0,
+            motor_port: 0,
+            registration_port: 0,
+            auto_assign_ports: false,
+            burst_timestep: 0.0,
+            use_gpu: false,
+            gpu_memory_fraction: 0.0,
+            neuron_space: 0,
+            synapse_space: 0,
+        };
+        settings.set_values(SettingsValues::from_config(
+            &crate::FeagiEmbedded::create_embedded_config(),
+        ));
+        settings
+    }
+}
+
+#[godot_api]
+impl FeagiEmbeddedSettings {
+    /// Check the settings before they are handed to FEAGI
+    ///
+    /// Ports already in use are reported, so call this before FEAGI starts
+    /// (a running instance holds its own ports).
+    ///
+    /// # Returns
+    ///
+    /// One message per problem; empty if the settings are valid
+    #[func]
+    fn validate(&self) -> PackedStringArray {
+        self.values()
+            .problems()
+            .iter()
+            .map(|problem| GString::from(problem.as_str()))
+            .collect()
+    }
+
+    /// Replace every port with one that is currently free
+    ///
+    /// # Returns
+    ///
+    /// `true` if free ports were found for all transports
+    #[func]
+    fn assign_free_ports(&mut self) -> bool {
+        let mut values = self.values();
+        match values.assign_free_ports() {
+            Ok(ports) => {
+                self.set_values(values);
+                godot_print!(
+                    "🔌 Assigned free ports: API {}, WebSocket {}-{}-{}-{}",
+                    ports[0],
+                    ports[1],
+                    ports[2],
+                    ports[3],
+                    ports[4]
+                );
+                true
+            }
+            Err(e) => {
+                godot_error!("❌ Could not find free ports: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Save the settings as a FEAGI configuration file
+    ///
+    /// `auto_assign_ports` is not part of the file. Nothing is written if a value
+    /// does not fit FEAGI's configuration (see `validate()`).
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Destination feagi_configuration.toml (absolute, `res://` or `user://`)
+    ///
+    /// # Returns
+    ///
+    /// `true` if the file was written
+    #[func]
+    fn save_toml(&self, path: GString) -> bool {
+        let path = globalize(&path);
+        match self.values().save_toml(Path::new(&path)) {
+            Ok(()) => true,
+            Err(e) => {
+                godot_error!("❌ Failed to save FEAGI settings to {}: {}", path, e);
+                false
+            }
+        }
+    }
+
+    /// Load the settings from a FEAGI configuration file
+    ///
+    /// Keys missing from the file take FEAGI's defaults.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - feagi_configuration.toml (absolute, `res://` or `user://`)
+    ///
+    /// # Returns
+    ///
+    /// `true` if the file was loaded
+    #[func]
+    fn load_toml(&mut self, path: GString) -> bool {
+        let path = globalize(&path);
+        match SettingsValues::load_toml(Path::new(&path)) {
+            Ok(values) => {
+                self.set_values(values);
+                true
+            }
+            Err(e) => {
+                godot_error!("❌ Failed to load FEAGI settings from {}: {}", path, e);
+                false
+            }
+        }
+    }
+}
+
+impl FeagiEmbeddedSettings {
+    /// Pick free ports if `auto_assign_ports` is set, then validate
+    ///
+    /// # Returns
+    ///
+    /// The configuration to start FEAGI with, or every problem found
+    pub fn prepare(&mut self) -> Result<FeagiConfig, Vec<String>> {
+        if self.auto_assign_ports && !self.assign_free_ports() {
+            return Err(vec!["No free ports available".to_string()]);
+        }
+        let values = self.values();
+        let problems = values.problems();
+        if problems.is_empty() {
+            values.to_config()
+        } else {
+            Err(problems)
+        }
+    }
+
+    /// The exported fields that make up a configuration file
+    fn values(&self) -> SettingsValues {
+        SettingsValues {
+            api_bind_host: self.api_bind_host.to_string(),
+            api_advertised_host: self.api_advertised_host.to_string(),
+            api_port: self.api_port,
+            websocket_enabled: self.websocket_enabled,
+            websocket_bind_host: self.websocket_bind_host.to_string(),
+            websocket_advertised_host: self.websocket_advertised_host.to_string(),
+            visualization_port: self.visualization_port,
+            sensory_port: self.sensory_port,
+            motor_port: self.motor_port,
+            registration_port: self.registration_port,
+            burst_timestep: self.burst_timestep,
+            use_gpu: self.use_gpu,
+            gpu_memory_fraction: self.gpu_memory_fraction,
+            neuron_space: self.neuron_space,
+            synapse_space: self.synapse_space,
+        }
+    }
+
+    fn set_values(&mut self, values: SettingsValues) {
+        self.api_bind_host = GString::from(values.api_bind_host.as_str());
+        self.api_advertised_host = GString::from(values.api_advertised_host.as_str());
+        self.api_port = values.api_port;
+
+        self.websocket_enabled = values.websocket_enabled;
+        self.websocket_bind_host = GString::from(values.websocket_bind_host.as_str());
+        self.websocket_advertised_host = GString::from(values.websocket_advertised_host.as_str());
+        self.visualization_port = values.visualization_port;
+        self.sensory_port = values.sensory_port;
+        self.motor_port = values.motor_port;
+        self.registration_port = values.registration_port;
+
+        self.burst_timestep = values.burst_timestep;
+        self.use_gpu = values.use_gpu;
+        self.gpu_memory_fraction = values.gpu_memory_fraction;
+        self.neuron_space = values.neuron_space;
+        self.synapse_space = values.synapse_space;
+    }
+}
+
+/// The settings stored in a configuration file, without Godot types
+#[derive(Clone, Debug, PartialEq)]
+struct SettingsValues {
+    api_bind_host: String,
+    api_advertised_host: String,
+    api_port: i32,
+    websocket_enabled: bool,
+    websocket_bind_host: String,
+    websocket_advertised_host: String,
+    visualization_port: i32,
+    sensory_port: i32,
+    motor_port: i32,
+    registration_port: i32,
+    burst_timestep: f64,
+    use_gpu: bool,
+    gpu_memory_fraction: f64,
+    neuron_space: i64,
+    synapse_space: i64,
+}
+
+impl SettingsValues {
+    /// Copy the covered fields out of a FEAGI configuration
+    fn from_config(config: &FeagiConfig) -> Self {
+        Self {
+            api_bind_host: config.api.bind_host.clone(),
+            api_advertised_host: config.api.advertised_host.clone(),
+            api_port: config.api.port as i32,
+            websocket_enabled: config.websocket.enabled,
+            websocket_bind_host: config.websocket.bind_host.clone(),
+            websocket_advertised_host: config.websocket.advertised_host.clone(),
+            visualization_port: config.websocket.visualization_port as i32,
+            sensory_port: config.websocket.sensory_port as i32,
+            motor_port: config.websocket.motor_port as i32,
+            registration_port: config.websocket.registration_port as i32,
+            burst_timestep: config.neural.burst_engine_timestep as f64,
+            use_gpu: config.resources.use_gpu,
+            gpu_memory_fraction: config.resources.gpu_memory_fraction as f64,
+            neuron_space: config.connectome.neuron_space as i64,
+            synapse_space: config.connectome.synapse_space as i64,
+        }
+    }
+
+    /// FEAGI configuration with these settings applied over FEAGI's defaults
+    ///
+    /// # Returns
+    ///
+    /// The configuration, or one message per value FEAGI's configuration cannot hold
+    fn to_config(&self) -> Result<FeagiConfig, Vec<String>> {
+        let mut unfit = Vec::new();
+        let mut config = FeagiConfig::default();
+
+        config.api.bind_host = self.api_bind_host.clone();
+        config.api.advertised_host = self.api_advertised_host.clone();
+        config.api.port = fit("api_port", self.api_port.into(), &mut unfit);
+
+        config.websocket.enabled = self.websocket_enabled;
+        config.websocket.bind_host = self.websocket_bind_host.clone();
+        config.websocket.advertised_host = self.websocket_advertised_host.clone();
+        config.websocket.visualization_port = fit(
+            "visualization_port",
+            self.visualization_port.into(),
+            &mut unfit,
+        );
+        config.websocket.sensory_port = fit("sensory_port", self.sensory_port.into(), &mut unfit);
+        config.websocket.motor_port = fit("motor_port", self.motor_port.into(), &mut unfit);
+        config.websocket.registration_port = fit(
+            "registration_port",
+            self.registration_port.into(),
+            &mut unfit,
+        );
+
+        config.neural.burst_engine_timestep = self.burst_timestep as _;
+        config.resources.use_gpu = self.use_gpu;
+        config.resources.gpu_memory_fraction = self.gpu_memory_fraction as _;
+        config.connectome.neuron_space = fit("neuron_space", self.neuron_space, &mut unfit);
+        config.connectome.synapse_space = fit("synapse_space", self.synapse_space, &mut unfit);
+
+        if unfit.is_empty() {
+            Ok(config)
+        } else {
+            Err(unfit)
+        }
+    }
+
+    /// Every problem with the settings (empty if they are valid)
+    fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        let mut hosts = vec![("api_bind_host", &self.api_bind_host)];
+        let mut ports = vec![("api_port", self.api_port, &self.api_bind_host)];
+        if self.websocket_enabled {
+            let ws_host = &self.websocket_bind_host;
+            hosts.push(("websocket_bind_host", ws_host));
+            ports.extend([
+                ("visualization_port", self.visualization_port, ws_host),
+                ("sensory_port", self.sensory_port, ws_host),
+                ("motor_port", self.motor_port, ws_host),
+                ("registration_port", self.registration_port, ws_host),
+            ]);
+        }
+
+        for (name, host) in &hosts {
+            if host.is_empty() || host.contains(char::is_whitespace) {
+                problems.push(format!("{} \"{}\" is not a valid host", name, host));
+            }
+        }
+        for (i, &(name, port, host)) in ports.iter().enumerate() {
+            let Ok(port) = u16::try_from(port) else {
+                problems.push(format!("{} {} is outside 1-65535", name, port));
+                continue;
+            };
+            if port == 0 {
+                problems.push(format!(
+                    "{} is 0 (set a port or enable auto_assign_ports)",
+                    name
+                ));
+                continue;
+            }
+            if let Some(&(other, _, _)) = ports[..i].iter().find(|p| p.1 == port as i32) {
+                problems.push(format!("{} {} is also used by {}", name, port, other));
+                continue;
+            }
+            if let Err(e) = TcpListener::bind((host.as_str(), port)) {
+                problems.push(format!(
+                    "{} {} on {} is not available: {}",
+                    name, port, host, e
+                ));
+            }
+        }
+
+        if !(self.burst_timestep.is_finite() && self.burst_timestep > 0.0) {
+            problems.push(format!(
+                "burst_timestep {} must be a positive number of seconds",
+                self.burst_timestep
+            ));
+        }
+        if !(self.gpu_memory_fraction > 0.0 && self.gpu_memory_fraction <= 1.0) {
+            problems.push(format!(
+                "gpu_memory_fraction {} is outside (0, 1]",
+                self.gpu_memory_fraction
+            ));
+        }
+        if self.neuron_space <= 0 {
+            problems.push(format!(
+                "neuron_space {} must be positive",
+                self.neuron_space
+            ));
+        }
+        if self.synapse_space <= 0 {
+            problems.push(format!(
+                "synapse_space {} must be positive",
+                self.synapse_space
+            ));
+        }
+
+        // Values FEAGI's configuration cannot hold, unless already reported above
+        if let Err(unfit) = self.to_config() {
+            for problem in unfit {
+                let field = problem.split(' ').next().unwrap_or_default();
+                if !problems.iter().any(|p| p.split(' ').next() == Some(field)) {
+                    problems.push(problem);
+                }
+            }
+        }
+
+        problems
+    }
+
+    /// Replace every port with one that is currently free
+    ///
+    /// # Returns
+    ///
+    /// The API, visualization, sensory, motor and registration ports
+    fn assign_free_ports(&mut self) -> std::io::Result<[u16; 5]> {
+        let api_host = self.api_bind_host.as_str();
+        let ws_host = self.websocket_bind_host.as_str();
+        // Keep every listener open until all ports are known so they are distinct
+        let listeners = [api_host, ws_host, ws_host, ws_host, ws_host]
+            .iter()
+            .map(|host| TcpListener::bind((*host, 0)))
+            .collect::<std::io::Result<Vec<TcpListener>>>()?;
+        let mut ports = [0u16; 5];
+        for (port, listener) in ports.iter_mut().zip(&listeners) {
+            *port = listener.local_addr()?.port();
+        }
+        self.api_port = ports[0].into();
+        self.visualization_port = ports[1].into();
+        self.sensory_port = ports[2].into();
+        self.motor_port = ports[3].into();
+        self.registration_port = ports[4].into();
+        Ok(ports)
+    }
+
+    /// Write the settings as feagi_configuration.toml
+    fn save_toml(&self, path: &Path) -> Result<(), String> {
+        let config = self.to_config().map_err(|unfit| unfit.join("; "))?;
+        let text = toml::to_string_pretty(&config).map_err(|e| e.to_string())?;
+        std::fs::write(path, text).map_err(|e| e.to_string())
+    }
+
+    /// Read the settings from feagi_configuration.toml (missing keys take FEAGI's defaults)
+    fn load_toml(path: &Path) -> Result<Self, String> {
+        feagi::load_config(Some(path), None)
+            .map(|config| Self::from_config(&config))
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// `value` converted to the type of a FEAGI configuration field
+///
+/// A value that does not fit is recorded in `unfit` and replaced by the type's default.
+fn fit<T: TryFrom<i64> + Default>(name: &str, value: i64, unfit: &mut Vec<String>) -> T {
+    T::try_from(value).unwrap_or_else(|_| {
+        unfit.push(format!(
+            "{} {} does not fit FEAGI's configuration",
+            name, value
+        ));
+        T::default()
+    })
+}
+
+/// Absolute filesystem path of an absolute, `res://` or `user://` path
+fn globalize(path: &GString) -> String {
+    ProjectSettings::singleton()
+        .globalize_path(path)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Embedded defaults on free ports
+    fn valid() -> SettingsValues {
+        let mut values =
+            SettingsValues::from_config(&crate::FeagiEmbedded::create_embedded_config());
+        values.assign_free_ports().unwrap();
+        values
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!(
+            "feagi_embedded_{}_{}.toml",
+            name,
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn defaults_on_free_ports_are_valid() {
+        assert_eq!(valid().problems(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn duplicate_ports_are_reported() {
+        let mut values = valid();
+        values.motor_port = values.sensory_port;
+        let problems = values.problems();
+        assert_eq!(problems.len(), 1, "{:?}", problems);
+        assert!(problems[0].starts_with("motor_port"));
+        assert!(problems[0].contains("also used by sensory_port"));
+    }
+
+    #[test]
+    fn out_of_range_ports_are_reported() {
+        let mut values = valid();
+        values.api_port = 70_000;
+        values.registration_port = -1;
+        values.visualization_port = 0;
+        let problems = values.problems();
+        assert!(problems.contains(&"api_port 70000 is outside 1-65535".to_string()));
+        assert!(problems.contains(&"registration_port -1 is outside 1-65535".to_string()));
+        assert!(problems
+            .iter()
+            .any(|p| p.starts_with("visualization_port is 0")));
+        // One message per field: the unfit conversion is not reported again
+        assert_eq!(problems.len(), 3, "{:?}", problems);
+    }
+
+    #[test]
+    fn ports_of_disabled_websockets_are_not_checked() {
+        let mut values = valid();
+        values.websocket_enabled = false;
+        values.sensory_port = -1;
+        values.motor_port = values.api_port;
+        assert_eq!(values.problems(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn bad_timesteps_are_reported() {
+        for timestep in [0.0, -0.01, f64::NAN, f64::INFINITY] {
+            let mut values = valid();
+            values.burst_timestep = timestep;
+            let problems = values.problems();
+            assert_eq!(problems.len(), 1, "{}: {:?}", timestep, problems);
+            assert!(problems[0].starts_with("burst_timestep"));
+        }
+    }
+
+    #[test]
+    fn capacities_are_converted_with_checks() {
+        let mut values = valid();
+        values.neuron_space = -5;
+        let problems = values.problems();
+        assert_eq!(
+            problems,
+            vec!["neuron_space -5 must be positive".to_string()]
+        );
+
+        // Either FEAGI's type holds the value exactly, or validation reports it
+        values.neuron_space = i64::MAX;
+        match values.to_config() {
+            Ok(config) => {
+                assert_eq!(config.connectome.neuron_space as i128, i64::MAX as i128);
+                assert!(values.problems().is_empty());
+            }
+            Err(unfit) => {
+                assert_eq!(unfit.len(), 1);
+                assert!(unfit[0].starts_with("neuron_space"));
+                assert_eq!(values.problems(), unfit);
+            }
+        }
+    }
+
+    #[test]
+    fn save_then_load_toml_is_lossless() {
+        let mut values = valid();
+        values.api_bind_host = "0.0.0.0".to_string();
+        values.api_advertised_host = "192.168.1.20".to_string();
+        values.websocket_enabled = false;
+        values.websocket_bind_host = "0.0.0.0".to_string();
+        values.websocket_advertised_host = "bv.local".to_string();
+        // Exact in f32 too, in case FEAGI stores these single precision
+        values.burst_timestep = 0.0625;
+        values.gpu_memory_fraction = 0.5;
+        values.use_gpu = false;
+        values.neuron_space = 123_456;
+        values.synapse_space = 7_654_321;
+
+        let path = temp_path("round_trip");
+        values.save_toml(&path).unwrap();
+        let loaded = SettingsValues::load_toml(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, Ok(values));
+    }
+
+    #[test]
+    fn unfit_values_are_not_saved() {
+        let mut values = valid();
+        values.api_port = -1;
+        let path = temp_path("unfit");
+        assert!(values.save_toml(&path).is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn assigned_ports_are_distinct_and_bindable() {
+        let mut values = valid();
+        let ports = values.assign_free_ports().unwrap();
+        let mut distinct = ports.to_vec();
+        distinct.sort_unstable();
+        distinct.dedup();
+        assert_eq!(distinct.len(), 5, "{:?}", ports);
+        assert_eq!(
+            [
+                values.api_port,
+                values.visualization_port,
+                values.sensory_port,
+                values.motor_port,
+                values.registration_port,
+            ],
+            ports.map(i32::from)
+        );
+        let listeners: Vec<TcpListener> = ports
+            .iter()
+            .map(|&port| TcpListener::bind(("127.0.0.1", port)).unwrap())
+            .collect();
+        assert_eq!(listeners.len(), 5);
+    }
+}
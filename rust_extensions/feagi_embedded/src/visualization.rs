@@ -0,0 +1,315 @@
+//! In-process visualization hand-off
+//!
+//! The burst engine publishes one visualization packet per burst on the FEAGI thread.
+//! `FrameExchange` carries the newest packet to Godot's main thread without locks:
+//! a double buffer (back buffer written by FEAGI, front buffer read by Godot) plus a
+//! hand-off slot that the two sides swap buffers through. Neither side ever waits for
+//! the other, and a burst that arrives before the main thread polls replaces the
+//! unread one (visualization only cares about the latest frame).
+//!
+//! `VisualizationFrame` flattens a packet into the arrays of the `visualization_data`
+//! signal.
+
+use std::cell::UnsafeCell;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+
+use feagi_serialization::{FeagiByteContainer, FeagiByteStructureType, FeagiSerializable};
+use feagi_structures::neuron_voxels::xyzp::CorticalMappedXYZPNeuronVoxels;
+
+/// Low bits of `shared`: index of the buffer in the hand-off slot
+const INDEX_MASK: u8 = 0b11;
+/// Set in `shared` while the hand-off slot holds a frame the consumer has not taken
+const FRESH_BIT: u8 = 0b100;
+
+/// Raw Type 11 structure (CorticalMappedXYZPNeuronVoxels) type byte
+const RAW_TYPE_11: u8 = 11;
+
+/// Latest-frame exchange between one producer thread and one consumer thread
+///
+/// Buffers are reused, so after warm-up publishing a frame does not allocate unless
+/// the packet grows.
+pub struct FrameExchange {
+    buffers: [UnsafeCell<Vec<u8>>; 3],
+    /// Buffer owned by the producer (only touched by `publish`)
+    back: AtomicU8,
+    /// Buffer owned by the consumer (only touched by `take`)
+    front: AtomicU8,
+    /// Buffer in the hand-off slot, plus FRESH_BIT
+    shared: AtomicU8,
+    published: AtomicU64,
+    consumed: AtomicU64,
+    overwritten: AtomicU64,
+}
+
+impl Default for FrameExchange {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// SAFETY: each buffer is owned by exactly one side at a time. Ownership only moves
+// through the atomic swap on `shared`, whose AcqRel ordering publishes the writes made
+// to a buffer before it is handed over.
+unsafe impl Sync for FrameExchange {}
+
+impl FrameExchange {
+    pub fn new() -> Self {
+        Self {
+            buffers: [
+                UnsafeCell::new(Vec::new()),
+                UnsafeCell::new(Vec::new()),
+                UnsafeCell::new(Vec::new()),
+            ],
+            back: AtomicU8::new(0),
+            shared: AtomicU8::new(1),
+            front: AtomicU8::new(2),
+            published: AtomicU64::new(0),
+            consumed: AtomicU64::new(0),
+            overwritten: AtomicU64::new(0),
+        }
+    }
+
+    /// Copy a packet into the back buffer and hand it over
+    ///
+    /// Must only be called from one thread at a time (the burst engine thread).
+    pub fn publish(&self, packet: &[u8]) {
+        let back = self.back.load(Ordering::Relaxed);
+        // SAFETY: the back buffer belongs to the producer until the swap below.
+        let buffer = unsafe { &mut *self.buffers[back as usize].get() };
+        buffer.clear();
+        buffer.extend_from_slice(packet);
+
+        let previous = self.shared.swap(back | FRESH_BIT, Ordering::AcqRel);
+        self.back.store(previous & INDEX_MASK, Ordering::Relaxed);
+        self.published.fetch_add(1, Ordering::Relaxed);
+        if previous & FRESH_BIT != 0 {
+            self.overwritten.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Run `read` on the newest unread packet, if one was published since the last call
+    ///
+    /// Must only be called from one thread at a time (Godot's main thread).
+    pub fn take<R>(&self, read: impl FnOnce(&[u8]) -> R) -> Option<R> {
+        if self.shared.load(Ordering::Acquire) & FRESH_BIT == 0 {
+            return None;
+        }
+        let front = self.front.load(Ordering::Relaxed);
+        let previous = self.shared.swap(front, Ordering::AcqRel);
+        let front = previous & INDEX_MASK;
+        self.front.store(front, Ordering::Relaxed);
+        self.consumed.fetch_add(1, Ordering::Relaxed);
+        // SAFETY: the front buffer belongs to the consumer until the next `take`.
+        let buffer = unsafe { &*self.buffers[front as usize].get() };
+        Some(read(buffer))
+    }
+
+    /// Frames published by the burst engine
+    pub fn published(&self) -> u64 {
+        self.published.load(Ordering::Relaxed)
+    }
+
+    /// Frames taken by the main thread
+    pub fn consumed(&self) -> u64 {
+        self.consumed.load(Ordering::Relaxed)
+    }
+
+    /// Frames replaced before the main thread polled them
+    pub fn overwritten(&self) -> u64 {
+        self.overwritten.load(Ordering::Relaxed)
+    }
+}
+
+/// One packet flattened for the `visualization_data` signal
+///
+/// Neurons of area `cortical_ids[i]` are the next `neuron_counts[i]` entries of
+/// `x`, `y`, `z` and `powers`. Vectors are reused between frames.
+#[derive(Default)]
+pub struct VisualizationFrame {
+    pub cortical_ids: Vec<String>,
+    pub neuron_counts: Vec<i32>,
+    pub x: Vec<i32>,
+    pub y: Vec<i32>,
+    pub z: Vec<i32>,
+    pub powers: Vec<f32>,
+}
+
+impl VisualizationFrame {
+    fn clear(&mut self) {
+        self.cortical_ids.clear();
+        self.neuron_counts.clear();
+        self.x.clear();
+        self.y.clear();
+        self.z.clear();
+        self.powers.clear();
+    }
+
+    /// Replace the frame with the neurons of a packet
+    ///
+    /// Accepts a FeagiByteContainer (every neuron voxel structure is read) or raw
+    /// Type 11 bytes. In-process packets are never LZ4 compressed.
+    ///
+    /// # Returns
+    ///
+    /// Error message if the packet could not be decoded (the frame is left empty)
+    pub fn decode(&mut self, packet: &[u8]) -> Result<(), String> {
+        self.clear();
+        match packet.first() {
+            None => Err("Empty visualization packet".to_string()),
+            Some(&RAW_TYPE_11) => {
+                let mut neuron_data = CorticalMappedXYZPNeuronVoxels::new();
+                neuron_data
+                    .try_deserialize_and_update_self_from_byte_slice(packet)
+                    .map_err(|e| format!("Type 11 deserialize error: {:?}", e))?;
+                self.append(&neuron_data);
+                Ok(())
+            }
+            Some(_) => self.decode_container(packet),
+        }
+    }
+
+    fn decode_container(&mut self, packet: &[u8]) -> Result<(), String> {
+        let mut container = FeagiByteContainer::new_empty();
+        let mut bytes = packet.to_vec();
+        container
+            .try_write_data_to_container_and_verify(&mut |data| {
+                std::mem::swap(data, &mut bytes);
+                Ok(())
+            })
+            .map_err(|e| format!("FeagiByteContainer error: {:?}", e))?;
+        let count = container
+            .try_get_number_contained_structures()
+            .map_err(|e| format!("FeagiByteContainer error: {:?}", e))?;
+
+        for index in 0..count {
+            // Structures other than neuron voxels (or newer than this build) are skipped
+            let Ok(structure) = container.try_create_new_struct_from_index(index) else {
+                continue;
+            };
+            if !matches!(
+                structure.get_type(),
+                FeagiByteStructureType::NeuronCategoricalXYZP
+            ) {
+                continue;
+            }
+            if let Some(neuron_data) = structure
+                .as_any()
+                .downcast_ref::<CorticalMappedXYZPNeuronVoxels>()
+            {
+                self.append(neuron_data);
+            }
+        }
+        Ok(())
+    }
+
+    fn append(&mut self, neuron_data: &CorticalMappedXYZPNeuronVoxels) {
+        for (cortical_id, neurons) in neuron_data.mappings.iter() {
+            let count = neurons.len();
+            if count == 0 {
+                continue;
+            }
+            self.cortical_ids.push(cortical_id.as_base_64());
+            self.neuron_counts.push(count as i32);
+            for neuron in neurons.iter() {
+                let coordinate = neuron.neuron_voxel_coordinate;
+                self.x.push(coordinate.x as i32);
+                self.y.push(coordinate.y as i32);
+                self.z.push(coordinate.z as i32);
+                self.powers.push(neuron.potential);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn taken(exchange: &FrameExchange) -> Option<Vec<u8>> {
+        exchange.take(|packet| packet.to_vec())
+    }
+
+    #[test]
+    fn take_is_empty_until_a_frame_is_published() {
+        let exchange = FrameExchange::new();
+        assert_eq!(taken(&exchange), None);
+        assert_eq!(exchange.consumed(), 0);
+    }
+
+    #[test]
+    fn published_frame_is_taken_once() {
+        let exchange = FrameExchange::new();
+        exchange.publish(&[1, 2, 3]);
+        assert_eq!(taken(&exchange), Some(vec![1, 2, 3]));
+        assert_eq!(taken(&exchange), None);
+        assert_eq!(exchange.published(), 1);
+        assert_eq!(exchange.consumed(), 1);
+        assert_eq!(exchange.overwritten(), 0);
+    }
+
+    #[test]
+    fn unread_frame_is_overwritten_by_the_next() {
+        let exchange = FrameExchange::new();
+        exchange.publish(&[1]);
+        exchange.publish(&[2, 2]);
+        exchange.publish(&[3, 3, 3]);
+        assert_eq!(taken(&exchange), Some(vec![3, 3, 3]));
+        assert_eq!(exchange.published(), 3);
+        assert_eq!(exchange.consumed(), 1);
+        assert_eq!(exchange.overwritten(), 2);
+    }
+
+    #[test]
+    fn buffers_are_reused_without_leaking_old_bytes() {
+        let exchange = FrameExchange::new();
+        for round in 0..10u8 {
+            let packet = vec![round; 10 - round as usize];
+            exchange.publish(&packet);
+            assert_eq!(taken(&exchange), Some(packet));
+        }
+        assert_eq!(exchange.overwritten(), 0);
+    }
+
+    #[test]
+    fn consumer_sees_whole_frames_in_publish_order() {
+        const FRAMES: u32 = 20_000;
+        let exchange = Arc::new(FrameExchange::new());
+        let producer = {
+            let exchange = Arc::clone(&exchange);
+            std::thread::spawn(move || {
+                for sequence in 1..=FRAMES {
+                    // Every byte of a frame repeats its sequence number
+                    let packet: Vec<u8> =
+                        sequence.to_le_bytes().repeat(1 + (sequence % 7) as usize);
+                    exchange.publish(&packet);
+                }
+            })
+        };
+
+        let mut last = 0;
+        loop {
+            let finished = producer.is_finished();
+            if let Some(sequence) = exchange.take(|packet| {
+                let chunks: Vec<&[u8]> = packet.chunks(4).collect();
+                assert!(
+                    chunks.windows(2).all(|pair| pair[0] == pair[1]),
+                    "torn frame"
+                );
+                u32::from_le_bytes(chunks[0].try_into().unwrap())
+            }) {
+                assert!(sequence > last, "frame {} after {}", sequence, last);
+                last = sequence;
+            }
+            if finished && last == FRAMES {
+                break;
+            }
+        }
+        producer.join().unwrap();
+        assert_eq!(exchange.published(), u64::from(FRAMES));
+        assert_eq!(
+            exchange.consumed() + exchange.overwritten(),
+            exchange.published()
+        );
+    }
+}
@@ -0,0 +1,564 @@
+//! Visualization packet detection, decompression and decode limits.
+//!
+//! Shared by `feagi_data_deserializer` and `feagi_agent_client`, which are separate cdylibs and
+//! decode the same packets. Packets reach Brain Visualizer as a plain FeagiByteContainer (SHM,
+//! WS without compression), an LZ4 block with a 4-byte little-endian size prefix or an LZ4
+//! frame wrapping the container (PNS-compressed WS), or a raw Type 11 structure when upstream
+//! unwrapped the container. This crate finds the shape, removes the compression under a size
+//! ceiling and hands the payload to the caller's decoder; parsing the payload stays with the
+//! callers, which own the feagi-serialization types. No Godot dependency, so it is tested with
+//! plain `cargo test`.
+
+use std::fmt;
+use std::io::Read;
+use std::time::Instant;
+
+/// Magic number that starts every LZ4 frame (little-endian 0x184D2204)
+pub const LZ4_FRAME_MAGIC: [u8; 4] = [0x04, 0x22, 0x4D, 0x18];
+
+/// Raw Type 11 structure (CorticalMappedXYZPNeuronVoxels) type byte
+pub const RAW_TYPE_11: u8 = 11;
+
+/// FeagiByteContainer versions recognised as an uncompressed payload
+pub const CONTAINER_VERSIONS: [u8; 2] = [2, 3];
+
+/// Whether a first byte is a known FeagiByteContainer version.
+///
+/// `const` so callers can assert at compile time that the container version their
+/// feagi-serialization build writes is recognised.
+pub const fn is_container_version(byte: u8) -> bool {
+    let mut i = 0;
+    while i < CONTAINER_VERSIONS.len() {
+        if CONTAINER_VERSIONS[i] == byte {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Transport compression found around the payload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    /// LZ4 block with a 4-byte little-endian decompressed size prefix (PNS default)
+    Lz4Block,
+    /// Standard LZ4 frame (magic 0x184D2204)
+    Lz4Frame,
+}
+
+impl Compression {
+    pub fn from_i32(compression: i32) -> Option<Self> {
+        match compression {
+            0 => Some(Compression::None),
+            1 => Some(Compression::Lz4Block),
+            2 => Some(Compression::Lz4Frame),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Compression::None => "none",
+            Compression::Lz4Block => "lz4_block",
+            Compression::Lz4Frame => "lz4_frame",
+        }
+    }
+}
+
+/// Uncompressed payload format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PayloadFormat {
+    /// FeagiByteContainer; holds the version byte
+    Container(u8),
+    RawType11,
+}
+
+impl PayloadFormat {
+    /// Format of uncompressed bytes, judged by the first byte.
+    pub fn of(bytes: &[u8]) -> Option<Self> {
+        match bytes.first().copied() {
+            Some(RAW_TYPE_11) => Some(PayloadFormat::RawType11),
+            Some(v) if is_container_version(v) => Some(PayloadFormat::Container(v)),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> String {
+        match self {
+            PayloadFormat::Container(version) => format!("container_v{}", version),
+            PayloadFormat::RawType11 => "raw_type11".to_string(),
+        }
+    }
+}
+
+/// Decode limits.
+#[derive(Clone, Copy, Debug)]
+pub struct FrameLimits {
+    /// Largest accepted decompressed payload, in bytes
+    pub max_decompressed_bytes: usize,
+    /// Most cortical areas accepted in one frame
+    pub max_areas_per_frame: usize,
+    /// Most neurons accepted in one cortical area
+    pub max_neurons_per_area: usize,
+}
+
+impl Default for FrameLimits {
+    fn default() -> Self {
+        Self {
+            max_decompressed_bytes: 256 * 1024 * 1024,
+            max_areas_per_frame: 4096,
+            max_neurons_per_area: 16 * 1024 * 1024,
+        }
+    }
+}
+
+impl FrameLimits {
+    /// Reject a frame with more cortical areas than the limit allows.
+    pub fn check_area_count(&self, areas: usize) -> Result<(), CodecError> {
+        if areas > self.max_areas_per_frame {
+            return Err(CodecError::new(
+                ErrorKind::OversizedPayload,
+                format!(
+                    "Frame holds {} cortical areas, above the limit of {}",
+                    areas, self.max_areas_per_frame
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Reject an area with more neurons than the limit allows (`area` names it in the error).
+    pub fn check_area_neurons(
+        &self,
+        neurons: usize,
+        area: impl FnOnce() -> String,
+    ) -> Result<(), CodecError> {
+        if neurons > self.max_neurons_per_area {
+            return Err(CodecError::new(
+                ErrorKind::OversizedPayload,
+                format!(
+                    "Area {} holds {} neurons, above the limit of {}",
+                    area(),
+                    neurons,
+                    self.max_neurons_per_area
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Category of a codec failure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    EmptyBuffer,
+    /// Neither a known payload format, an LZ4 frame nor a plausible LZ4 block
+    UnsupportedFormat,
+    /// Payload above the decompression ceiling or decode limits
+    OversizedPayload,
+    /// LZ4 block or frame failed to decompress
+    Lz4Failure,
+}
+
+/// A codec failure with its category and a precise message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodecError {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+impl CodecError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for CodecError {}
+
+impl From<CodecError> for String {
+    fn from(error: CodecError) -> Self {
+        error.message
+    }
+}
+
+/// Uncompressed payload bytes and the compression they were found in.
+#[derive(Debug)]
+pub struct Payload {
+    pub compression: Compression,
+    pub bytes: Vec<u8>,
+    /// Time spent decompressing (0 for uncompressed packets)
+    pub decompress_ms: f64,
+}
+
+/// Detect, decompress and decode one packet.
+///
+/// `decode` parses the uncompressed payload. An LZ4 block whose size prefix happens to start
+/// with 2, 3 or 11 looks like a plain payload; if `decode` rejects it, the packet is retried as
+/// an LZ4 block, and the first error is returned if that fails too.
+pub fn decode_packet<T, E: From<CodecError>>(
+    packet: Vec<u8>,
+    limits: &FrameLimits,
+    mut decode: impl FnMut(Payload) -> Result<T, E>,
+) -> Result<T, E> {
+    let payload = unwrap_compression(packet, limits)?;
+    let retry_as_lz4 = (payload.compression == Compression::None
+        && plausible_lz4_block(&payload.bytes, limits))
+    .then(|| payload.bytes.clone());
+
+    match decode(payload) {
+        Ok(decoded) => Ok(decoded),
+        Err(error) => {
+            let Some(original) = retry_as_lz4 else {
+                return Err(error);
+            };
+            match timed(Compression::Lz4Block, || {
+                decompress_lz4_block(&original, limits)
+            }) {
+                Ok(payload) => decode(payload).map_err(|_| error),
+                Err(_) => Err(error),
+            }
+        }
+    }
+}
+
+/// Identify the transport compression of a packet and remove it.
+///
+/// Packets starting with a known payload format byte are passed through unchanged.
+pub fn unwrap_compression(packet: Vec<u8>, limits: &FrameLimits) -> Result<Payload, CodecError> {
+    if packet.is_empty() {
+        return Err(CodecError::new(ErrorKind::EmptyBuffer, "Empty buffer"));
+    }
+    if packet.starts_with(&LZ4_FRAME_MAGIC) {
+        return timed(Compression::Lz4Frame, || {
+            decompress_lz4_frame(&packet, limits)
+        });
+    }
+    if PayloadFormat::of(&packet).is_some() {
+        return Ok(Payload {
+            compression: Compression::None,
+            bytes: packet,
+            decompress_ms: 0.0,
+        });
+    }
+
+    match lz4_block_declared_size(&packet) {
+        Some(declared) if declared > limits.max_decompressed_bytes => Err(CodecError::new(
+            ErrorKind::OversizedPayload,
+            format!(
+                "Unknown payload format (first byte {}): not a FeagiByteContainer (2, 3), raw Type 11 (11) \
+                 or LZ4 frame, and as an LZ4 block it would declare {} decompressed bytes, above the {} byte ceiling",
+                packet[0], declared, limits.max_decompressed_bytes
+            ),
+        )),
+        Some(_) => timed(Compression::Lz4Block, || {
+            decompress_lz4_block(&packet, limits)
+        })
+        .map_err(|e| {
+            CodecError::new(
+                e.kind,
+                format!(
+                    "Unknown payload format (first byte {}): not a FeagiByteContainer (2, 3), raw Type 11 (11) \
+                     or LZ4 frame, and LZ4 block decompression failed: {}",
+                    packet[0], e.message
+                ),
+            )
+        }),
+        None => Err(CodecError::new(
+            ErrorKind::UnsupportedFormat,
+            format!(
+                "Unknown payload format (first byte {}, {} bytes): too short for an LZ4 block and not a \
+                 FeagiByteContainer (2, 3), raw Type 11 (11) or LZ4 frame",
+                packet[0],
+                packet.len()
+            ),
+        )),
+    }
+}
+
+fn timed(
+    compression: Compression,
+    decompress: impl FnOnce() -> Result<Vec<u8>, CodecError>,
+) -> Result<Payload, CodecError> {
+    let start = Instant::now();
+    let bytes = decompress()?;
+    Ok(Payload {
+        compression,
+        bytes,
+        decompress_ms: start.elapsed().as_secs_f64() * 1000.0,
+    })
+}
+
+/// Whether bytes could be an LZ4 block: the size prefix is within the ceiling and larger than
+/// the packet (LZ4 never expands a PNS payload into something smaller than its input).
+fn plausible_lz4_block(packet: &[u8], limits: &FrameLimits) -> bool {
+    matches!(
+        lz4_block_declared_size(packet),
+        Some(declared) if declared > packet.len() && declared <= limits.max_decompressed_bytes
+    )
+}
+
+/// Decompressed size declared by an LZ4 block prefix (None if the packet is too short or the
+/// prefix is zero).
+fn lz4_block_declared_size(packet: &[u8]) -> Option<usize> {
+    if packet.len() < 5 {
+        return None;
+    }
+    let declared = u32::from_le_bytes([packet[0], packet[1], packet[2], packet[3]]) as usize;
+    (declared > 0).then_some(declared)
+}
+
+fn decompress_lz4_block(packet: &[u8], limits: &FrameLimits) -> Result<Vec<u8>, CodecError> {
+    let declared = lz4_block_declared_size(packet).ok_or_else(|| {
+        CodecError::new(
+            ErrorKind::Lz4Failure,
+            "LZ4 block too short or declares 0 bytes",
+        )
+    })?;
+    if declared > limits.max_decompressed_bytes || declared > i32::MAX as usize {
+        return Err(CodecError::new(
+            ErrorKind::OversizedPayload,
+            format!(
+                "LZ4 block declares {} bytes, above the {} byte ceiling",
+                declared, limits.max_decompressed_bytes
+            ),
+        ));
+    }
+    lz4::block::decompress(packet, None)
+        .map_err(|e| CodecError::new(ErrorKind::Lz4Failure, format!("LZ4 block: {}", e)))
+}
+
+fn decompress_lz4_frame(packet: &[u8], limits: &FrameLimits) -> Result<Vec<u8>, CodecError> {
+    let lz4_failure =
+        |e: std::io::Error| CodecError::new(ErrorKind::Lz4Failure, format!("LZ4 frame: {}", e));
+    let decoder = lz4::Decoder::new(packet).map_err(lz4_failure)?;
+    let mut out = Vec::new();
+    decoder
+        .take(limits.max_decompressed_bytes as u64 + 1)
+        .read_to_end(&mut out)
+        .map_err(lz4_failure)?;
+    if out.len() > limits.max_decompressed_bytes {
+        return Err(CodecError::new(
+            ErrorKind::OversizedPayload,
+            format!(
+                "LZ4 frame decompresses to more than the {} byte ceiling",
+                limits.max_decompressed_bytes
+            ),
+        ));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    /// Stand-in container payload: version byte 2, then filler
+    fn container(len: usize) -> Vec<u8> {
+        let mut bytes = vec![2u8];
+        bytes.extend((1..len).map(|i| (i % 7) as u8));
+        bytes
+    }
+
+    fn lz4_block(payload: &[u8]) -> Vec<u8> {
+        lz4::block::compress(payload, None, true).unwrap()
+    }
+
+    fn lz4_frame(payload: &[u8]) -> Vec<u8> {
+        let mut encoder = lz4::EncoderBuilder::new().build(Vec::new()).unwrap();
+        encoder.write_all(payload).unwrap();
+        let (out, result) = encoder.finish();
+        result.unwrap();
+        out
+    }
+
+    /// Decode that accepts only payloads of `len` bytes with a known format
+    fn expect_len(len: usize) -> impl FnMut(Payload) -> Result<Payload, String> {
+        move |payload| match PayloadFormat::of(&payload.bytes) {
+            Some(_) if payload.bytes.len() == len => Ok(payload),
+            _ => Err(format!("bad payload of {} bytes", payload.bytes.len())),
+        }
+    }
+
+    #[test]
+    fn uncompressed_payloads_pass_through() {
+        let limits = FrameLimits::default();
+        for bytes in [container(40), vec![3, 0, 0], vec![RAW_TYPE_11, 1, 2]] {
+            let payload = unwrap_compression(bytes.clone(), &limits).unwrap();
+            assert_eq!(payload.compression, Compression::None);
+            assert_eq!(payload.bytes, bytes);
+        }
+        assert_eq!(PayloadFormat::of(&[3]), Some(PayloadFormat::Container(3)));
+        assert_eq!(
+            PayloadFormat::of(&[RAW_TYPE_11]),
+            Some(PayloadFormat::RawType11)
+        );
+        assert_eq!(PayloadFormat::of(&[7]), None);
+    }
+
+    #[test]
+    fn empty_and_short_unknown_packets_are_rejected() {
+        let limits = FrameLimits::default();
+        let empty = unwrap_compression(Vec::new(), &limits).unwrap_err();
+        assert_eq!(empty.kind, ErrorKind::EmptyBuffer);
+        let short = unwrap_compression(vec![0xFF, 1, 2], &limits).unwrap_err();
+        assert_eq!(short.kind, ErrorKind::UnsupportedFormat);
+    }
+
+    #[test]
+    fn lz4_block_is_decompressed() {
+        let payload = container(1000);
+        let packet = lz4_block(&payload);
+        assert!(
+            PayloadFormat::of(&packet).is_none(),
+            "prefix 0xE8 is not a format byte"
+        );
+
+        let decoded = decode_packet(packet, &FrameLimits::default(), expect_len(1000)).unwrap();
+        assert_eq!(decoded.compression, Compression::Lz4Block);
+        assert_eq!(decoded.bytes, payload);
+    }
+
+    #[test]
+    fn lz4_frame_is_decompressed() {
+        let payload = container(1000);
+        let decoded = decode_packet(
+            lz4_frame(&payload),
+            &FrameLimits::default(),
+            expect_len(1000),
+        )
+        .unwrap();
+        assert_eq!(decoded.compression, Compression::Lz4Frame);
+        assert_eq!(decoded.bytes, payload);
+    }
+
+    #[test]
+    fn corrupt_lz4_is_an_lz4_failure() {
+        let limits = FrameLimits::default();
+        let mut block = lz4_block(&container(1000));
+        block.truncate(8);
+        assert_eq!(
+            unwrap_compression(block, &limits).unwrap_err().kind,
+            ErrorKind::Lz4Failure
+        );
+        let mut frame = lz4_frame(&container(1000));
+        frame[4] = 0; // frame descriptor with version 0
+        assert_eq!(
+            unwrap_compression(frame, &limits).unwrap_err().kind,
+            ErrorKind::Lz4Failure
+        );
+    }
+
+    #[test]
+    fn block_declaring_more_than_the_ceiling_is_rejected_before_decompressing() {
+        let limits = FrameLimits {
+            max_decompressed_bytes: 999,
+            ..FrameLimits::default()
+        };
+        let error = unwrap_compression(lz4_block(&container(1000)), &limits).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::OversizedPayload);
+        assert!(error.message.contains("1000"), "{}", error);
+
+        // A lying prefix fails the same way without allocating the declared size
+        let lying = vec![0xFF, 0xFF, 0xFF, 0x7F, 0, 0];
+        let error = unwrap_compression(lying, &FrameLimits::default()).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::OversizedPayload);
+    }
+
+    #[test]
+    fn frame_above_the_ceiling_is_rejected() {
+        let limits = FrameLimits {
+            max_decompressed_bytes: 999,
+            ..FrameLimits::default()
+        };
+        let error = unwrap_compression(lz4_frame(&container(1000)), &limits).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::OversizedPayload);
+        assert!(unwrap_compression(lz4_frame(&container(999)), &limits).is_ok());
+    }
+
+    #[test]
+    fn block_whose_prefix_looks_like_a_container_is_retried_as_lz4() {
+        // 258 = 0x0102: the size prefix starts with 2, a container version
+        let payload = container(258);
+        let packet = lz4_block(&payload);
+        assert_eq!(packet[0], 2);
+
+        let mut attempts = Vec::new();
+        let decoded = decode_packet(packet, &FrameLimits::default(), |payload: Payload| {
+            attempts.push(payload.compression);
+            expect_len(258)(payload)
+        })
+        .unwrap();
+        assert_eq!(attempts, [Compression::None, Compression::Lz4Block]);
+        assert_eq!(decoded.compression, Compression::Lz4Block);
+        assert_eq!(decoded.bytes, payload);
+    }
+
+    #[test]
+    fn failed_retry_reports_the_first_error() {
+        let packet = lz4_block(&container(258));
+        let len = packet.len();
+        let error = decode_packet(packet, &FrameLimits::default(), expect_len(1)).unwrap_err();
+        assert_eq!(error, format!("bad payload of {} bytes", len));
+    }
+
+    #[test]
+    fn implausible_blocks_are_not_retried() {
+        // Declared size (2) below the packet length: a real container, not an LZ4 block
+        let packet = vec![2, 0, 0, 0, 9, 9, 9, 9];
+        let mut attempts = 0;
+        let error = decode_packet(packet, &FrameLimits::default(), |_| {
+            attempts += 1;
+            Err::<(), String>("bad container".to_string())
+        })
+        .unwrap_err();
+        assert_eq!(attempts, 1);
+        assert_eq!(error, "bad container");
+    }
+
+    #[test]
+    fn codec_errors_reach_the_caller_error_type() {
+        let error = decode_packet(
+            Vec::new(),
+            &FrameLimits::default(),
+            |_| Ok::<(), String>(()),
+        )
+        .unwrap_err();
+        assert_eq!(error, "Empty buffer");
+    }
+
+    #[test]
+    fn area_and_neuron_limits() {
+        let limits = FrameLimits {
+            max_areas_per_frame: 2,
+            max_neurons_per_area: 10,
+            ..FrameLimits::default()
+        };
+        assert!(limits.check_area_count(2).is_ok());
+        assert_eq!(
+            limits.check_area_count(3).unwrap_err().kind,
+            ErrorKind::OversizedPayload
+        );
+        assert!(limits
+            .check_area_neurons(10, || unreachable!("only named on failure"))
+            .is_ok());
+        let error = limits
+            .check_area_neurons(11, || "iv00_C".to_string())
+            .unwrap_err();
+        assert_eq!(error.kind, ErrorKind::OversizedPayload);
+        assert_eq!(
+            error.message,
+            "Area iv00_C holds 11 neurons, above the limit of 10"
+        );
+    }
+}
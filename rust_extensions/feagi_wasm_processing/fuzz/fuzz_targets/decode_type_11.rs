@@ -0,0 +1,24 @@
+//! Arbitrary bytes into the Type 11 decoder, with default and tight limits.
+//!
+//! Run with `cargo fuzz run decode_type_11` from `feagi_wasm_processing`.
+
+#![no_main]
+
+use feagi_wasm_processing::{decode_type_11_inner, DecodeLimits};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = decode_type_11_inner(data, &DecodeLimits::default());
+    let tight = DecodeLimits {
+        max_buffer_bytes: 4096,
+        max_areas: 4,
+        max_neurons_per_area: 16,
+    };
+    if let Ok(out) = decode_type_11_inner(data, &tight) {
+        assert!(out.areas.len() <= tight.max_areas);
+        assert!(out
+            .areas
+            .values()
+            .all(|a| a.x_array.len() <= tight.max_neurons_per_area));
+    }
+});
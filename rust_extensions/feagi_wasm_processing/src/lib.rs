@@ -19,6 +19,33 @@ pub struct DecodeOutput {
     pub error: Option<String>,
 }
 
+/// Bytes of one area header: 6-byte cortical ID, u32 data offset, u32 data length.
+const AREA_HEADER_BYTES: usize = 14;
+
+/// Bytes of one neuron across the four arrays (x, y, z as i32, potential as f32).
+const NEURON_BYTES: usize = 16;
+
+/// Resource limits applied while decoding; payloads above them are rejected.
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeLimits {
+    /// Largest accepted buffer, in bytes
+    pub max_buffer_bytes: usize,
+    /// Most cortical areas accepted in one frame
+    pub max_areas: usize,
+    /// Most neurons accepted in one cortical area
+    pub max_neurons_per_area: usize,
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        Self {
+            max_buffer_bytes: 256 * 1024 * 1024,
+            max_areas: 4096,
+            max_neurons_per_area: 16 * 1024 * 1024,
+        }
+    }
+}
+
 /// Decode FEAGI "Type 11" neuron payloads in the browser (WebAssembly).
 ///
 /// - `buffer`: byte slice containing a Type 11 message (LE-encoded header + area sections).
@@ -26,7 +53,27 @@ pub struct DecodeOutput {
 ///   Each `areas[cortical_id]` has `x_array`, `y_array`, `z_array` (i32[]) and `p_array` (f32[]).
 #[wasm_bindgen]
 pub fn decode_type_11(buffer: &[u8]) -> JsValue {
-    let result = match decode_type_11_inner(buffer) {
+    to_js(decode_type_11_inner(buffer, &DecodeLimits::default()))
+}
+
+/// Same as `decode_type_11` with caller-chosen limits (0 keeps the default of a limit).
+///
+/// - `max_areas`: most cortical areas accepted in one frame (default 4096)
+/// - `max_neurons_per_area`: most neurons accepted in one area (default 16M)
+#[wasm_bindgen]
+pub fn decode_type_11_with_limits(buffer: &[u8], max_areas: u32, max_neurons_per_area: u32) -> JsValue {
+    let mut limits = DecodeLimits::default();
+    if max_areas > 0 {
+        limits.max_areas = max_areas as usize;
+    }
+    if max_neurons_per_area > 0 {
+        limits.max_neurons_per_area = max_neurons_per_area as usize;
+    }
+    to_js(decode_type_11_inner(buffer, &limits))
+}
+
+fn to_js(result: Result<DecodeOutput, String>) -> JsValue {
+    let result = match result {
         Ok(out) => out,
         Err(e) => DecodeOutput {
             success: false,
@@ -38,10 +85,21 @@ pub fn decode_type_11(buffer: &[u8]) -> JsValue {
     serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
 }
 
-fn decode_type_11_inner(buffer: &[u8]) -> Result<DecodeOutput, String> {
+/// Decode a Type 11 buffer under `limits`.
+///
+/// Every offset and length read from the buffer is bounds- and overflow-checked, so malformed
+/// input is reported as an error and never panics (also with 32-bit `usize` on wasm32).
+pub fn decode_type_11_inner(buffer: &[u8], limits: &DecodeLimits) -> Result<DecodeOutput, String> {
     if buffer.len() < 4 {
         return Err("Buffer too small for global header".to_string());
     }
+    if buffer.len() > limits.max_buffer_bytes {
+        return Err(format!(
+            "Buffer of {} bytes exceeds the {} byte limit",
+            buffer.len(),
+            limits.max_buffer_bytes
+        ));
+    }
 
     let structure_type = buffer[0];
     let version = buffer[1];
@@ -56,10 +114,18 @@ fn decode_type_11_inner(buffer: &[u8]) -> Result<DecodeOutput, String> {
     if num_areas == 0 {
         return Err("No cortical areas in data".to_string());
     }
+    if num_areas as usize > limits.max_areas {
+        return Err(format!(
+            "{} cortical areas exceed the limit of {}",
+            num_areas, limits.max_areas
+        ));
+    }
 
     let mut pos = 4usize;
-    let area_headers_size = (num_areas as usize) * 14;
-    if pos + area_headers_size > buffer.len() {
+    let area_headers_size = (num_areas as usize)
+        .checked_mul(AREA_HEADER_BYTES)
+        .ok_or("Area header size overflows")?;
+    if area_headers_size > buffer.len() - pos {
         return Err("Buffer too small for area headers".to_string());
     }
 
@@ -80,21 +146,35 @@ fn decode_type_11_inner(buffer: &[u8]) -> Result<DecodeOutput, String> {
     let mut total_neurons: u32 = 0;
 
     for h in headers.into_iter() {
-        if h.off + h.len > buffer.len() {
-            return Err(format!("Area {} data range exceeds buffer", h.id));
-        }
-        if h.len % 16 != 0 {
+        let data = h
+            .off
+            .checked_add(h.len)
+            .and_then(|end| buffer.get(h.off..end))
+            .ok_or_else(|| format!("Area {} data range exceeds buffer", h.id))?;
+        if h.len % NEURON_BYTES != 0 {
             return Err(format!("Area {} data length {} not divisible by 16", h.id, h.len));
         }
-        let num = (h.len / 16) as u32;
-        let mut p = h.off;
-        let arr_bytes = (num as usize) * 4;
-        let x = bytes_to_i32(&buffer[p..p+arr_bytes]); p += arr_bytes;
-        let y = bytes_to_i32(&buffer[p..p+arr_bytes]); p += arr_bytes;
-        let z = bytes_to_i32(&buffer[p..p+arr_bytes]); p += arr_bytes;
-        let power = bytes_to_f32(&buffer[p..p+arr_bytes]);
-        areas.insert(h.id, AreaOutput { x_array: x, y_array: y, z_array: z, p_array: power });
-        total_neurons += num;
+        let num = h.len / NEURON_BYTES;
+        if num > limits.max_neurons_per_area {
+            return Err(format!(
+                "Area {} holds {} neurons, above the limit of {}",
+                h.id, num, limits.max_neurons_per_area
+            ));
+        }
+        let arr_bytes = num * 4;
+        let (x, rest) = data.split_at(arr_bytes);
+        let (y, rest) = rest.split_at(arr_bytes);
+        let (z, power) = rest.split_at(arr_bytes);
+        total_neurons = u32::try_from(num)
+            .ok()
+            .and_then(|num| total_neurons.checked_add(num))
+            .ok_or("Total neuron count overflows")?;
+        areas.insert(h.id, AreaOutput {
+            x_array: bytes_to_i32(x),
+            y_array: bytes_to_i32(y),
+            z_array: bytes_to_i32(z),
+            p_array: bytes_to_f32(power),
+        });
     }
 
     Ok(DecodeOutput { success: true, total_neurons, areas, error: None })
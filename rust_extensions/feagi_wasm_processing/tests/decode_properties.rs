@@ -0,0 +1,123 @@
+//! Property tests of the Type 11 decoder: arbitrary and mutated input must be rejected
+//! cleanly, and well-formed buffers must round-trip.
+
+use feagi_wasm_processing::{decode_type_11_inner, DecodeLimits};
+use proptest::prelude::*;
+
+/// One area as (6-byte ID, neurons as (x, y, z, p)).
+type Area = (String, Vec<(i32, i32, i32, f32)>);
+
+/// Build a well-formed Type 11 buffer.
+fn encode(areas: &[Area]) -> Vec<u8> {
+    let mut buffer = vec![11u8, 1];
+    buffer.extend_from_slice(&(areas.len() as u16).to_le_bytes());
+    let mut offset = 4 + areas.len() * 14;
+    for (id, neurons) in areas {
+        let mut id_bytes = [0u8; 6];
+        id_bytes[..id.len()].copy_from_slice(id.as_bytes());
+        buffer.extend_from_slice(&id_bytes);
+        buffer.extend_from_slice(&(offset as u32).to_le_bytes());
+        buffer.extend_from_slice(&((neurons.len() * 16) as u32).to_le_bytes());
+        offset += neurons.len() * 16;
+    }
+    for (_, neurons) in areas {
+        for n in neurons {
+            buffer.extend_from_slice(&n.0.to_le_bytes());
+        }
+        for n in neurons {
+            buffer.extend_from_slice(&n.1.to_le_bytes());
+        }
+        for n in neurons {
+            buffer.extend_from_slice(&n.2.to_le_bytes());
+        }
+        for n in neurons {
+            buffer.extend_from_slice(&n.3.to_le_bytes());
+        }
+    }
+    buffer
+}
+
+fn areas_strategy() -> impl Strategy<Value = Vec<Area>> {
+    prop::collection::btree_map(
+        "[a-z]{6}",
+        prop::collection::vec(
+            (any::<i32>(), any::<i32>(), any::<i32>(), -1.0f32..1.0),
+            0..32,
+        ),
+        1..8,
+    )
+    .prop_map(|areas| areas.into_iter().collect())
+}
+
+proptest! {
+    #[test]
+    fn arbitrary_bytes_never_panic(bytes in prop::collection::vec(any::<u8>(), 0..512)) {
+        let _ = decode_type_11_inner(&bytes, &DecodeLimits::default());
+    }
+
+    #[test]
+    fn arbitrary_headers_never_panic(num_areas in any::<u16>(), headers in prop::collection::vec(any::<u8>(), 0..256)) {
+        let mut bytes = vec![11u8, 1];
+        bytes.extend_from_slice(&num_areas.to_le_bytes());
+        bytes.extend_from_slice(&headers);
+        let _ = decode_type_11_inner(&bytes, &DecodeLimits::default());
+    }
+
+    #[test]
+    fn well_formed_buffers_round_trip(areas in areas_strategy()) {
+        let out = decode_type_11_inner(&encode(&areas), &DecodeLimits::default()).unwrap();
+        let total: usize = areas.iter().map(|(_, n)| n.len()).sum();
+        prop_assert_eq!(out.total_neurons as usize, total);
+        for (id, neurons) in &areas {
+            let area = &out.areas[id];
+            prop_assert_eq!(&area.x_array, &neurons.iter().map(|n| n.0).collect::<Vec<_>>());
+            prop_assert_eq!(&area.y_array, &neurons.iter().map(|n| n.1).collect::<Vec<_>>());
+            prop_assert_eq!(&area.z_array, &neurons.iter().map(|n| n.2).collect::<Vec<_>>());
+            prop_assert_eq!(&area.p_array, &neurons.iter().map(|n| n.3).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn truncated_and_mutated_buffers_never_panic(
+        areas in areas_strategy(),
+        cut in any::<prop::sample::Index>(),
+        flips in prop::collection::vec((any::<prop::sample::Index>(), any::<u8>()), 0..8),
+    ) {
+        let mut bytes = encode(&areas);
+        for (index, value) in flips {
+            let i = index.index(bytes.len());
+            bytes[i] ^= value;
+        }
+        let _ = decode_type_11_inner(&bytes, &DecodeLimits::default());
+        bytes.truncate(cut.index(bytes.len()));
+        let _ = decode_type_11_inner(&bytes, &DecodeLimits::default());
+    }
+
+    #[test]
+    fn limits_are_enforced(areas in areas_strategy()) {
+        let buffer = encode(&areas);
+        let most_neurons = areas.iter().map(|(_, n)| n.len()).max().unwrap();
+        let area_limit = DecodeLimits { max_areas: areas.len() - 1, ..DecodeLimits::default() };
+        prop_assert!(decode_type_11_inner(&buffer, &area_limit).is_err());
+        let neuron_limit = DecodeLimits { max_neurons_per_area: most_neurons, ..DecodeLimits::default() };
+        prop_assert!(decode_type_11_inner(&buffer, &neuron_limit).is_ok());
+        if most_neurons > 0 {
+            let neuron_limit = DecodeLimits { max_neurons_per_area: most_neurons - 1, ..DecodeLimits::default() };
+            prop_assert!(decode_type_11_inner(&buffer, &neuron_limit).is_err());
+        }
+    }
+}
+
+/// `off + len` used to overflow on wasm32 before the range check.
+#[test]
+fn area_range_near_usize_max_is_rejected() {
+    let mut buffer = vec![11u8, 1, 1, 0];
+    buffer.extend_from_slice(b"area00");
+    buffer.extend_from_slice(&u32::MAX.to_le_bytes());
+    buffer.extend_from_slice(&16u32.to_le_bytes());
+    buffer.extend_from_slice(&[0u8; 16]);
+    let error = decode_type_11_inner(&buffer, &DecodeLimits::default())
+        .err()
+        .unwrap();
+    assert!(error.contains("exceeds buffer"), "{}", error);
+}